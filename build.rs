@@ -0,0 +1,52 @@
+use std::{env, fs, path::Path, process::Command};
+
+/// Exposes build-time information such as the target triple and git commit to `--version`.
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    watch_git_head();
+
+    let target = env::var("TARGET").unwrap_or_else(|_| String::from("unknown"));
+    println!("cargo:rustc-env=ERDTREE_TARGET={target}");
+
+    let git_commit = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|hash| hash.trim().to_owned())
+        .filter(|hash| !hash.is_empty())
+        .unwrap_or_else(|| String::from("unknown"));
+
+    println!("cargo:rustc-env=ERDTREE_GIT_COMMIT={git_commit}");
+}
+
+/// Reruns the build script whenever the commit that `HEAD` resolves to changes, which happens
+/// either when `HEAD` itself changes or when the branch it points to moves. Nothing is watched
+/// outside of a git checkout, e.g. when building from a published crate.
+fn watch_git_head() {
+    let git_dir = Path::new(".git");
+    let head = git_dir.join("HEAD");
+
+    let Ok(contents) = fs::read_to_string(&head) else {
+        return;
+    };
+
+    println!("cargo:rerun-if-changed={}", head.display());
+
+    if let Some(reference) = contents.trim().strip_prefix("ref: ") {
+        let reference = git_dir.join(reference);
+
+        if reference.exists() {
+            println!("cargo:rerun-if-changed={}", reference.display());
+        }
+    }
+
+    // Branches that haven't moved since the last 'git gc' only exist in here.
+    let packed_refs = git_dir.join("packed-refs");
+
+    if packed_refs.exists() {
+        println!("cargo:rerun-if-changed={}", packed_refs.display());
+    }
+}
@@ -0,0 +1,44 @@
+#!/usr/bin/env bash
+
+# Compares scan times of builds using the system allocator, mimalloc, and jemalloc against a
+# generated tree. Usage: allocator_benchmarks.sh [NUM_DIRS] [FILES_PER_DIR] [-- ERD_ARGS...]
+
+if ! command -v hyperfine &> /dev/null; then
+  printf "Error: Script requires hyperfine.\n"
+  exit 1
+fi
+
+num_dirs="${1:-2000}"
+files_per_dir="${2:-1000}"
+shift 2 2> /dev/null
+
+if [[ "$1" == "--" ]]; then
+  shift
+fi
+
+bench_dir=$(mktemp -d)
+trap "rm -rf $bench_dir" EXIT
+
+printf "Generating %d directories with %d files each in %s...\n" "$num_dirs" "$files_per_dir" "$bench_dir"
+
+for ((d = 0; d < num_dirs; d++)); do
+  dir="$bench_dir/tree/$((d % 32))/$d"
+  mkdir -p "$dir"
+  (cd "$dir" && seq -f "file_%g.txt" 1 "$files_per_dir" | xargs touch)
+done
+
+for allocator in system mimalloc jemalloc; do
+  if [[ "$allocator" == "system" ]]; then
+    cargo build --release || exit 1
+  else
+    cargo build --release --features "$allocator" || exit 1
+  fi
+
+  cp target/release/erd "$bench_dir/erd-$allocator"
+done
+
+hyperfine \
+  --warmup 2 \
+  "$bench_dir/erd-system --no-config $* $bench_dir/tree" \
+  "$bench_dir/erd-mimalloc --no-config $* $bench_dir/tree" \
+  "$bench_dir/erd-jemalloc --no-config $* $bench_dir/tree"
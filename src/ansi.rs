@@ -48,6 +48,48 @@ pub trait Escaped: AsRef<str> {
 
         resultant
     }
+
+    /// Returns the amount of `char`s that would actually be rendered, ignoring color/style
+    /// escape sequences. Makes the same assumptions as [`Escaped::truncate`].
+    fn visible_len(&self) -> usize {
+        let mut len = 0;
+        let mut chars = self.as_ref().chars();
+
+        while let Some(ch) = chars.next() {
+            if ch == '\u{1b}' {
+                for code in chars.by_ref() {
+                    if code == 'm' {
+                        break;
+                    }
+                }
+                continue;
+            }
+            len += 1;
+        }
+
+        len
+    }
+
+    /// Returns a copy with all color/style escape sequences removed. Makes the same assumptions
+    /// as [`Escaped::truncate`].
+    fn strip(&self) -> String {
+        let mut resultant = String::new();
+        let mut chars = self.as_ref().chars();
+
+        while let Some(ch) = chars.next() {
+            if ch == '\u{1b}' {
+                for code in chars.by_ref() {
+                    if code == 'm' {
+                        break;
+                    }
+                }
+                continue;
+            }
+            resultant.push(ch);
+        }
+
+        resultant
+    }
 }
 
 impl Escaped for str {}
@@ -62,3 +104,21 @@ fn truncate() {
 
     assert_eq!(control, trunc);
 }
+
+#[test]
+fn visible_len() {
+    use ansi_term::Color::Red;
+
+    let base = format!("{}!!!", Red.bold().paint("Hello World"));
+
+    assert_eq!(<str as Escaped>::visible_len(&base), 14);
+}
+
+#[test]
+fn strip() {
+    use ansi_term::Color::Red;
+
+    let control = format!("{} World", Red.bold().paint("Hello"));
+
+    assert_eq!(<str as Escaped>::strip(&control), "Hello World");
+}
@@ -0,0 +1,16 @@
+use clap::ValueEnum;
+
+/// How sizes are padded to the width of the size column.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, Default)]
+pub enum Alignment {
+    /// Pad sizes on the left so that they line up on their last digit
+    #[default]
+    Right,
+
+    /// Pad sizes on the right so that they line up on their first digit, keeping units beside
+    /// their numbers
+    Left,
+
+    /// Don't pad sizes at all, not even small numbers
+    None,
+}
@@ -92,7 +92,14 @@ pub trait Reconciler: CommandFactory + FromArgMatches {
                         continue;
                     };
                     final_args.push(key);
-                    final_args.extend(raw.map(OsStr::to_os_string));
+
+                    // Values that were split on a delimiter need to be rejoined.
+                    if let Some(delim) = arg.get_value_delimiter() {
+                        let values = raw.map(OsStr::to_string_lossy).collect::<Vec<_>>();
+                        final_args.push(OsString::from(values.join(&delim.to_string())));
+                    } else {
+                        final_args.extend(raw.map(OsStr::to_os_string));
+                    }
                 },
             }
         }
@@ -0,0 +1,19 @@
+/// Names of directories that are commonly generated by build tools, package managers, and
+/// caches, which are hidden by `--no-artifacts`.
+pub const DEFAULTS: &[&str] = &[
+    "node_modules",
+    "target",
+    ".venv",
+    "venv",
+    "__pycache__",
+    ".cache",
+    ".gradle",
+    ".mypy_cache",
+    ".next",
+    ".parcel-cache",
+    ".pytest_cache",
+    ".terraform",
+    ".tox",
+    ".zig-cache",
+    "zig-cache",
+];
@@ -1,4 +1,7 @@
 use super::{Context, PrefixKind};
+
+#[cfg(unix)]
+use super::long::Column;
 use std::convert::From;
 
 /// Utility struct to help store maximum column widths for attributes of each node. Each width is
@@ -17,6 +20,12 @@ pub struct Properties {
     #[cfg(unix)]
     pub max_block_width: usize,
 
+    #[cfg(target_os = "linux")]
+    pub max_security_context_width: usize,
+
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    pub max_flags_width: usize,
+
     #[cfg(unix)]
     pub max_owner_width: usize,
 
@@ -26,15 +35,35 @@ pub struct Properties {
 
 impl From<&Context> for Properties {
     fn from(ctx: &Context) -> Self {
-        let unit_width = match ctx.unit {
-            PrefixKind::Bin if ctx.human => 3,
-            PrefixKind::Si if ctx.human => 2,
+        let unit_width = match (ctx.fixed_unit(), ctx.prefix_kind()) {
+            (Some(unit), _) => unit.as_str().len(),
+            (None, PrefixKind::Bin) if ctx.human => 3,
+            (None, PrefixKind::Si) if ctx.human => 2,
+            (None, unit) if unit.is_raw() => 0,
             _ => 1,
         };
 
-        Self {
+        let props = Self {
             max_size_unit_width: unit_width,
             ..Default::default()
+        };
+
+        // Columns are at least as wide as their labels so that '--header' lines up.
+        #[cfg(unix)]
+        if ctx.header {
+            return Self {
+                max_nlink_width: Column::Nlink.label().len(),
+                max_ino_width: Column::Ino.label().len(),
+                max_owner_width: Column::Owner.label().len(),
+                max_group_width: Column::Group.label().len(),
+                #[cfg(target_os = "linux")]
+                max_security_context_width: Column::Context.label().len(),
+                #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+                max_flags_width: Column::Flags.label().len(),
+                ..props
+            };
         }
+
+        props
     }
 }
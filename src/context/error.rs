@@ -14,6 +14,35 @@ pub enum Error {
     #[error("No glob was provided")]
     EmptyGlob,
 
+    #[error("No extensions were provided to '--ext'")]
+    EmptyExt,
+
+    #[error("Missing '--ext' argument")]
+    ExtNotProvided,
+
+    #[error("Missing '--links' argument")]
+    LinksNotProvided,
+
+    #[error("Missing '--mime' argument")]
+    MimeNotProvided,
+
+    #[error("Expected a MIME type of the form 'type/subtype' but got '{0}'")]
+    InvalidMime(String),
+
+    #[cfg(unix)]
+    #[error("Missing '--xattr' argument")]
+    XattrNotProvided,
+
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    #[error("Missing '--flags' argument")]
+    FlagsNotProvided,
+
+    #[error("'{0}' only applies to '--layout flat' and '--layout iflat'")]
+    FlatOnly(&'static str),
+
+    #[error("'{0}' can't be used with '--output jsonl' as records are written while the tree is assembled")]
+    NotStreamable(&'static str),
+
     #[error("{0}")]
     IgnoreError(#[from] IgnoreError),
 
@@ -0,0 +1,44 @@
+use crate::disk_usage::units;
+use std::str::FromStr;
+
+/// Sizes in bytes at which '--gradient' switches from green to yellow and from yellow to red.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Thresholds {
+    pub yellow: u64,
+    pub red: u64,
+}
+
+impl FromStr for Thresholds {
+    type Err = String;
+
+    /// Parses thresholds of the form `<YELLOW>,<RED>`, e.g. `1MiB,1GiB`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (yellow, red) = s
+            .split_once(',')
+            .ok_or_else(|| format!("Expected thresholds of the form 'YELLOW,RED' but got '{s}'"))?;
+
+        let yellow = units::parse_size(yellow)?;
+        let red = units::parse_size(red)?;
+
+        if yellow > red {
+            return Err(format!(
+                "The yellow threshold of '{s}' exceeds the red threshold"
+            ));
+        }
+
+        Ok(Self { yellow, red })
+    }
+}
+
+#[test]
+fn parse_thresholds() {
+    assert_eq!(
+        "1MiB,1GiB".parse(),
+        Ok(Thresholds {
+            yellow: 2_u64.pow(20),
+            red: 2_u64.pow(30)
+        })
+    );
+    assert!("1GiB,1MiB".parse::<Thresholds>().is_err());
+    assert!("1GiB".parse::<Thresholds>().is_err());
+}
@@ -0,0 +1,18 @@
+use clap::ValueEnum;
+
+/// Attribute by which '--group-by' partitions the entries of flat layouts into sections.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Key {
+    /// Group entries by the name of their owner
+    #[cfg(unix)]
+    Owner,
+
+    /// Group entries by file extension
+    Ext,
+
+    /// Group entries by their depth relative to the root
+    Depth,
+
+    /// Group entries by the directory that contains them
+    Parent,
+}
@@ -0,0 +1,75 @@
+use std::str::FromStr;
+
+/// A comparison against the amount of hard links to a file as provided to `--links`, e.g. '>1'.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Filter {
+    op: Op,
+    count: u64,
+}
+
+/// Comparison operators supported by [`Filter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl Filter {
+    /// Whether or not `nlink` satisfies the comparison.
+    pub const fn matches(&self, nlink: u64) -> bool {
+        match self.op {
+            Op::Eq => nlink == self.count,
+            Op::Gt => nlink > self.count,
+            Op::Ge => nlink >= self.count,
+            Op::Lt => nlink < self.count,
+            Op::Le => nlink <= self.count,
+        }
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    /// Parses a count optionally preceded by one of '=', '>', '>=', '<', or '<='. A bare count is
+    /// treated as an exact match.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        let (op, count) = [
+            (">=", Op::Ge),
+            ("<=", Op::Le),
+            (">", Op::Gt),
+            ("<", Op::Lt),
+            ("=", Op::Eq),
+        ]
+        .into_iter()
+        .find_map(|(sym, op)| s.strip_prefix(sym).map(|count| (op, count)))
+        .unwrap_or((Op::Eq, s));
+
+        let count = count.trim().parse::<u64>().map_err(|_| {
+            format!("expected a count optionally preceded by '=', '>', '>=', '<', or '<=': '{s}'")
+        })?;
+
+        Ok(Self { op, count })
+    }
+}
+
+#[test]
+fn parse_and_match() {
+    let filter = ">1".parse::<Filter>().unwrap();
+    assert!(filter.matches(2));
+    assert!(!filter.matches(1));
+
+    let filter = "1".parse::<Filter>().unwrap();
+    assert!(filter.matches(1));
+    assert!(!filter.matches(2));
+
+    let filter = "<= 2".parse::<Filter>().unwrap();
+    assert!(filter.matches(2));
+    assert!(!filter.matches(3));
+
+    assert!("many".parse::<Filter>().is_err());
+}
@@ -0,0 +1,75 @@
+use super::Context;
+use clap::ValueEnum;
+
+/// Columns of the long view that may be selected and reordered with '--long=COLUMNS'.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum Column {
+    /// Inode number
+    Ino,
+
+    /// Permissions in symbolic or, with '--octal', numeric notation
+    Perms,
+
+    /// Number of hardlinks to the underlying inode
+    Nlink,
+
+    /// Name or, with '--numeric-uid-gid', ID of the owner
+    Owner,
+
+    /// Name or, with '--numeric-uid-gid', ID of the group
+    Group,
+
+    /// Security context as shown by 'ls -Z'
+    #[cfg(target_os = "linux")]
+    Context,
+
+    /// File flags as shown by 'ls -lO'
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    Flags,
+
+    /// Disk usage, which is then omitted from its usual place
+    Size,
+
+    /// Timestamp selected by '--time' (alias: mtime)
+    #[value(alias("mtime"))]
+    Time,
+}
+
+impl Column {
+    /// Label of the column as printed by '--header'.
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Ino => "Inode",
+            Self::Perms => "Mode",
+            Self::Nlink => "Links",
+            Self::Owner => "Owner",
+            Self::Group => "Group",
+            #[cfg(target_os = "linux")]
+            Self::Context => "Context",
+            #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+            Self::Flags => "Flags",
+            Self::Size => "Size",
+            Self::Time => "Date",
+        }
+    }
+}
+
+/// The columns shown by a bare '--long', extended by '--ino', '--nlink', '--group', '--context',
+/// and '--file-flags'. Disk usage isn't among them as it retains its usual place in the row.
+pub fn default_columns(ctx: &Context) -> Vec<Column> {
+    [
+        (ctx.ino, Column::Ino),
+        (true, Column::Perms),
+        (ctx.nlink, Column::Nlink),
+        (true, Column::Owner),
+        (ctx.group, Column::Group),
+        #[cfg(target_os = "linux")]
+        (ctx.selinux, Column::Context),
+        #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+        (ctx.file_flags, Column::Flags),
+        (true, Column::Time),
+    ]
+    .into_iter()
+    .filter_map(|(shown, column)| shown.then_some(column))
+    .collect()
+}
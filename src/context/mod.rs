@@ -1,7 +1,11 @@
-use super::disk_usage::{file_size::DiskUsage, units::PrefixKind};
+use super::disk_usage::{
+    file_size::DiskUsage,
+    units::{self, FixedUnit, PrefixKind, Rounding, Unit},
+};
+use crate::fs::{ignored, inode::Inode};
 
 use args::Reconciler;
-use clap::{FromArgMatches, Parser};
+use clap::{ArgAction, FromArgMatches, Parser};
 use color::Coloring;
 use error::Error;
 use ignore::{
@@ -10,8 +14,9 @@ use ignore::{
 };
 use regex::Regex;
 use std::{
-    borrow::Borrow,
+    borrow::{Borrow, Cow},
     convert::From,
+    ffi::{OsStr, OsString},
     io::{stdin, stdout, IsTerminal},
     num::NonZeroUsize,
     path::{Path, PathBuf},
@@ -31,30 +36,60 @@ pub mod color;
 /// Controlling order of directories in output.
 pub mod dir;
 
+/// Alignment of sizes within their column.
+pub mod align;
+
 /// [Context] related errors.
 pub mod error;
 
 /// Common cross-platform file-types.
 pub mod file;
 
+/// Thresholds for coloring sizes by magnitude.
+pub mod gradient;
+
+/// Attributes that flat layouts can be grouped by.
+pub mod group;
+
 /// For determining the output layout.
 pub mod layout;
 
 /// Utilities to print output.
 pub mod column;
 
+/// Columns that make up the long view.
+#[cfg(unix)]
+pub mod long;
+
+/// Directories that are considered build and cache artifacts.
+pub mod artifact;
+
+/// Filtering by the amount of hard links to a file.
+pub mod links;
+
+/// Annotating entries with their share of a total size.
+pub mod percent;
+
+/// Formats that the output can be rendered in.
+pub mod output;
+
+/// Whether entries are measured and how the size of each is to be shown.
+pub mod size;
+
 /// Printing order kinds.
 pub mod sort;
 
-/// Different types of timestamps available in long view.
-#[cfg(unix)]
+/// Alternating background shading of rows.
+pub mod stripe;
+
+/// Different types of timestamps available in long view and the UTC offset to show them at.
 pub mod time;
 
 /// Defines the CLI.
 #[derive(Parser, Debug)]
 #[command(name = "erdtree")]
 #[command(author = "Benjamin Nguyen. <benjamin.van.nguyen@gmail.com>")]
-#[command(version = "3.1.2")]
+#[command(version = "3.1.2", disable_version_flag = true)]
 #[command(about = "erdtree (erd) is a cross-platform, multi-threaded, and general purpose filesystem and disk usage utility.", long_about = None)]
 pub struct Context {
     /// Directory to traverse; defaults to current working directory
@@ -68,14 +103,33 @@ pub struct Context {
     #[arg(short = 'C', long, value_enum, default_value_t)]
     pub color: Coloring,
 
-    /// Print physical or logical file size
+    /// Format of the output
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t)]
+    pub output: output::Format,
+
+    /// Write output to PATH rather than stdout, replacing it only once output is complete
+    #[arg(short = 'o', long, value_name = "PATH")]
+    pub output_file: Option<PathBuf>,
+
+    /// Print physical or logical file size, or both side by side
     #[arg(short, long, value_enum, default_value_t)]
     pub disk_usage: DiskUsage,
 
+    /// Report '--disk-usage block' in blocks of this size rather than 512 bytes, e.g. '4096' or
+    /// '1M' like 'du -B'
+    #[cfg(unix)]
+    #[arg(long, value_name = "SIZE", value_parser = units::parse_block_size)]
+    pub block_size: Option<u64>,
+
     /// Follow symlinks
     #[arg(short = 'f', long)]
     pub follow: bool,
 
+    /// Attribute the sizes of followed symlinks to the paths where they appear, counting files
+    /// reachable from several paths only once rather than at each of them
+    #[arg(long, requires = "follow")]
+    pub logical_layout: bool,
+
     /// Print disk usage in human-readable format
     #[arg(short = 'H', long)]
     pub human: bool,
@@ -84,25 +138,58 @@ pub struct Context {
     #[arg(short = 'i', long)]
     pub no_ignore: bool,
 
+    /// Only show entries that are ignored by .gitignore and similar files
+    #[arg(long, conflicts_with = "no_ignore")]
+    pub only_ignored: bool,
+
+    /// Exclude entries matching gitignore-style patterns read from a file; patterns containing a
+    /// slash, such as '/target', are anchored at the directory being scanned
+    #[arg(long, value_name = "FILE")]
+    pub exclude_from: Option<PathBuf>,
+
     /// Display file icons
     #[arg(short = 'I', long)]
     pub icons: bool,
 
-    /// Show extended metadata and attributes
+    /// Show extended metadata and attributes; optionally pick exactly which columns to show and
+    /// in what order, e.g. '--long=perms,owner,size,mtime'
     #[cfg(unix)]
-    #[arg(short, long)]
-    pub long: bool,
+    #[arg(
+        short,
+        long,
+        value_enum,
+        value_name = "COLUMNS",
+        value_delimiter = ',',
+        num_args = 0..=1,
+        require_equals = true
+    )]
+    pub long: Option<Vec<long::Column>>,
 
     /// Show file's groups
     #[cfg(unix)]
     #[arg(long)]
     pub group: bool,
 
-    /// Show each file's ino
+    /// Show user and group IDs rather than their names
     #[cfg(unix)]
     #[arg(long)]
+    pub numeric_uid_gid: bool,
+
+    /// Show each file's inode number, with or without long view
+    #[cfg(unix)]
+    #[arg(long, visible_alias = "inodes")]
     pub ino: bool,
 
+    /// Show each file's security context like 'ls -Z', with or without long view
+    #[cfg(target_os = "linux")]
+    #[arg(short = 'Z', long = "context")]
+    pub selinux: bool,
+
+    /// Show the file flags of each entry in long view like 'ls -lO'
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    #[arg(short = 'O', long, requires = "long")]
+    pub file_flags: bool,
+
     /// Show the total number of hardlinks to the underlying inode
     #[cfg(unix)]
     #[arg(long)]
@@ -118,15 +205,43 @@ pub struct Context {
     #[arg(long, value_enum, requires = "long")]
     pub time: Option<time::Stamp>,
 
-    /// Which format to use for the timestamp; default by default
+    /// Timestamp format: default, iso, iso-strict, short, relative, or e.g. '+%Y-%m-%d %H:%M'
+    #[cfg(unix)]
+    #[arg(long = "time-format", value_name = "FORMAT", requires = "long")]
+    pub time_format: Option<time::Spec>,
+
+    /// Show timestamps of the long view and JSONL records in UTC rather than local time
+    #[arg(long, conflicts_with = "timezone")]
+    pub utc: bool,
+
+    /// Show timestamps of the long view and JSONL records in a time zone such as 'Europe/Berlin'
+    /// or at a fixed UTC offset such as '+05:30' rather than local time
+    #[arg(
+        long,
+        value_name = "TZ",
+        value_parser = time::parse_zone,
+        allow_hyphen_values = true
+    )]
+    pub timezone: Option<time::Zone>,
+
+    /// Print a line labeling the columns of the long view above the output
+    #[cfg(unix)]
+    #[arg(long, requires = "long")]
+    pub header: bool,
+
+    /// Print rows as fields separated by a single space without color, padding, or tree prefixes
     #[cfg(unix)]
-    #[arg(long = "time-format", value_enum, requires = "long")]
-    pub time_format: Option<time::Format>,
+    #[arg(long, requires = "long")]
+    pub parseable: bool,
 
     /// Maximum depth to display
     #[arg(short = 'L', long, value_name = "NUM")]
     level: Option<usize>,
 
+    /// Minimum depth to display; deeper entries are anchored to the root
+    #[arg(long, value_name = "NUM")]
+    min_depth: Option<usize>,
+
     /// Regular expression (or glob if '--glob' or '--iglob' is used) used to match files
     #[arg(short, long)]
     pub pattern: Option<String>,
@@ -143,13 +258,107 @@ pub struct Context {
     #[arg(short = 't', long, requires = "pattern", value_enum)]
     pub file_type: Option<file::Type>,
 
+    /// Only show entries whose names contain NEEDLE along with the directories leading to them
+    #[arg(long, value_name = "NEEDLE")]
+    pub find: Option<String>,
+
+    /// Match '--find' as a subsequence of names rather than as a substring
+    #[arg(long, requires = "find")]
+    pub fuzzy: bool,
+
+    /// Only show files with the given comma-separated extensions, e.g. 'rs,toml,md'
+    #[arg(long, value_name = "EXTS")]
+    pub ext: Option<String>,
+
+    /// Only show symlinks whose targets do not exist
+    #[arg(long)]
+    pub broken_links: bool,
+
+    /// Only show files whose amount of hard links satisfies a comparison such as '>1'
+    #[arg(long, value_name = "CMP", allow_hyphen_values = true)]
+    pub links: Option<links::Filter>,
+
+    /// Only show files whose sniffed content type matches a MIME type such as 'image/*'
+    #[arg(long, value_name = "TYPE")]
+    pub mime: Option<String>,
+
+    /// Only show files that carry the extended attribute NAME, e.g. 'security.capability'
+    #[cfg(unix)]
+    #[arg(long, value_name = "NAME")]
+    pub xattr: Option<String>,
+
+    /// List the names and sizes of each file's extended attributes beneath it
+    #[cfg(unix)]
+    #[arg(short = '@', long)]
+    pub list_xattrs: bool,
+
+    /// Show the values of extended attributes rather than their sizes
+    #[cfg(unix)]
+    #[arg(long, requires = "list_xattrs")]
+    pub xattr_values: bool,
+
+    /// Only show files that carry all of the comma-separated file FLAGS, e.g. 'uchg' or 'schg'
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    #[arg(long, value_name = "FLAGS", value_parser = crate::fs::permissions::flags::parse)]
+    pub flags: Option<crate::fs::permissions::flags::Flags>,
+
+    /// List the entries of each file's POSIX access control lists beneath it
+    #[cfg(target_os = "linux")]
+    #[arg(long)]
+    pub acl: bool,
+
+    /// Only show the first NUM entries of each directory after sorting and summarize the rest
+    #[arg(long, visible_alias = "max-children", value_name = "NUM")]
+    pub top: Option<NonZeroUsize>,
+
+    /// Print the NUM largest files found anywhere beneath the root instead of the tree
+    #[arg(long, value_name = "NUM", conflicts_with = "suppress_size")]
+    pub top_files: Option<NonZeroUsize>,
+
+    /// Hide the contents of common build and cache directories such as 'target' and '.venv'
+    #[arg(long)]
+    pub no_artifacts: bool,
+
+    /// Comma-separated names of additional directories to treat as artifacts
+    #[arg(long, value_name = "NAMES", requires = "no_artifacts")]
+    pub extra_artifacts: Option<String>,
+
     /// Remove empty directories from output
     #[arg(short = 'P', long)]
     pub prune: bool,
 
-    /// How to sort entries
-    #[arg(short, long, value_enum, default_value_t)]
-    pub sort: sort::Type,
+    /// How to sort entries; chain comma-separated keys to break ties, e.g. 'type,rsize,name'
+    #[arg(
+        short,
+        long,
+        value_enum,
+        value_delimiter = ',',
+        action = ArgAction::Set,
+        default_value = "size"
+    )]
+    pub sort: Vec<sort::Type>,
+
+    /// Sort flat layouts by the given columns instead of '--sort', e.g. 'owner,path'
+    #[arg(
+        long,
+        value_enum,
+        value_name = "COLUMN",
+        value_delimiter = ',',
+        action = ArgAction::Set
+    )]
+    pub sort_by: Vec<sort::Column>,
+
+    /// Order siblings randomly instead of sorting them
+    #[arg(long, conflicts_with = "sort_by")]
+    pub shuffle: bool,
+
+    /// Seed for '--shuffle' so that the random order is reproducible across runs
+    #[arg(long, value_name = "SEED", requires = "shuffle")]
+    pub seed: Option<u64>,
+
+    /// Split flat layouts into sections with subtotals by the given attribute
+    #[arg(long, value_enum, value_name = "KEY")]
+    pub group_by: Option<group::Key>,
 
     /// Sort directories before or after all other file types
     #[arg(long, value_enum, default_value_t)]
@@ -159,9 +368,56 @@ pub struct Context {
     #[arg(short = 'T', long, default_value_t = Context::num_threads())]
     pub threads: usize,
 
-    /// Report disk usage in binary or SI units
-    #[arg(short, long, value_enum, default_value_t)]
-    pub unit: PrefixKind,
+    /// Report disk usage in binary (bin) or SI (si) units, as raw byte counts (raw, raw-grouped),
+    /// in sectors, blocks, or pages, or in a single unit rather than scaling each size, e.g.
+    /// 'fixed:MiB'
+    #[arg(short, long, value_parser = units::parse_unit, default_value = "bin")]
+    pub unit: Unit,
+
+    /// Show how many files and directories each directory contains, recursively
+    #[arg(long)]
+    pub count: bool,
+
+    /// Highlight entries larger than the given size, e.g. '5G'
+    #[arg(long, value_name = "SIZE", value_parser = units::parse_size)]
+    pub warn_size: Option<u64>,
+
+    /// Exit with a non-zero status if any entry is larger than '--warn-size'
+    #[arg(long, requires = "warn_size")]
+    pub fail_on_warn: bool,
+
+    /// Number of decimal places to report scaled sizes with, e.g. '1.25 KiB'
+    #[arg(
+        long,
+        value_name = "NUM",
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u8).range(..=9)
+    )]
+    pub precision: u8,
+
+    /// How to round scaled sizes to '--precision' decimal places
+    #[arg(long, value_enum, value_name = "MODE", default_value_t)]
+    pub round: Rounding,
+
+    /// Minimum width of the size column, not including units
+    #[arg(long, value_name = "NUM")]
+    pub size_width: Option<usize>,
+
+    /// How to pad sizes to the width of the size column
+    #[arg(long, value_enum, value_name = "ALIGNMENT", default_value_t)]
+    pub size_align: align::Alignment,
+
+    /// Color sizes green, yellow, or red by magnitude rather than by unit
+    #[arg(long)]
+    pub gradient: bool,
+
+    /// Sizes at which '--gradient' turns yellow and red respectively
+    #[arg(long, value_name = "YELLOW,RED", default_value = "1MiB,1GiB")]
+    pub gradient_thresholds: gradient::Thresholds,
+
+    /// Annotate every entry with its share of its parent's or the root's total size
+    #[arg(long, value_enum, value_name = "BASIS")]
+    pub percent: Option<percent::Basis>,
 
     /// Prevent traversal into directories that are on different filesystems
     #[arg(short = 'x', long = "one-file-system")]
@@ -183,10 +439,26 @@ pub struct Context {
     /// Print completions for a given shell to stdout
     pub completions: Option<clap_complete::Shell>,
 
+    /// Print version, build target, enabled features, and supported backends
+    #[arg(short = 'V', long)]
+    pub version: bool,
+
+    /// Print version information as JSON
+    #[arg(long, requires = "version")]
+    pub json: bool,
+
     /// Only print directories
     #[arg(long)]
     pub dirs_only: bool,
 
+    /// Only print files, listing them in tree order without their directories
+    #[arg(long, conflicts_with = "dirs_only")]
+    pub leaf_only: bool,
+
+    /// Prefix files with the path of their parent directory relative to the root
+    #[arg(long, requires = "leaf_only")]
+    pub leaf_paths: bool,
+
     /// Don't read configuration file
     #[arg(long)]
     pub no_config: bool,
@@ -195,14 +467,70 @@ pub struct Context {
     #[arg(long)]
     pub no_progress: bool,
 
+    /// Count entries ahead of traversal so the progress indicator can estimate time remaining
+    #[arg(long, conflicts_with = "no_progress")]
+    pub eta: bool,
+
+    /// Report recoverable problems such as malformed color settings on stderr
+    #[arg(long)]
+    pub verbose: bool,
+
+    /// Show entries of the Nix store and symlinks into it by their package names without hashes and
+    /// total the disk usage of each derivation
+    #[cfg(feature = "nix")]
+    #[arg(long)]
+    pub nix: bool,
+
+    /// Annotate XDG base directories or their platform equivalents such as the cache, data,
+    /// downloads, and temp directories with their category and total the disk usage of each
+    #[arg(long)]
+    pub xdg: bool,
+
+    /// Print the total size along with counts of everything traversed and errors encountered
+    #[arg(long)]
+    pub report: bool,
+
+    /// Show usage against the current user's disk quota on the filesystem, if one applies
+    #[cfg(target_os = "linux")]
+    #[arg(long)]
+    pub quota: bool,
+
+    /// Scan a temporary read-only btrfs or ZFS snapshot so results reflect a single point in time
+    #[cfg(target_os = "linux")]
+    #[arg(long)]
+    pub consistent: bool,
+
+    /// Annotate ZFS dataset mountpoints with their compression ratio and used/referenced sizes
+    #[cfg(feature = "zfs")]
+    #[arg(long)]
+    pub zfs: bool,
+
     /// Omit disk usage from output
     #[arg(long)]
     pub suppress_size: bool,
 
+    /// Skip reading metadata entirely and only print the structure of the tree; fastest for huge
+    /// directories
+    #[cfg_attr(unix, arg(long, conflicts_with = "long"))]
+    #[cfg_attr(not(unix), arg(long))]
+    pub structure_only: bool,
+
     /// Truncate output to fit terminal emulator window
     #[arg(long)]
     pub truncate: bool,
 
+    /// Shade the background of alternating depth levels or rows
+    #[arg(long, value_enum)]
+    pub stripe: Option<stripe::Type>,
+
+    /// Right-align sizes against the edge of the terminal with dot leaders following names
+    #[arg(long)]
+    pub aligned_sizes: bool,
+
+    /// Draw a bar next to each size that is proportional to its share of the root's total size
+    #[arg(long, conflicts_with = "suppress_size")]
+    pub bar: bool,
+
     //////////////////////////
     /* INTERNAL USAGE BELOW */
     //////////////////////////
@@ -222,6 +550,10 @@ pub struct Context {
     #[clap(skip = usize::default())]
     pub max_size_unit_width: usize,
 
+    /// Total size of the root directory which '--bar' is scaled against
+    #[clap(skip = u64::default())]
+    pub root_size: u64,
+
     /// Restricts column width of nlink for long view
     #[clap(skip = usize::default())]
     #[cfg(unix)]
@@ -232,6 +564,11 @@ pub struct Context {
     #[cfg(unix)]
     pub max_ino_width: usize,
 
+    /// Restricts column width of the `SELinux` security context
+    #[clap(skip = usize::default())]
+    #[cfg(target_os = "linux")]
+    pub max_security_context_width: usize,
+
     /// Restricts column width of block for long view
     #[clap(skip = usize::default())]
     #[cfg(unix)]
@@ -247,25 +584,76 @@ pub struct Context {
     #[cfg(unix)]
     pub max_group_width: usize,
 
+    /// Restricts column width of file flags for long view
+    #[clap(skip = usize::default())]
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    pub max_flags_width: usize,
+
     /// Width of the terminal emulator's window
     #[clap(skip)]
     pub window_width: Option<usize>,
+
+    /// Name that the root is shown with when `dir` is a snapshot standing in for it
+    #[clap(skip)]
+    pub root_name: Option<OsString>,
 }
 
-type Predicate = Result<Box<dyn Fn(&DirEntry) -> bool + Send + Sync + 'static>, Error>;
+pub type Filter = Box<dyn Fn(&DirEntry) -> bool + Send + Sync + 'static>;
+
+type Predicate = Result<Filter, Error>;
 
 impl Context {
     /// Initializes [Context], optionally reading in the configuration file to override defaults.
     /// Arguments provided will take precedence over config.
     pub fn try_init() -> Result<Self, Error> {
-        Self::compute_args().and_then(|args| {
+        let ctx = Self::compute_args().and_then(|args| {
             color::no_color_env();
             Self::from_arg_matches(&args).map_err(Error::Config)
-        })
+        })?;
+
+        let flat = matches!(ctx.layout, layout::Type::Flat | layout::Type::Iflat);
+
+        if !flat && !ctx.sort_by.is_empty() {
+            return Err(Error::FlatOnly("--sort-by"));
+        }
+
+        if !flat && ctx.group_by.is_some() {
+            return Err(Error::FlatOnly("--group-by"));
+        }
+
+        if ctx.output == output::Format::Jsonl {
+            ctx.check_streamable()?;
+        }
+
+        Ok(ctx)
+    }
+
+    /// Records of '--output jsonl' are written while the tree is assembled, so anything that
+    /// reshapes the tree afterwards can't apply to them. Pruning, be it by '--prune' or by
+    /// filtering, is the exception as it's carried out as each directory is totaled.
+    fn check_streamable(&self) -> Result<(), Error> {
+        let reshaping = [
+            (self.dirs_only, "--dirs-only"),
+            (self.leaf_only, "--leaf-only"),
+            (self.find.is_some(), "--find"),
+            (self.min_depth.is_some(), "--min-depth"),
+            (self.top.is_some(), "--top"),
+            (self.top_files.is_some(), "--top-files"),
+            (self.no_artifacts, "--no-artifacts"),
+            (
+                matches!(self.layout, layout::Type::Flat | layout::Type::Iflat),
+                "--layout",
+            ),
+        ];
+
+        match reshaping.into_iter().find(|(used, _)| *used) {
+            Some((_, arg)) => Err(Error::NotStreamable(arg)),
+            None => Ok(()),
+        }
     }
 
     /// Determines whether or not it's appropriate to display color in output based on
-    /// the Coloring, and whether or not stdout is connected to a tty.
+    /// the Coloring, and whether or not output goes to stdout connected to a tty.
     ///
     /// If Coloring is Force then this will always evaluate to `false`.
     pub fn no_color(&self) -> bool {
@@ -274,7 +662,7 @@ impl Context {
         }
 
         match self.color {
-            Coloring::Auto if !self.stdout_is_tty => true,
+            Coloring::Auto if !self.stdout_is_tty || self.output_file.is_some() => true,
             Coloring::None => true,
             Coloring::Auto | Coloring::Force => false,
         }
@@ -287,17 +675,210 @@ impl Context {
             .map_or_else(|| Path::new("."), |pb| pb.as_path())
     }
 
+    /// The kind of prefix that sizes are reported with, that of the unit for '--unit fixed:UNIT'.
+    pub const fn prefix_kind(&self) -> PrefixKind {
+        match self.unit {
+            Unit::Prefix(prefix_kind) => prefix_kind,
+            Unit::Fixed(fixed_unit) => fixed_unit.prefix_kind(),
+        }
+    }
+
+    /// The unit that every size is reported in if '--unit fixed:UNIT' was used.
+    pub const fn fixed_unit(&self) -> Option<FixedUnit> {
+        match self.unit {
+            Unit::Prefix(_) => None,
+            Unit::Fixed(fixed_unit) => Some(fixed_unit),
+        }
+    }
+
+    /// Whether or not entries are measured at all. If not, sizes are omitted from the output rather
+    /// than shown as placeholders, which are reserved for entries pending a measurement.
+    pub const fn size_policy(&self) -> size::Policy {
+        if self.suppress_size || self.structure_only {
+            size::Policy::Suppress
+        } else {
+            size::Policy::Measure
+        }
+    }
+
+    /// Shorthand for whether or not the [`size::Policy`] is to measure entries.
+    pub const fn measures_sizes(&self) -> bool {
+        matches!(self.size_policy(), size::Policy::Measure)
+    }
+
+    /// Whether or not the output ends with the tally of directories and files, which
+    /// '--parseable' leaves out so that every line is a row.
+    pub const fn tallies(&self) -> bool {
+        #[cfg(unix)]
+        if self.parseable {
+            return false;
+        }
+
+        true
+    }
+
+    /// Whether or not rows are followed by lines detailing the extended attributes or access
+    /// control lists of their entries.
+    #[cfg(unix)]
+    pub const fn has_details(&self) -> bool {
+        #[cfg(target_os = "linux")]
+        let acl = self.acl;
+
+        #[cfg(not(target_os = "linux"))]
+        let acl = false;
+
+        self.list_xattrs || acl
+    }
+
+    /// Whether or not the long view is enabled.
+    #[cfg(unix)]
+    pub const fn long(&self) -> bool {
+        self.long.is_some()
+    }
+
+    /// Whether or not the columns of the long view were picked explicitly with '--long=COLUMNS',
+    /// in which case disk usage is only shown if it's among them.
+    #[cfg(unix)]
+    pub fn long_columns_selected(&self) -> bool {
+        self.long
+            .as_ref()
+            .is_some_and(|columns| !columns.is_empty())
+    }
+
+    /// Whether or not the `SELinux` security context of each entry needs to be read, either for
+    /// '--context' or for a context column picked with '--long=COLUMNS'.
+    #[cfg(target_os = "linux")]
+    pub fn needs_security_context(&self) -> bool {
+        self.selinux
+            || self
+                .long
+                .as_ref()
+                .is_some_and(|columns| columns.contains(&long::Column::Context))
+    }
+
+    /// Whether or not the file flags of each entry are shown, either for '--file-flags' or for a
+    /// flags column picked with '--long=COLUMNS'.
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    pub fn shows_file_flags(&self) -> bool {
+        self.file_flags
+            || self
+                .long
+                .as_ref()
+                .is_some_and(|columns| columns.contains(&long::Column::Flags))
+    }
+
+    /// The columns of the long view in the order that they ought to be displayed.
+    #[cfg(unix)]
+    pub fn long_columns(&self) -> Cow<'_, [long::Column]> {
+        match self.long.as_deref() {
+            Some(columns) if !columns.is_empty() => Cow::from(columns),
+            _ => Cow::from(long::default_columns(self)),
+        }
+    }
+
     /// Returns canonical [Path] of the root directory to be traversed.
     pub fn dir_canonical(&self) -> PathBuf {
         std::fs::canonicalize(self.dir()).unwrap_or_else(|_| self.dir().to_path_buf())
     }
 
+    /// Whether or not owners, groups, and extended attributes need to be collected for each entry
+    /// either for display in long view or for sorting and grouping flat layouts.
+    #[cfg(unix)]
+    pub fn needs_unix_attrs(&self) -> bool {
+        self.long()
+            || self.group_by == Some(group::Key::Owner)
+            || self
+                .sort_by
+                .iter()
+                .any(|column| matches!(column, sort::Column::Owner | sort::Column::Group))
+    }
+
     /// The max depth to print. Note that all directories are fully traversed to compute file
     /// sizes; this just determines how much to print.
     pub fn level(&self) -> usize {
         self.level.unwrap_or(usize::MAX)
     }
 
+    /// The min depth to print. Entries shallower than this are hidden, with the exception of the
+    /// root, while entries at this depth are displayed as children of the root.
+    pub fn min_depth(&self) -> usize {
+        self.min_depth.unwrap_or(0)
+    }
+
+    /// Whether or not any of the filters that only keep matching files are in effect, in which case
+    /// directories left empty ought to be pruned.
+    pub const fn filtered(&self) -> bool {
+        #[cfg(unix)]
+        let xattr = self.xattr.is_some();
+
+        #[cfg(not(unix))]
+        let xattr = false;
+
+        #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+        let flags = self.flags.is_some();
+
+        #[cfg(not(any(target_os = "macos", target_os = "freebsd")))]
+        let flags = false;
+
+        self.pattern.is_some()
+            || self.ext.is_some()
+            || self.broken_links
+            || self.mime.is_some()
+            || self.links.is_some()
+            || self.only_ignored
+            || xattr
+            || flags
+    }
+
+    /// Whether or not `name` is matched by `--find`. Matching is case-insensitive unless the
+    /// needle contains an uppercase character.
+    pub fn find_matches(&self, name: &OsStr) -> bool {
+        let Some(needle) = self.find.as_deref() else {
+            return true;
+        };
+
+        let name = name.to_string_lossy();
+
+        let (needle, name) = if needle.chars().any(char::is_uppercase) {
+            (Cow::from(needle), name)
+        } else {
+            (
+                Cow::from(needle.to_lowercase()),
+                Cow::from(name.to_lowercase()),
+            )
+        };
+
+        if !self.fuzzy {
+            return name.contains(needle.as_ref());
+        }
+
+        let mut name_chars = name.chars();
+        needle.chars().all(|ch| name_chars.any(|c| c == ch))
+    }
+
+    /// Whether or not `name` belongs to a directory whose contents ought to be hidden by
+    /// `--no-artifacts`.
+    pub fn is_artifact(&self, name: &OsStr) -> bool {
+        if !self.no_artifacts {
+            return false;
+        }
+
+        let Some(name) = name.to_str() else {
+            return false;
+        };
+
+        artifact::DEFAULTS.contains(&name)
+            || self
+                .extra_artifacts
+                .as_ref()
+                .is_some_and(|extra| extra.split(',').any(|n| n.trim() == name))
+    }
+
+    /// The maximum amount of entries to show per directory, if any.
+    pub fn top(&self) -> Option<usize> {
+        self.top.map(NonZeroUsize::get)
+    }
+
     /// Which timestamp type to use for long view; defaults to modified.
     #[cfg(unix)]
     pub fn time(&self) -> time::Stamp {
@@ -306,8 +887,18 @@ impl Context {
 
     /// Which format to use for the timestamp; default by default
     #[cfg(unix)]
-    pub fn time_format(&self) -> time::Format {
-        self.time_format.unwrap_or_default()
+    pub fn time_format(&self) -> &time::Spec {
+        self.time_format
+            .as_ref()
+            .unwrap_or(&time::Spec::Preset(time::Format::Default))
+    }
+
+    /// Time zone to show timestamps in as set by '--utc' or '--timezone'; local time if `None`.
+    pub fn timezone(&self) -> Option<time::Zone> {
+        if self.utc {
+            return Some(time::Zone::utc());
+        }
+        self.timezone
     }
 
     /// Which `FileType` to filter on; defaults to regular file.
@@ -362,6 +953,149 @@ impl Context {
         })
     }
 
+    /// Predicate used for filtering via file extensions. Directories are always included for the
+    /// same reason as in [`Self::regex_predicate`]. Extensions are compared case-insensitively and
+    /// may optionally be provided with a leading '.'.
+    pub fn ext_predicate(&self) -> Predicate {
+        let Some(exts) = self.ext.as_ref() else {
+            return Err(Error::ExtNotProvided);
+        };
+
+        let exts = exts
+            .split(',')
+            .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+            .filter(|ext| !ext.is_empty())
+            .collect::<Vec<_>>();
+
+        if exts.is_empty() {
+            return Err(Error::EmptyExt);
+        }
+
+        Ok(Box::new(move |dir_entry| {
+            if dir_entry.file_type().is_some_and(|ft| ft.is_dir()) {
+                return true;
+            }
+
+            dir_entry.path().extension().is_some_and(|ext| {
+                let ext = ext.to_string_lossy().to_lowercase();
+                exts.contains(&ext)
+            })
+        }))
+    }
+
+    /// Predicate used for filtering out everything but broken symlinks. Directories are always
+    /// included for the same reason as in [`Self::regex_predicate`].
+    pub fn broken_links_predicate() -> Filter {
+        Box::new(|dir_entry| {
+            dir_entry.file_type().is_some_and(|ft| ft.is_dir())
+                || crate::fs::is_broken_symlink(dir_entry)
+        })
+    }
+
+    /// Predicate used for filtering via content type as determined by the leading bytes of regular
+    /// files. Directories are always included for the same reason as in [`Self::regex_predicate`].
+    pub fn mime_predicate(&self) -> Predicate {
+        let Some(pattern) = self.mime.clone() else {
+            return Err(Error::MimeNotProvided);
+        };
+
+        if !pattern.contains('/') {
+            return Err(Error::InvalidMime(pattern));
+        }
+
+        Ok(Box::new(move |dir_entry| {
+            let Some(file_type) = dir_entry.file_type() else {
+                return false;
+            };
+
+            if file_type.is_dir() {
+                return true;
+            }
+
+            file_type.is_file()
+                && crate::fs::filetype::mime_type(dir_entry.path())
+                    .is_some_and(|mime| crate::fs::filetype::mime_matches(&pattern, mime))
+        }))
+    }
+
+    /// Predicate used for filtering via the amount of hard links to a file. Directories are always
+    /// included for the same reason as in [`Self::regex_predicate`].
+    pub fn links_predicate(&self) -> Predicate {
+        let Some(filter) = self.links else {
+            return Err(Error::LinksNotProvided);
+        };
+
+        Ok(Box::new(move |dir_entry| {
+            if dir_entry.file_type().is_some_and(|ft| ft.is_dir()) {
+                return true;
+            }
+
+            dir_entry
+                .metadata()
+                .ok()
+                .and_then(|md| Inode::try_from(&md).ok())
+                .is_some_and(|inode| filter.matches(inode.nlink))
+        }))
+    }
+
+    /// Predicate used for filtering via the presence of an extended attribute. Directories are
+    /// always included for the same reason as in [`Self::regex_predicate`], though those carrying
+    /// the attribute are kept even if none of their contents do as per [`Self::xattr_matches`].
+    #[cfg(unix)]
+    pub fn xattr_predicate(&self) -> Predicate {
+        use crate::fs::xattr::ExtendedAttr;
+
+        let Some(name) = self.xattr.clone() else {
+            return Err(Error::XattrNotProvided);
+        };
+
+        Ok(Box::new(move |dir_entry| {
+            dir_entry.file_type().is_some_and(|ft| ft.is_dir())
+                || dir_entry.has_xattr(OsStr::new(&name))
+        }))
+    }
+
+    /// Whether `dir_entry` carries the extended attribute sought by '--xattr'.
+    #[cfg(unix)]
+    pub fn xattr_matches(&self, dir_entry: &DirEntry) -> bool {
+        use crate::fs::xattr::ExtendedAttr;
+
+        self.xattr
+            .as_ref()
+            .is_some_and(|name| dir_entry.has_xattr(OsStr::new(name)))
+    }
+
+    /// Predicate used for filtering via file flags. Directories are always included for the same
+    /// reason as in [`Self::regex_predicate`].
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    pub fn flags_predicate(&self) -> Predicate {
+        use crate::fs::permissions::flags::Flags;
+
+        let Some(flags) = self.flags else {
+            return Err(Error::FlagsNotProvided);
+        };
+
+        Ok(Box::new(move |dir_entry| {
+            dir_entry.file_type().is_some_and(|ft| ft.is_dir())
+                || dir_entry
+                    .metadata()
+                    .is_ok_and(|md| Flags::of(&md).contains(flags))
+        }))
+    }
+
+    /// Predicate used for filtering out everything that isn't ignored. Entries are checked against
+    /// the ignore rules of the directories above them as they're reached, the same rules that the
+    /// walker would otherwise apply. Directories are always included for the same reason as in
+    /// [`Self::regex_predicate`].
+    pub fn only_ignored_predicate(&self) -> Filter {
+        let rules = ignored::Rules::new(self.dir_canonical());
+
+        Box::new(move |dir_entry| {
+            let is_dir = dir_entry.file_type().is_some_and(|ft| ft.is_dir());
+            is_dir || rules.is_ignored(dir_entry.path(), is_dir)
+        })
+    }
+
     /// Predicate used for filtering via globs and file-types.
     pub fn glob_predicate(&self) -> Predicate {
         let mut builder = OverrideBuilder::new(self.dir());
@@ -450,7 +1184,9 @@ impl Context {
 
     /// Update column width properties.
     pub fn update_column_properties(&mut self, col_props: &column::Properties) {
-        self.max_size_width = col_props.max_size_width;
+        self.max_size_width = col_props
+            .max_size_width
+            .max(self.size_width.unwrap_or_default());
         self.max_size_unit_width = col_props.max_size_unit_width;
 
         #[cfg(unix)]
@@ -461,6 +1197,27 @@ impl Context {
             self.max_block_width = col_props.max_block_width;
             self.max_ino_width = col_props.max_ino_width;
         }
+
+        #[cfg(target_os = "linux")]
+        {
+            self.max_security_context_width = col_props.max_security_context_width;
+        }
+
+        #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+        {
+            self.max_flags_width = col_props.max_flags_width;
+        }
+    }
+
+    /// Replaces `dir` with that of a snapshot which is traversed in place of the original directory
+    /// whose name, `root_name`, the root continues to be shown with.
+    #[cfg(target_os = "linux")]
+    pub fn with_dir(self, dir: PathBuf, root_name: Option<OsString>) -> Self {
+        Self {
+            dir: Some(dir),
+            root_name,
+            ..self
+        }
     }
 
     /// Setter for `window_width` which is set to the current terminal emulator's window width.
@@ -471,7 +1228,15 @@ impl Context {
 
     /// Answers whether disk usage is asked to be reported in bytes.
     pub const fn byte_metric(&self) -> bool {
-        matches!(self.disk_usage, DiskUsage::Logical | DiskUsage::Physical)
+        #[cfg(target_os = "linux")]
+        if matches!(self.disk_usage, DiskUsage::Unique) {
+            return true;
+        }
+
+        matches!(
+            self.disk_usage,
+            DiskUsage::Logical | DiskUsage::Physical | DiskUsage::Both
+        )
     }
 
     /// Do any of the components of a path match the provided glob? This is used for ensuring that
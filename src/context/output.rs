@@ -0,0 +1,14 @@
+use clap::ValueEnum;
+
+/// Formats that the output can be rendered in.
+#[derive(Clone, Copy, Debug, ValueEnum, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Human-readable output as determined by '--layout'
+    #[default]
+    Tree,
+
+    /// One JSON object per line for each entry, followed by a summary record in place of
+    /// '--report' and '--quota'. The entries of each directory are written as soon as it's
+    /// totaled, so the root comes last
+    Jsonl,
+}
@@ -0,0 +1,11 @@
+use clap::ValueEnum;
+
+/// Determines which total the size of each node is reported as a share of.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum Basis {
+    /// Share of the total size of the parent directory
+    Parent,
+
+    /// Share of the total size of the root directory
+    Root,
+}
@@ -0,0 +1,41 @@
+use crate::disk_usage::file_size::FileSize;
+
+/// Whether or not entries are measured, as determined by [`super::Context::size_policy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Policy {
+    /// Files are measured and directories are given the aggregate size of their contents.
+    Measure,
+
+    /// Nothing is measured because of '--suppress-size' or '--structure-only', so sizes are left
+    /// out of the output altogether.
+    Suppress,
+}
+
+/// The size of a single entry as understood under a given [Policy].
+#[derive(Clone, Copy)]
+pub enum State<'a> {
+    /// Sizes aren't measured at all. Rendered as nothing.
+    Suppressed,
+
+    /// The entry has no measurement, such as a directory whose contents haven't been totaled, a
+    /// socket, or a binary file under '--disk-usage line'. Rendered as a placeholder.
+    Pending,
+
+    /// The entry was measured to be empty. Rendered like any other size, e.g. `0 B`.
+    Zero(&'a FileSize),
+
+    /// The entry was measured to be non-empty.
+    Measured(&'a FileSize),
+}
+
+impl Policy {
+    /// The [State] of an entry whose measurement, if any, is `file_size`.
+    pub const fn state(self, file_size: Option<&FileSize>) -> State<'_> {
+        match (self, file_size) {
+            (Self::Suppress, _) => State::Suppressed,
+            (Self::Measure, None) => State::Pending,
+            (Self::Measure, Some(file_size)) if file_size.value() == 0 => State::Zero(file_size),
+            (Self::Measure, Some(file_size)) => State::Measured(file_size),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use clap::ValueEnum;
 
-/// Order in which to print nodes.
+/// Order in which to print nodes. Several can be chained where each subsequent one breaks ties
+/// left by the ones before it.
 #[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum Type {
     /// Sort entries by file name in lexicographical order.
@@ -8,6 +9,28 @@ pub enum Type {
     /// Sort entries by file name in reversed lexicographical order.
     Rname,
 
+    /// Sort entries by file name ignoring case and accents, e.g. 'Älder' before 'azathoth'
+    NameCi,
+
+    /// Sort entries by file name ignoring case and accents in reversed order
+    RnameCi,
+
+    /// Sort entries by file name treating runs of digits as numbers, e.g. file2 before file10
+    Version,
+
+    /// Sort entries by file name treating runs of digits as numbers in reversed order
+    Rversion,
+
+    /// Sort entries by file extension then by file name, entries without an extension first
+    Ext,
+
+    /// Sort entries by file extension then by file name in reversed order
+    Rext,
+
+    /// Sort directories above all other file types
+    #[value(name = "type")]
+    Kind,
+
     /// Sort entries by size smallest to largest, top to bottom
     #[default]
     Size,
@@ -15,16 +38,30 @@ pub enum Type {
     /// Sort entries by size largest to smallest, bottom to top
     Rsize,
 
-    /// Sort entries by newer to older Accessing Date
+    /// Sort entries by the amount of entries they contain recursively, fewest to most, top to
+    /// bottom
+    Count,
+
+    /// Sort entries by the amount of entries they contain recursively, most to fewest, top to
+    /// bottom
+    Rcount,
+
+    /// Sort entries by newer to older Accessing Date (alias: atime)
+    #[value(alias("atime"))]
     Access,
 
-    /// Sort entries by older to newer Accessing Date
+    /// Sort entries by older to newer Accessing Date (alias: ratime)
+    #[value(alias("ratime"))]
     Raccess,
 
-    /// Sort entries by newer to older Creation Date
+    /// Sort entries by newer to older Creation Date, or Alteration Date where unavailable (alias:
+    /// btime)
+    #[value(alias("btime"))]
     Create,
 
-    /// Sort entries by older to newer Creation Date
+    /// Sort entries by older to newer Creation Date, or Alteration Date where unavailable (alias:
+    /// rbtime)
+    #[value(alias("rbtime"))]
     Rcreate,
 
     /// Sort entries by newer to older Alteration Date
@@ -33,3 +70,34 @@ pub enum Type {
     /// Sort entries by older to newer Alteration Date
     Rmod,
 }
+
+/// Columns of flat output that '--sort-by' can order entries by. Each one sorts in the same
+/// direction as its '--sort' counterpart.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Column {
+    /// Sort entries by their path relative to the root in lexicographical order
+    Path,
+
+    /// Sort entries by size smallest to largest, top to bottom
+    Size,
+
+    /// Sort entries by the amount of entries they contain recursively, fewest to most
+    Count,
+
+    /// Sort entries by newer to older Alteration Date
+    Mtime,
+
+    /// Sort entries by newer to older Accessing Date
+    Atime,
+
+    /// Sort entries by newer to older Creation Date, or Alteration Date where unavailable
+    Btime,
+
+    /// Sort entries by the name of their owner in lexicographical order
+    #[cfg(unix)]
+    Owner,
+
+    /// Sort entries by the name of their group in lexicographical order
+    #[cfg(unix)]
+    Group,
+}
@@ -0,0 +1,11 @@
+use clap::ValueEnum;
+
+/// Determines which rows of the output receive background shading.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum Type {
+    /// Shade the rows of every other depth level
+    Depth,
+
+    /// Shade every other row
+    Row,
+}
@@ -1,12 +1,26 @@
+use chrono::{DateTime, FixedOffset, Local, Offset, Utc};
+use chrono_tz::Tz;
+use std::time::SystemTime;
+
+#[cfg(unix)]
+use chrono::format::{Item, StrftimeItems};
+#[cfg(unix)]
 use clap::ValueEnum;
+#[cfg(unix)]
+use std::str::FromStr;
 
 /// Different types of timestamps available in long-view.
+#[cfg(unix)]
 #[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum Stamp {
-    /// Time created (alias: ctime)
-    #[value(alias("ctime"))]
+    /// Time created (alias: btime)
+    #[value(alias("btime"))]
     Create,
 
+    /// Time of last status change such as to permissions or ownership; Unix only (alias: ctime)
+    #[value(alias("ctime"))]
+    Change,
+
     /// Time last accessed (alias: atime)
     #[value(alias("atime"))]
     Access,
@@ -18,6 +32,7 @@ pub enum Stamp {
 }
 
 /// Different formatting options for timestamps
+#[cfg(unix)]
 #[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum Format {
     /// Timestamp formatted following the iso8601, with slight differences and the time-zone omitted
@@ -32,4 +47,223 @@ pub enum Format {
     /// Timestamp is shown in DD MMM HH:MM format
     #[default]
     Default,
+
+    /// Timestamp is shown relative to the current time, e.g. "3 days ago"
+    Relative,
+}
+
+/// How to format timestamps: either one of the presets of [Format] or a custom strftime string.
+#[cfg(unix)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Spec {
+    Preset(Format),
+    Custom(String),
+}
+
+#[cfg(unix)]
+impl FromStr for Spec {
+    type Err = String;
+
+    /// Parses either the name of a [Format] or a strftime string prefixed with `+`, e.g.
+    /// `+%Y-%m-%d %H:%M`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(custom) = s.strip_prefix('+') else {
+            return Format::from_str(s, true).map(Self::Preset);
+        };
+
+        if StrftimeItems::new(custom).any(|item| matches!(item, Item::Error)) {
+            return Err(format!("'{custom}' is not a valid strftime format"));
+        }
+
+        Ok(Self::Custom(custom.to_owned()))
+    }
+}
+
+/// Time zone that timestamps are shown in as set by '--utc' or '--timezone'.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Zone {
+    /// The same offset from UTC all year round.
+    Fixed(FixedOffset),
+
+    /// A zone of the IANA time zone database whose offset changes with daylight saving time.
+    Named(Tz),
+}
+
+impl Zone {
+    /// The UTC zone that '--utc' stands for.
+    pub fn utc() -> Self {
+        Self::Fixed(Utc.fix())
+    }
+}
+
+/// Parses a time zone given to '--timezone', which is either the name of a zone of the IANA time
+/// zone database such as `Europe/Berlin` or a UTC offset as accepted by [`parse_offset`].
+pub fn parse_zone(s: &str) -> Result<Zone, String> {
+    if let Ok(tz) = s.parse::<Tz>() {
+        return Ok(Zone::Named(tz));
+    }
+
+    parse_offset(s).map(Zone::Fixed).map_err(|_| {
+        format!(
+            "'{s}' is neither a time zone such as 'Europe/Berlin' nor an offset such as '+05:30'"
+        )
+    })
+}
+
+/// Parses a UTC offset, which is either `UTC`, `Z`, or a signed number of hours optionally
+/// followed by minutes, e.g. `+05:30`, `-0800`, or `+09`.
+pub fn parse_offset(s: &str) -> Result<FixedOffset, String> {
+    if s.eq_ignore_ascii_case("utc") || s.eq_ignore_ascii_case("z") {
+        return Ok(FixedOffset::east_opt(0).unwrap());
+    }
+
+    let invalid = || format!("'{s}' is not a UTC offset such as '+05:30' or 'UTC'");
+
+    let (sign, rest) = match s.as_bytes().first() {
+        Some(b'+') => (1, &s[1..]),
+        Some(b'-') => (-1, &s[1..]),
+        _ => return Err(invalid()),
+    };
+
+    let bytes = rest.as_bytes();
+    let is_digits = |bytes: &[u8]| bytes.iter().all(u8::is_ascii_digit);
+
+    // Only `HH`, `HHMM`, and `HH:MM` are accepted.
+    let (hours, minutes) = match bytes.len() {
+        2 | 4 if is_digits(bytes) => (&rest[..2], &rest[2..]),
+        5 if bytes[2] == b':' && is_digits(&bytes[..2]) && is_digits(&bytes[3..]) => {
+            (&rest[..2], &rest[3..])
+        },
+        _ => return Err(invalid()),
+    };
+
+    let hours = hours.parse::<i32>().map_err(|_| invalid())?;
+    let minutes = minutes.parse::<i32>().unwrap_or(0);
+
+    if minutes >= 60 {
+        return Err(invalid());
+    }
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(invalid)
+}
+
+/// Converts `time` to a datetime at the offset that the zone `tz` had at that time, or at the
+/// local offset in effect at that time if there's none.
+pub fn localize(time: SystemTime, tz: Option<Zone>) -> DateTime<FixedOffset> {
+    let offset = match tz {
+        Some(Zone::Fixed(offset)) => offset,
+        Some(Zone::Named(tz)) => DateTime::<Utc>::from(time)
+            .with_timezone(&tz)
+            .offset()
+            .fix(),
+        None => *DateTime::<Local>::from(time).offset(),
+    };
+
+    DateTime::<Utc>::from(time).with_timezone(&offset)
+}
+
+/// Describes how long ago `dt` was relative to `now` in the largest whole unit, e.g. "3 days ago".
+/// Timestamps in the future are described as e.g. "in 3 days".
+#[cfg(unix)]
+pub fn relative(dt: DateTime<FixedOffset>, now: DateTime<FixedOffset>) -> String {
+    const UNITS: [(i64, &str); 6] = [
+        (60 * 60 * 24 * 365, "year"),
+        (60 * 60 * 24 * 30, "month"),
+        (60 * 60 * 24 * 7, "week"),
+        (60 * 60 * 24, "day"),
+        (60 * 60, "hour"),
+        (60, "minute"),
+    ];
+
+    let elapsed = now.signed_duration_since(dt).num_seconds();
+
+    let Some((count, unit)) = UNITS
+        .iter()
+        .find_map(|(secs, unit)| (elapsed.abs() >= *secs).then(|| (elapsed.abs() / secs, unit)))
+    else {
+        return String::from("just now");
+    };
+
+    let plural = if count == 1 { "" } else { "s" };
+
+    if elapsed < 0 {
+        format!("in {count} {unit}{plural}")
+    } else {
+        format!("{count} {unit}{plural} ago")
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn parse_spec() {
+    assert_eq!("iso".parse(), Ok(Spec::Preset(Format::Iso)));
+    assert_eq!("relative".parse(), Ok(Spec::Preset(Format::Relative)));
+    assert_eq!(
+        "+%Y-%m-%d %H:%M".parse(),
+        Ok(Spec::Custom(String::from("%Y-%m-%d %H:%M")))
+    );
+    assert!("+%Q".parse::<Spec>().is_err());
+    assert!("%Y".parse::<Spec>().is_err());
+}
+
+#[test]
+fn parse_zones() {
+    let berlin = parse_zone("Europe/Berlin").unwrap();
+
+    assert_eq!(berlin, Zone::Named(chrono_tz::Europe::Berlin));
+    assert_eq!(
+        parse_zone("+05:30"),
+        Ok(Zone::Fixed(
+            FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap()
+        ))
+    );
+    assert!(parse_zone("Atlantis/Poseidonis").is_err());
+
+    // Daylight saving time is in effect in July but not in January.
+    let at = |secs| SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+
+    assert_eq!(
+        localize(at(1_700_000_000), Some(berlin))
+            .offset()
+            .local_minus_utc(),
+        3600
+    );
+    assert_eq!(
+        localize(at(1_720_000_000), Some(berlin))
+            .offset()
+            .local_minus_utc(),
+        7200
+    );
+}
+
+#[test]
+fn parse_offsets() {
+    let east = |secs| Ok(FixedOffset::east_opt(secs).unwrap());
+
+    assert_eq!(parse_offset("UTC"), east(0));
+    assert_eq!(parse_offset("+05:30"), east(5 * 3600 + 30 * 60));
+    assert_eq!(parse_offset("-0800"), east(-8 * 3600));
+    assert_eq!(parse_offset("+09"), east(9 * 3600));
+    assert!(parse_offset("05:30").is_err());
+    assert!(parse_offset("+5").is_err());
+    assert!(parse_offset("+0:530").is_err());
+    assert!(parse_offset("+05:3").is_err());
+    assert!(parse_offset("+0530:").is_err());
+    assert!(parse_offset("+05:60").is_err());
+    assert!(parse_offset("+25:00").is_err());
+}
+
+#[test]
+#[cfg(unix)]
+fn relative_timestamps() {
+    use chrono::Duration;
+
+    let now = Local::now();
+    let now = now.with_timezone(now.offset());
+
+    assert_eq!(relative(now - Duration::seconds(30), now), "just now");
+    assert_eq!(relative(now - Duration::minutes(1), now), "1 minute ago");
+    assert_eq!(relative(now - Duration::days(3), now), "3 days ago");
+    assert_eq!(relative(now - Duration::days(400), now), "1 year ago");
+    assert_eq!(relative(now + Duration::hours(2), now), "in 2 hours");
 }
@@ -1,3 +1,4 @@
+use super::BLOCK_SIZE_BYTES;
 use std::{
     fmt::{self, Display},
     fs::Metadata,
@@ -7,16 +8,56 @@ use std::{
 #[derive(Default)]
 pub struct Metric {
     pub value: u64,
+    block_size: Option<u64>,
 }
 
 impl Metric {
     pub fn init(md: &Metadata) -> Self {
-        Self { value: md.blocks() }
+        Self {
+            value: md.blocks(),
+            ..Self::default()
+        }
+    }
+
+    /// Report usage in blocks of `block_size` bytes rather than 512 bytes, rounding up like
+    /// `du -B` does.
+    pub const fn with_block_size(mut self, block_size: Option<u64>) -> Self {
+        self.block_size = block_size;
+        self
+    }
+
+    /// The amount of blocks to report taking into account `block_size`.
+    pub fn blocks(&self) -> u64 {
+        self.block_size.map_or(self.value, |block_size| {
+            let bytes = self.value.saturating_mul(u64::from(BLOCK_SIZE_BYTES));
+            bytes / block_size + u64::from(bytes % block_size != 0)
+        })
     }
 }
 
 impl Display for Metric {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        <u64 as Display>::fmt(&self.value, f)
+        <u64 as Display>::fmt(&self.blocks(), f)
     }
 }
+
+#[test]
+fn block_size_rounds_up() {
+    let metric = Metric {
+        value: 9,
+        ..Metric::default()
+    };
+
+    assert_eq!(metric.blocks(), 9);
+    assert_eq!(metric.with_block_size(Some(1024)).blocks(), 5);
+
+    let metric = Metric {
+        value: u64::MAX,
+        ..Metric::default()
+    };
+
+    assert_eq!(
+        metric.with_block_size(Some(512)).blocks(),
+        u64::MAX / 512 + 1
+    );
+}
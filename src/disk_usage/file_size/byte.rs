@@ -1,9 +1,12 @@
-use super::super::units::{BinPrefix, PrefixKind, SiPrefix, UnitPrefix};
+use super::super::units::{BinPrefix, FixedUnit, PrefixKind, Rounding, SiPrefix, UnitPrefix};
 use filesize::PathExt;
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
 use std::{
     cell::{Ref, RefCell},
     fmt::{self, Display},
     fs::Metadata,
+    ops::{AddAssign, SubAssign},
     path::Path,
 };
 
@@ -15,6 +18,16 @@ pub struct Metric {
     #[allow(dead_code)]
     kind: MetricKind,
     prefix_kind: PrefixKind,
+    fixed_unit: Option<FixedUnit>,
+    precision: u8,
+    rounding: Rounding,
+
+    /// Block size of the filesystem that the file resides on for use with [`PrefixKind::Blocks`].
+    pub fs_block_size: u64,
+
+    /// Sectors, blocks, or pages accumulated from other metrics. Each file occupies a whole number
+    /// of them so totals are the sum of those of the files rather than derived from total bytes.
+    units: Option<u64>,
 
     /// To prevent allocating the same string twice. We allocate the first time
     /// in [`crate::tree::Tree::update_column_properties`] in order to compute the max column width for
@@ -22,6 +35,13 @@ pub struct Metric {
     cached_display: RefCell<String>,
 }
 
+/// Size in bytes of a sector as reported by [`PrefixKind::Sectors`].
+const SECTOR_SIZE: u64 = 512;
+
+/// Size in bytes of a page as reported by [`PrefixKind::Pages`], also used as the filesystem block
+/// size where it can't be queried.
+const PAGE_SIZE: u64 = 4096;
+
 /// Represents the appropriate method in which to compute bytes. `Logical` represent the total amount
 /// of bytes in a file; `Physical` represents how many bytes are actually used to store the file on
 /// disk.
@@ -45,6 +65,11 @@ impl Metric {
             human_readable,
             kind,
             prefix_kind,
+            fixed_unit: None,
+            precision: 1,
+            rounding: Rounding::Nearest,
+            fs_block_size: fs_block_size(metadata),
+            units: None,
             cached_display: RefCell::default(),
         }
     }
@@ -56,6 +81,11 @@ impl Metric {
             human_readable,
             kind: MetricKind::Logical,
             prefix_kind,
+            fixed_unit: None,
+            precision: 1,
+            rounding: Rounding::Nearest,
+            fs_block_size: PAGE_SIZE,
+            units: None,
             cached_display: RefCell::default(),
         }
     }
@@ -67,6 +97,11 @@ impl Metric {
             human_readable,
             kind: MetricKind::Physical,
             prefix_kind,
+            fixed_unit: None,
+            precision: 1,
+            rounding: Rounding::Nearest,
+            fs_block_size: PAGE_SIZE,
+            units: None,
             cached_display: RefCell::default(),
         }
     }
@@ -78,7 +113,13 @@ impl Metric {
         prefix_kind: PrefixKind,
         human_readable: bool,
     ) -> Self {
-        let value = path.size_on_disk_fast(metadata).unwrap_or(metadata.len());
+        // Placeholders of cloud providers such as `OneDrive` don't occupy any space locally until
+        // their contents are downloaded.
+        let value = if crate::fs::cloud::is_placeholder(metadata) {
+            0
+        } else {
+            path.size_on_disk_fast(metadata).unwrap_or(metadata.len())
+        };
         let kind = MetricKind::Physical;
 
         Self {
@@ -86,10 +127,34 @@ impl Metric {
             human_readable,
             kind,
             prefix_kind,
+            fixed_unit: None,
+            precision: 1,
+            rounding: Rounding::Nearest,
+            fs_block_size: fs_block_size(metadata),
+            units: None,
             cached_display: RefCell::default(),
         }
     }
 
+    /// Reports the [Metric] in `fixed_unit`, if any, regardless of its magnitude. Takes precedence
+    /// over `human_readable`.
+    pub const fn with_fixed_unit(mut self, fixed_unit: Option<FixedUnit>) -> Self {
+        self.fixed_unit = fixed_unit;
+        self
+    }
+
+    /// Reports scaled sizes with `precision` decimal places rather than one.
+    pub const fn with_precision(mut self, precision: u8) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Rounds scaled sizes as per `rounding` rather than to the nearest value.
+    pub const fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
     /// Returns an immutable borrow of the `cached_display`.
     pub fn cached_display(&self) -> Ref<'_, String> {
         self.cached_display.borrow()
@@ -106,9 +171,24 @@ impl Display for Metric {
             }
         }
 
-        let value = self.value as f64;
+        let display = match self.fixed_unit {
+            Some(FixedUnit::Byte) => format!("{} {}", self.value, FixedUnit::Byte),
+            Some(unit) => format!("{} {unit}", self.fmt_scaled(unit.base_value())),
+            None => self.fmt_prefixed(),
+        };
+
+        write!(f, "{display}")?;
+
+        self.cached_display.replace(display);
 
-        let display = match self.prefix_kind {
+        Ok(())
+    }
+}
+
+impl Metric {
+    /// Formats the [Metric] using either SI or binary prefixes.
+    fn fmt_prefixed(&self) -> String {
+        match self.prefix_kind {
             PrefixKind::Si => {
                 if self.human_readable {
                     let unit = SiPrefix::from(self.value);
@@ -116,9 +196,7 @@ impl Display for Metric {
                     if unit == SiPrefix::Base {
                         format!("{} {unit}", self.value)
                     } else {
-                        let base_value = unit.base_value();
-                        let size = value / (base_value as f64);
-                        format!("{size:.1} {unit}")
+                        format!("{} {unit}", self.fmt_scaled(unit.base_value()))
                     }
                 } else {
                     format!("{} {}", self.value, SiPrefix::Base)
@@ -131,24 +209,109 @@ impl Display for Metric {
                     if unit == BinPrefix::Base {
                         format!("{} {unit}", self.value)
                     } else {
-                        let base_value = unit.base_value();
-                        let size = value / (base_value as f64);
-                        format!("{size:.1} {unit}")
+                        format!("{} {unit}", self.fmt_scaled(unit.base_value()))
                     }
                 } else {
                     format!("{} {}", self.value, BinPrefix::Base)
                 }
             },
+            PrefixKind::Raw => self.value.to_string(),
+            PrefixKind::RawGrouped => group_digits(self.value),
+            PrefixKind::Sectors | PrefixKind::Blocks | PrefixKind::Pages => {
+                self.units().to_string()
+            },
+        }
+    }
+
+    /// Number of sectors, blocks, or pages as per `prefix_kind` that the file occupies, or that the
+    /// files it was accumulated from occupy altogether.
+    pub fn units(&self) -> u64 {
+        self.units.unwrap_or_else(|| {
+            let unit_size = match self.prefix_kind {
+                PrefixKind::Sectors => SECTOR_SIZE,
+                PrefixKind::Blocks => self.fs_block_size,
+                _ => PAGE_SIZE,
+            };
+            count(self.value, unit_size)
+        })
+    }
+
+    /// Formats the value divided by `base_value` with `precision` decimal places, rounded as per
+    /// `rounding`. Rounding up or down is done in integer arithmetic so that e.g. a size that is
+    /// just over a unit is never reported as exactly that unit when rounding up.
+    fn fmt_scaled(&self, base_value: u64) -> String {
+        let precision = usize::from(self.precision);
+
+        if self.rounding == Rounding::Nearest {
+            let size = self.value as f64 / (base_value as f64);
+            return format!("{size:.precision$}");
+        }
+
+        let scale = 10_u128.pow(u32::from(self.precision));
+        let (numerator, base_value) = (u128::from(self.value) * scale, u128::from(base_value));
+
+        let scaled = if self.rounding == Rounding::Up {
+            (numerator + base_value - 1) / base_value
+        } else {
+            numerator / base_value
         };
+        let (whole, fraction) = (scaled / scale, scaled % scale);
 
-        write!(f, "{display}")?;
+        if precision == 0 {
+            whole.to_string()
+        } else {
+            format!("{whole}.{fraction:0precision$}")
+        }
+    }
+}
 
-        self.cached_display.replace(display);
+/// Number of units of `unit_size` bytes needed to hold `value` bytes, rounding up like `du -B`.
+fn count(value: u64, unit_size: u64) -> u64 {
+    // Equivalent to `u64::div_ceil` which is newer than the minimum supported Rust version.
+    value / unit_size + u64::from(value % unit_size != 0)
+}
 
-        Ok(())
+impl AddAssign<&Self> for Metric {
+    fn add_assign(&mut self, rhs: &Self) {
+        self.units = Some(self.units() + rhs.units());
+        self.value += rhs.value;
+    }
+}
+
+impl SubAssign<&Self> for Metric {
+    fn sub_assign(&mut self, rhs: &Self) {
+        self.units = Some(self.units().saturating_sub(rhs.units()));
+        self.value = self.value.saturating_sub(rhs.value);
     }
 }
 
+/// Block size of the filesystem that the file of `metadata` resides on.
+#[cfg(unix)]
+fn fs_block_size(metadata: &Metadata) -> u64 {
+    metadata.blksize()
+}
+
+/// Block size of the filesystem that the file of `metadata` resides on.
+#[cfg(not(unix))]
+const fn fs_block_size(_metadata: &Metadata) -> u64 {
+    PAGE_SIZE
+}
+
+/// Separates every three digits of `value` with a comma, e.g. `1234567` becomes `1,234,567`.
+fn group_digits(value: u64) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    grouped
+}
+
 #[test]
 fn test_metric() {
     let metric = Metric {
@@ -156,6 +319,11 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: false,
         prefix_kind: PrefixKind::Bin,
+        fixed_unit: None,
+        precision: 1,
+        rounding: Rounding::Nearest,
+        fs_block_size: PAGE_SIZE,
+        units: None,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "100 B");
@@ -165,6 +333,11 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: true,
         prefix_kind: PrefixKind::Si,
+        fixed_unit: None,
+        precision: 1,
+        rounding: Rounding::Nearest,
+        fs_block_size: PAGE_SIZE,
+        units: None,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "1.0 KB");
@@ -174,6 +347,11 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: true,
         prefix_kind: PrefixKind::Bin,
+        fixed_unit: None,
+        precision: 1,
+        rounding: Rounding::Nearest,
+        fs_block_size: PAGE_SIZE,
+        units: None,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "1000 B");
@@ -183,6 +361,11 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: true,
         prefix_kind: PrefixKind::Bin,
+        fixed_unit: None,
+        precision: 1,
+        rounding: Rounding::Nearest,
+        fs_block_size: PAGE_SIZE,
+        units: None,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "1.0 KiB");
@@ -192,6 +375,11 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: true,
         prefix_kind: PrefixKind::Bin,
+        fixed_unit: None,
+        precision: 1,
+        rounding: Rounding::Nearest,
+        fs_block_size: PAGE_SIZE,
+        units: None,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "1.0 MiB");
@@ -201,7 +389,118 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: false,
         prefix_kind: PrefixKind::Bin,
+        fixed_unit: None,
+        precision: 1,
+        rounding: Rounding::Nearest,
+        fs_block_size: PAGE_SIZE,
+        units: None,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "123454 B");
 }
+
+#[test]
+fn test_fixed_unit_metric() {
+    let metric =
+        Metric::init_empty_logical(true, PrefixKind::Bin).with_fixed_unit(Some(FixedUnit::Mebi));
+    assert_eq!(format!("{metric}"), "0.0 MiB");
+
+    let mut metric =
+        Metric::init_empty_logical(false, PrefixKind::Bin).with_fixed_unit(Some(FixedUnit::Kilo));
+    metric.value = 1500;
+    assert_eq!(format!("{metric}"), "1.5 KB");
+
+    let mut metric =
+        Metric::init_empty_logical(true, PrefixKind::Si).with_fixed_unit(Some(FixedUnit::Byte));
+    metric.value = 123_454;
+    assert_eq!(format!("{metric}"), "123454 B");
+}
+
+#[test]
+fn test_raw_metric() {
+    let mut metric = Metric::init_empty_logical(true, PrefixKind::Raw);
+    metric.value = 1_234_567;
+    assert_eq!(format!("{metric}"), "1234567");
+
+    let mut metric = Metric::init_empty_logical(true, PrefixKind::RawGrouped);
+    metric.value = 1_234_567;
+    assert_eq!(format!("{metric}"), "1,234,567");
+
+    let mut metric = Metric::init_empty_logical(false, PrefixKind::RawGrouped);
+    metric.value = 123;
+    assert_eq!(format!("{metric}"), "123");
+
+    let mut metric = Metric::init_empty_logical(false, PrefixKind::RawGrouped)
+        .with_fixed_unit(Some(FixedUnit::Kibi));
+    metric.value = 1536;
+    assert_eq!(format!("{metric}"), "1.5 KiB");
+}
+
+#[test]
+fn test_metric_precision() {
+    let mut metric = Metric::init_empty_logical(true, PrefixKind::Bin).with_precision(2);
+    metric.value = 1241;
+    assert_eq!(format!("{metric}"), "1.21 KiB");
+
+    let mut metric = Metric::init_empty_logical(true, PrefixKind::Si).with_precision(0);
+    metric.value = 1500;
+    assert_eq!(format!("{metric}"), "2 KB");
+
+    let mut metric = Metric::init_empty_logical(false, PrefixKind::Bin)
+        .with_fixed_unit(Some(FixedUnit::Kibi))
+        .with_precision(3);
+    metric.value = 1536;
+    assert_eq!(format!("{metric}"), "1.500 KiB");
+}
+
+#[test]
+fn test_metric_rounding() {
+    let metric = |rounding| {
+        let mut metric = Metric::init_empty_logical(true, PrefixKind::Bin).with_rounding(rounding);
+        metric.value = 1080;
+        format!("{metric}")
+    };
+
+    assert_eq!(metric(Rounding::Nearest), "1.1 KiB");
+    assert_eq!(metric(Rounding::Up), "1.1 KiB");
+    assert_eq!(metric(Rounding::Down), "1.0 KiB");
+
+    let mut metric = Metric::init_empty_logical(true, PrefixKind::Si)
+        .with_precision(0)
+        .with_rounding(Rounding::Up);
+    metric.value = 1001;
+    assert_eq!(format!("{metric}"), "2 KB");
+
+    let mut metric = Metric::init_empty_logical(false, PrefixKind::Bin)
+        .with_fixed_unit(Some(FixedUnit::Mebi))
+        .with_precision(2)
+        .with_rounding(Rounding::Up);
+    metric.value = 1;
+    assert_eq!(format!("{metric}"), "0.01 MiB");
+}
+
+#[test]
+fn test_metric_counts() {
+    let metric = |prefix_kind, value| {
+        let mut metric = Metric::init_empty_logical(true, prefix_kind);
+        metric.value = value;
+        metric.fs_block_size = 1024;
+        metric
+    };
+
+    assert_eq!(metric(PrefixKind::Sectors, 1241).to_string(), "3");
+    assert_eq!(metric(PrefixKind::Sectors, 1024).to_string(), "2");
+    assert_eq!(metric(PrefixKind::Blocks, 1241).to_string(), "2");
+    assert_eq!(metric(PrefixKind::Pages, 1241).to_string(), "1");
+    assert_eq!(metric(PrefixKind::Pages, 0).to_string(), "0");
+    assert_eq!(
+        metric(PrefixKind::Pages, u64::MAX).units(),
+        u64::MAX / 4096 + 1
+    );
+
+    let mut total = metric(PrefixKind::Sectors, 0);
+    total += &metric(PrefixKind::Sectors, 100);
+    total += &metric(PrefixKind::Sectors, 600);
+    assert_eq!(total.value, 700);
+    assert_eq!(total.to_string(), "3");
+}
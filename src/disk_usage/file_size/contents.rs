@@ -0,0 +1,50 @@
+use memmap2::Mmap;
+use std::{fs::File, io::Read, ops::Deref, path::Path};
+
+/// Files at least this large are memory-mapped rather than read into a buffer.
+const MMAP_THRESHOLD: u64 = 1024 * 1024;
+
+/// The contents of a file which are mapped into memory if the file is large enough to make it
+/// worthwhile, otherwise read into a buffer.
+pub enum Contents {
+    Mapped(Mmap),
+    Buffered(Vec<u8>),
+}
+
+impl Contents {
+    /// Reads the contents of the file at `path`, returning `None` if it can't be read.
+    pub fn read(path: &Path) -> Option<Self> {
+        let mut file = File::open(path).ok()?;
+        let len = file.metadata().ok()?.len();
+
+        if len >= MMAP_THRESHOLD {
+            // The file may be truncated by another process while mapped, in which case accessing
+            // the missing pages raises SIGBUS; that risk is accepted as it is by `ripgrep`.
+            if let Ok(mmap) = unsafe { Mmap::map(&file) } {
+                return Some(Self::Mapped(mmap));
+            }
+        }
+
+        let mut buf = Vec::with_capacity(usize::try_from(len).unwrap_or_default());
+        file.read_to_end(&mut buf).ok()?;
+
+        Some(Self::Buffered(buf))
+    }
+
+    /// The contents as UTF-8 text, or `None` if they aren't valid UTF-8 as in the case of binary
+    /// files.
+    pub fn text(&self) -> Option<&str> {
+        std::str::from_utf8(self).ok()
+    }
+}
+
+impl Deref for Contents {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Mapped(mmap) => mmap,
+            Self::Buffered(buf) => buf,
+        }
+    }
+}
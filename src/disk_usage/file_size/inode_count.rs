@@ -0,0 +1,30 @@
+use std::{
+    convert::From,
+    fmt::{self, Display},
+};
+
+/// Concerned with measuring file size by how many filesystem entries, and thus inodes, a file or
+/// directory accounts for.
+#[derive(Default)]
+pub struct Metric {
+    pub value: u64,
+}
+
+impl Metric {
+    /// Initializes a [Metric] for a single filesystem entry.
+    pub const fn init() -> Self {
+        Self { value: 1 }
+    }
+}
+
+impl From<u64> for Metric {
+    fn from(value: u64) -> Self {
+        Self { value }
+    }
+}
+
+impl Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <u64 as Display>::fmt(&self.value, f)
+    }
+}
@@ -1,7 +1,7 @@
+use super::contents::Contents;
 use std::{
     convert::{AsRef, From},
     fmt::{self, Display},
-    fs,
     path::Path,
 };
 
@@ -16,9 +16,12 @@ impl Metric {
     /// lines in that file. If a file is not UTF-8 encoded as in the case of a binary jpeg file
     /// then `None` will be returned.
     pub fn init(path: impl AsRef<Path>) -> Option<Self> {
-        let data = fs::read_to_string(path.as_ref()).ok()?;
+        let contents = Contents::read(path.as_ref())?;
+        let data = contents.text()?.as_bytes();
 
-        let lines = data.lines().count();
+        // Same as `str::lines` which doesn't count a trailing line terminator as an empty line.
+        let unterminated = !data.is_empty() && !data.ends_with(b"\n");
+        let lines = memchr::memchr_iter(b'\n', data).count() + usize::from(unterminated);
 
         u64::try_from(lines).map(|value| Self { value }).ok()
     }
@@ -43,3 +46,11 @@ fn test_line_count() {
 
     assert_eq!(metric.value, 4);
 }
+
+#[test]
+fn test_unterminated_line_count() {
+    let tmp = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(tmp.path(), "Carcosa\n\nHastur").unwrap();
+
+    assert_eq!(Metric::init(tmp.path()).unwrap().value, 3);
+}
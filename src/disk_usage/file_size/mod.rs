@@ -3,7 +3,7 @@ use clap::ValueEnum;
 use std::{
     convert::From,
     fmt::{self, Display},
-    ops::AddAssign,
+    ops::{AddAssign, SubAssign},
 };
 
 /// Concerned with measuring file size in blocks.
@@ -13,6 +13,12 @@ pub mod block;
 /// Concerned with measuring file size in bytes, logical or physical.
 pub mod byte;
 
+/// Reading the contents of files for metrics that need to inspect them.
+mod contents;
+
+/// Concerned with measuring file size by inode count.
+pub mod inode_count;
+
 /// Concerned with measuring file size by line count.
 pub mod line_count;
 
@@ -27,6 +33,9 @@ pub enum FileSize {
     Word(word_count::Metric),
     Line(line_count::Metric),
     Byte(byte::Metric),
+    /// Logical and physical sizes, respectively, for when both are reported side by side.
+    Both(byte::Metric, byte::Metric),
+    Inodes(inode_count::Metric),
     #[cfg(unix)]
     Block(block::Metric),
 }
@@ -41,12 +50,22 @@ pub enum DiskUsage {
     #[default]
     Physical,
 
+    /// Logical and physical sizes side by side; sorting by size uses the physical size
+    Both,
+
+    /// Physical size excluding extents shared with reflinked copies and snapshots
+    #[cfg(target_os = "linux")]
+    Unique,
+
     /// How many total lines a file contains
     Line,
 
     /// How many total words a file contains
     Word,
 
+    /// How many filesystem entries, and thus inodes, a file or directory accounts for
+    Inodes,
+
     /// How many blocks are allocated to store the file
     #[cfg(unix)]
     Block,
@@ -58,8 +77,10 @@ impl FileSize {
     pub const fn value(&self) -> u64 {
         match self {
             Self::Byte(metric) => metric.value,
+            Self::Both(_, physical) => physical.value,
             Self::Line(metric) => metric.value,
             Self::Word(metric) => metric.value,
+            Self::Inodes(metric) => metric.value,
 
             #[cfg(unix)]
             Self::Block(metric) => metric.value,
@@ -70,9 +91,19 @@ impl FileSize {
 impl AddAssign<&Self> for FileSize {
     fn add_assign(&mut self, rhs: &Self) {
         match self {
-            Self::Byte(metric) => metric.value += rhs.value(),
+            Self::Byte(metric) => match rhs {
+                Self::Byte(rhs_metric) => *metric += rhs_metric,
+                _ => metric.value += rhs.value(),
+            },
+            Self::Both(logical, physical) => {
+                if let Self::Both(rhs_logical, rhs_physical) = rhs {
+                    *logical += rhs_logical;
+                    *physical += rhs_physical;
+                }
+            },
             Self::Line(metric) => metric.value += rhs.value(),
             Self::Word(metric) => metric.value += rhs.value(),
+            Self::Inodes(metric) => metric.value += rhs.value(),
 
             #[cfg(unix)]
             Self::Block(metric) => metric.value += rhs.value(),
@@ -80,18 +111,69 @@ impl AddAssign<&Self> for FileSize {
     }
 }
 
+impl SubAssign<&Self> for FileSize {
+    fn sub_assign(&mut self, rhs: &Self) {
+        match self {
+            Self::Byte(metric) => match rhs {
+                Self::Byte(rhs_metric) => *metric -= rhs_metric,
+                _ => metric.value = metric.value.saturating_sub(rhs.value()),
+            },
+            Self::Both(logical, physical) => {
+                if let Self::Both(rhs_logical, rhs_physical) = rhs {
+                    *logical -= rhs_logical;
+                    *physical -= rhs_physical;
+                }
+            },
+            Self::Line(metric) => metric.value = metric.value.saturating_sub(rhs.value()),
+            Self::Word(metric) => metric.value = metric.value.saturating_sub(rhs.value()),
+            Self::Inodes(metric) => metric.value = metric.value.saturating_sub(rhs.value()),
+
+            #[cfg(unix)]
+            Self::Block(metric) => metric.value = metric.value.saturating_sub(rhs.value()),
+        }
+    }
+}
+
+/// Applies the options that govern how byte sizes are reported to `metric`.
+pub const fn formatted(metric: byte::Metric, ctx: &Context) -> byte::Metric {
+    metric
+        .with_fixed_unit(ctx.fixed_unit())
+        .with_precision(ctx.precision)
+        .with_rounding(ctx.round)
+}
+
 impl From<&Context> for FileSize {
     fn from(ctx: &Context) -> Self {
-        use DiskUsage::{Line, Logical, Physical, Word};
+        use DiskUsage::{Both, Inodes, Line, Logical, Physical, Word};
+
+        let logical = || {
+            formatted(
+                byte::Metric::init_empty_logical(ctx.human, ctx.prefix_kind()),
+                ctx,
+            )
+        };
+
+        let physical = || {
+            formatted(
+                byte::Metric::init_empty_physical(ctx.human, ctx.prefix_kind()),
+                ctx,
+            )
+        };
 
         match ctx.disk_usage {
-            Logical => Self::Byte(byte::Metric::init_empty_logical(ctx.human, ctx.unit)),
-            Physical => Self::Byte(byte::Metric::init_empty_physical(ctx.human, ctx.unit)),
+            Logical => Self::Byte(logical()),
+            Physical => Self::Byte(physical()),
+            #[cfg(target_os = "linux")]
+            DiskUsage::Unique => Self::Byte(physical()),
+            Both => Self::Both(logical(), physical()),
             Line => Self::Line(line_count::Metric::default()),
             Word => Self::Word(word_count::Metric::default()),
+            Inodes => Self::Inodes(inode_count::Metric::default()),
 
             #[cfg(unix)]
-            DiskUsage::Block => Self::Block(block::Metric::default()),
+            DiskUsage::Block => {
+                Self::Block(block::Metric::default().with_block_size(ctx.block_size))
+            },
         }
     }
 }
@@ -101,7 +183,9 @@ impl Display for FileSize {
         match self {
             Self::Word(metric) => write!(f, "{metric}"),
             Self::Line(metric) => write!(f, "{metric}"),
+            Self::Inodes(metric) => write!(f, "{metric}"),
             Self::Byte(metric) => write!(f, "{metric}"),
+            Self::Both(logical, physical) => write!(f, "{logical} {physical}"),
 
             #[cfg(unix)]
             Self::Block(metric) => write!(f, "{metric}"),
@@ -1,7 +1,7 @@
+use super::contents::Contents;
 use std::{
     convert::{AsRef, From},
     fmt::{self, Display},
-    fs,
     path::Path,
 };
 
@@ -18,14 +18,29 @@ impl Metric {
     ///
     /// Words are UTF-8 encoded byte sequences delimited by Unicode Derived Core Property `White_Space`.
     pub fn init(path: impl AsRef<Path>) -> Option<Self> {
-        let data = fs::read_to_string(path.as_ref()).ok()?;
+        let contents = Contents::read(path.as_ref())?;
+        let data = contents.text()?;
 
-        let words = data.split_whitespace().count();
+        let words = if data.is_ascii() {
+            count_ascii_words(data.as_bytes())
+        } else {
+            data.split_whitespace().count()
+        };
 
         u64::try_from(words).map(|value| Self { value }).ok()
     }
 }
 
+/// Counts words bytewise which is only correct for ASCII text. `White_Space` includes the vertical
+/// tab unlike [`u8::is_ascii_whitespace`].
+fn count_ascii_words(data: &[u8]) -> usize {
+    let is_whitespace = |byte: &u8| matches!(byte, b' ' | b'\t'..=b'\r');
+
+    data.split(is_whitespace)
+        .filter(|word| !word.is_empty())
+        .count()
+}
+
 impl From<u64> for Metric {
     fn from(value: u64) -> Self {
         Self { value }
@@ -45,3 +60,13 @@ fn test_line_count() {
 
     assert_eq!(metric.value, 27);
 }
+
+#[test]
+fn test_ascii_word_count() {
+    let text = " the\tking\x0Bin\r\nyellow  ";
+
+    assert_eq!(
+        count_ascii_words(text.as_bytes()),
+        text.split_whitespace().count()
+    );
+}
@@ -5,7 +5,7 @@ use std::{
 };
 
 /// Determines whether to use SI prefixes or binary prefixes.
-#[derive(Copy, Clone, Debug, ValueEnum, Default)]
+#[derive(Copy, Clone, Debug, ValueEnum, Default, PartialEq, Eq)]
 pub enum PrefixKind {
     /// Displays disk usage using binary prefixes.
     #[default]
@@ -13,6 +13,78 @@ pub enum PrefixKind {
 
     /// Displays disk usage using SI prefixes.
     Si,
+
+    /// Displays disk usage as a plain count of bytes without a unit.
+    Raw,
+
+    /// Displays disk usage as a plain count of bytes with digits grouped by thousands.
+    RawGrouped,
+
+    /// Displays disk usage as a count of 512-byte sectors, rounded up.
+    Sectors,
+
+    /// Displays disk usage as a count of blocks of the filesystem each file resides on, rounded up.
+    Blocks,
+
+    /// Displays disk usage as a count of 4 KiB pages, rounded up.
+    Pages,
+}
+
+impl PrefixKind {
+    /// Answers whether sizes are reported as plain counts without a unit.
+    pub const fn is_raw(self) -> bool {
+        matches!(
+            self,
+            Self::Raw | Self::RawGrouped | Self::Sectors | Self::Blocks | Self::Pages
+        )
+    }
+}
+
+/// What sizes are reported in as given to '--unit'.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Unit {
+    /// Sizes scaled with prefixes of the given kind or reported as plain counts.
+    Prefix(PrefixKind),
+
+    /// Every size in the one unit regardless of its magnitude.
+    Fixed(FixedUnit),
+}
+
+/// How scaled sizes are rounded to the number of decimal places they're reported with.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, Default)]
+pub enum Rounding {
+    /// Round towards the larger value so that sizes are never under-reported.
+    Up,
+
+    /// Round towards the smaller value.
+    Down,
+
+    /// Round to the closest value.
+    #[default]
+    Nearest,
+}
+
+/// A single unit that every size is reported in rather than the closest human-readable unit.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum FixedUnit {
+    #[value(name = "B")]
+    Byte,
+    #[value(name = "KiB")]
+    Kibi,
+    #[value(name = "MiB")]
+    Mebi,
+    #[value(name = "GiB")]
+    Gibi,
+    #[value(name = "TiB")]
+    Tebi,
+    #[value(name = "KB")]
+    Kilo,
+    #[value(name = "MB")]
+    Mega,
+    #[value(name = "GB")]
+    Giga,
+    #[value(name = "TB")]
+    Tera,
 }
 
 /// Binary prefixes.
@@ -61,6 +133,31 @@ impl BinPrefix {
     }
 }
 
+impl FixedUnit {
+    /// Returns the human readable representation of the unit.
+    pub const fn as_str(&self) -> &str {
+        match self {
+            Self::Byte => "B",
+            Self::Kibi => "KiB",
+            Self::Mebi => "MiB",
+            Self::Gibi => "GiB",
+            Self::Tebi => "TiB",
+            Self::Kilo => "KB",
+            Self::Mega => "MB",
+            Self::Giga => "GB",
+            Self::Tera => "TB",
+        }
+    }
+
+    /// The kind of prefix that the unit belongs to, bytes going along with binary prefixes.
+    pub const fn prefix_kind(self) -> PrefixKind {
+        match self {
+            Self::Kilo | Self::Mega | Self::Giga | Self::Tera => PrefixKind::Si,
+            Self::Byte | Self::Kibi | Self::Mebi | Self::Gibi | Self::Tebi => PrefixKind::Bin,
+        }
+    }
+}
+
 pub trait UnitPrefix {
     fn base_value(&self) -> u64;
 }
@@ -89,6 +186,22 @@ impl UnitPrefix for BinPrefix {
     }
 }
 
+impl UnitPrefix for FixedUnit {
+    fn base_value(&self) -> u64 {
+        match self {
+            Self::Byte => 1,
+            Self::Kibi => BinPrefix::Kibi.base_value(),
+            Self::Mebi => BinPrefix::Mebi.base_value(),
+            Self::Gibi => BinPrefix::Gibi.base_value(),
+            Self::Tebi => BinPrefix::Tebi.base_value(),
+            Self::Kilo => SiPrefix::Kilo.base_value(),
+            Self::Mega => SiPrefix::Mega.base_value(),
+            Self::Giga => SiPrefix::Giga.base_value(),
+            Self::Tera => SiPrefix::Tera.base_value(),
+        }
+    }
+}
+
 /// Get the closest human-readable unit prefix for value.
 impl From<u64> for BinPrefix {
     fn from(value: u64) -> Self {
@@ -133,8 +246,104 @@ impl Display for BinPrefix {
     }
 }
 
+impl Display for FixedUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 impl Display for SiPrefix {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.as_str())
     }
 }
+
+/// Parses the argument of '--unit' which is either a [`PrefixKind`] such as `si` or `fixed:`
+/// followed by a [`FixedUnit`] such as `fixed:MiB`.
+pub fn parse_unit(unit: &str) -> Result<Unit, String> {
+    unit.strip_prefix("fixed:")
+        .map_or_else(
+            || PrefixKind::from_str(unit, false).map(Unit::Prefix),
+            |fixed| FixedUnit::from_str(fixed, true).map(Unit::Fixed),
+        )
+        .map_err(|_| {
+            format!("Expected a unit such as 'bin', 'si', 'raw', or 'fixed:MiB' but got '{unit}'")
+        })
+}
+
+/// Parses a human-readable size such as `512`, `5G`, `1.5MiB`, or `10KB` into bytes. Bare prefixes
+/// like `K` and `G` are binary as is the case with `du`; SI units have to be spelled out in full.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn parse_size(size: &str) -> Result<u64, String> {
+    let invalid = || format!("Expected a size such as '512', '5G', or '1.5MiB' but got '{size}'");
+
+    let trimmed = size.trim();
+    let split = trimmed
+        .find(|ch: char| ch.is_ascii_alphabetic())
+        .unwrap_or(trimmed.len());
+    let (value, unit) = trimmed.split_at(split);
+
+    let value = value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value >= 0.0)
+        .ok_or_else(invalid)?;
+
+    let unit = match unit.to_ascii_lowercase().as_str() {
+        "" | "b" => FixedUnit::Byte,
+        "k" | "kib" => FixedUnit::Kibi,
+        "m" | "mib" => FixedUnit::Mebi,
+        "g" | "gib" => FixedUnit::Gibi,
+        "t" | "tib" => FixedUnit::Tebi,
+        "kb" => FixedUnit::Kilo,
+        "mb" => FixedUnit::Mega,
+        "gb" => FixedUnit::Giga,
+        "tb" => FixedUnit::Tera,
+        _ => return Err(invalid()),
+    };
+
+    Ok((value * unit.base_value() as f64) as u64)
+}
+
+/// Parses the argument of '--block-size' which, as with `du -B`, is a size such as `4096` or `1M`
+/// that mustn't be zero.
+pub fn parse_block_size(size: &str) -> Result<u64, String> {
+    match parse_size(size)? {
+        0 => Err(format!(
+            "Expected a block size of at least one byte but got '{size}'"
+        )),
+        bytes => Ok(bytes),
+    }
+}
+
+#[test]
+fn parse_units() {
+    assert_eq!(parse_unit("si"), Ok(Unit::Prefix(PrefixKind::Si)));
+    assert_eq!(
+        parse_unit("raw-grouped"),
+        Ok(Unit::Prefix(PrefixKind::RawGrouped))
+    );
+    assert_eq!(parse_unit("fixed:MiB"), Ok(Unit::Fixed(FixedUnit::Mebi)));
+    assert_eq!(parse_unit("fixed:kb"), Ok(Unit::Fixed(FixedUnit::Kilo)));
+    assert!(parse_unit("fixed:").is_err());
+    assert!(parse_unit("MiB").is_err());
+}
+
+#[test]
+fn parse_sizes() {
+    assert_eq!(parse_size("512"), Ok(512));
+    assert_eq!(parse_size("5G"), Ok(5 * 2_u64.pow(30)));
+    assert_eq!(parse_size("1.5MiB"), Ok(3 * 2_u64.pow(19)));
+    assert_eq!(parse_size("10kb"), Ok(10_000));
+    assert!(parse_size("5X").is_err());
+    assert!(parse_size("-1K").is_err());
+}
+
+#[test]
+fn parse_block_sizes() {
+    assert_eq!(parse_block_size("4096"), Ok(4096));
+    assert_eq!(parse_block_size("1M"), Ok(2_u64.pow(20)));
+    assert!(parse_block_size("0").is_err());
+    assert!(parse_block_size("0.1").is_err());
+}
@@ -0,0 +1,134 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+/// Errors that may occur while writing a file with [write].
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to write output to '{}': {1}", .0.display())]
+pub struct Error(PathBuf, io::Error);
+
+/// Writes `contents` to `path` by way of a temporary file alongside it that is renamed over `path`
+/// once fully written. Readers of `path` therefore never observe a partially written file, and an
+/// existing file at `path` is left untouched if writing fails.
+pub fn write(path: &Path, contents: &str) -> Result<(), Error> {
+    let mut file = AtomicFile::create(path)?;
+
+    file.write_all(contents.as_bytes())
+        .map_err(|e| Error(path.to_path_buf(), e))?;
+
+    file.commit()
+}
+
+/// Same as [write] but for contents that are written bit by bit. Nothing takes the place of the
+/// destination until [`AtomicFile::commit`] is called, and the temporary file is removed if
+/// [`AtomicFile`] is dropped before then.
+pub struct AtomicFile {
+    path: PathBuf,
+    tmp: PathBuf,
+    file: Option<BufWriter<File>>,
+}
+
+impl AtomicFile {
+    /// Creates the temporary file that will eventually take the place of `path`.
+    pub fn create(path: &Path) -> Result<Self, Error> {
+        let tmp = temp_path(path);
+
+        let file = File::create(&tmp).map_err(|e| Error(path.to_path_buf(), e))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            tmp,
+            file: Some(BufWriter::new(file)),
+        })
+    }
+
+    /// Flushes everything written so far to disk and renames the temporary file over the
+    /// destination.
+    pub fn commit(mut self) -> Result<(), Error> {
+        let Some(file) = self.file.take() else {
+            return Ok(());
+        };
+
+        let committed = file
+            .into_inner()
+            .map_err(io::IntoInnerError::into_error)
+            .and_then(|file| file.sync_all())
+            .and_then(|()| fs::rename(&self.tmp, &self.path));
+
+        if let Err(e) = committed {
+            let _ = fs::remove_file(&self.tmp);
+            return Err(Error(self.path.clone(), e));
+        }
+
+        Ok(())
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.as_mut().map_or(Ok(0), |file| file.write(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.as_mut().map_or(Ok(()), Write::flush)
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if self.file.take().is_some() {
+            let _ = fs::remove_file(&self.tmp);
+        }
+    }
+}
+
+/// Hidden path in the same directory as `path` so that the final rename doesn't cross filesystems.
+fn temp_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+
+    path.with_file_name(format!(".{file_name}.erd-{}.tmp", process::id()))
+}
+
+#[test]
+fn write_replaces_existing() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::TempDir::new()?;
+    let path = dir.path().join("report.txt");
+
+    fs::write(&path, "stale")?;
+    write(&path, "fresh")?;
+
+    assert_eq!(fs::read_to_string(&path)?, "fresh");
+    assert_eq!(
+        fs::read_dir(dir.path())?.count(),
+        1,
+        "Temporary file left behind"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn drop_leaves_destination_untouched() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::TempDir::new()?;
+    let path = dir.path().join("records.jsonl");
+
+    fs::write(&path, "stale")?;
+
+    let mut file = AtomicFile::create(&path)?;
+    file.write_all(b"partial")?;
+    drop(file);
+
+    assert_eq!(fs::read_to_string(&path)?, "stale");
+    assert_eq!(
+        fs::read_dir(dir.path())?.count(),
+        1,
+        "Temporary file left behind"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use std::fs::Metadata;
+
+/// Flag set on files whose contents have been evicted to a cloud provider such as iCloud Drive.
+#[cfg(target_os = "macos")]
+const SF_DATALESS: u32 = 0x4000_0000;
+
+/// Attributes that cloud providers such as `OneDrive` set on files that aren't available locally.
+#[cfg(windows)]
+const CLOUD_ONLY_ATTRIBUTES: u32 =
+    FILE_ATTRIBUTE_OFFLINE | FILE_ATTRIBUTE_RECALL_ON_OPEN | FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS;
+
+#[cfg(windows)]
+const FILE_ATTRIBUTE_OFFLINE: u32 = 0x0000_1000;
+
+#[cfg(windows)]
+const FILE_ATTRIBUTE_RECALL_ON_OPEN: u32 = 0x0004_0000;
+
+#[cfg(windows)]
+const FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS: u32 = 0x0040_0000;
+
+/// Whether or not the file described by `metadata` is a placeholder whose contents live with a
+/// cloud provider rather than on the local disk.
+#[cfg(target_os = "macos")]
+pub fn is_placeholder(metadata: &Metadata) -> bool {
+    use std::os::macos::fs::MetadataExt;
+
+    metadata.st_flags() & SF_DATALESS != 0
+}
+
+/// Whether or not the file described by `metadata` is a placeholder whose contents live with a
+/// cloud provider rather than on the local disk.
+#[cfg(windows)]
+pub fn is_placeholder(metadata: &Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+
+    metadata.file_attributes() & CLOUD_ONLY_ATTRIBUTES != 0
+}
+
+/// Whether or not the file described by `metadata` is a placeholder whose contents live with a
+/// cloud provider rather than on the local disk. There is no such notion on this platform.
+#[cfg(not(any(windows, target_os = "macos")))]
+pub const fn is_placeholder(_metadata: &Metadata) -> bool {
+    false
+}
+
+#[test]
+fn local_file_is_not_placeholder() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+
+    std::fs::write(file.path(), "Kadath in the cold waste").unwrap();
+
+    assert!(!is_placeholder(&file.as_file().metadata().unwrap()));
+}
@@ -0,0 +1,145 @@
+use std::{collections::HashSet, fs::File, io, os::fd::AsRawFd, path::Path};
+
+/// Retrieves the extent map of a file. musl declares the request argument of `ioctl` as a
+/// `c_int` rather than a `c_ulong`.
+#[cfg(not(target_env = "musl"))]
+const FS_IOC_FIEMAP: libc::c_ulong = 0xC020_660B;
+#[cfg(target_env = "musl")]
+#[allow(clippy::cast_possible_wrap)]
+const FS_IOC_FIEMAP: libc::c_int = 0xC020_660B_u32 as libc::c_int;
+
+/// Set on the final extent of a file.
+const FIEMAP_EXTENT_LAST: u32 = 0x0001;
+
+/// Set on extents that are shared with other files, e.g. reflinked copies or snapshots.
+const FIEMAP_EXTENT_SHARED: u32 = 0x2000;
+
+/// How many extents to request per `ioctl`.
+const EXTENTS_PER_REQUEST: u32 = 64;
+
+/// Mirrors `struct fiemap_extent` from `linux/fiemap.h`.
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct Extent {
+    logical: u64,
+    physical: u64,
+    length: u64,
+    reserved64: [u64; 2],
+    flags: u32,
+    reserved: [u32; 3],
+}
+
+/// Mirrors `struct fiemap` from `linux/fiemap.h` followed by room for its extents.
+#[repr(C)]
+struct Request {
+    start: u64,
+    length: u64,
+    flags: u32,
+    mapped_extents: u32,
+    extent_count: u32,
+    reserved: u32,
+    extents: [Extent; EXTENTS_PER_REQUEST as usize],
+}
+
+/// An extent that a file shares with other files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SharedExtent {
+    /// Byte offset of the extent on its device.
+    pub physical: u64,
+    pub length: u64,
+}
+
+/// Returns the extents of the file at `path` that are shared with other files. Copy-on-write
+/// filesystems such as btrfs and XFS share extents between reflinked copies and snapshots so that
+/// their data is only stored once.
+pub fn shared_extents(path: &Path) -> io::Result<Vec<SharedExtent>> {
+    let file = File::open(path)?;
+    let mut shared = vec![];
+    let mut start = 0;
+
+    loop {
+        let mut request = Request {
+            start,
+            length: u64::MAX - start,
+            flags: 0,
+            mapped_extents: 0,
+            extent_count: EXTENTS_PER_REQUEST,
+            reserved: 0,
+            extents: [Extent::default(); EXTENTS_PER_REQUEST as usize],
+        };
+
+        let ret = unsafe {
+            libc::ioctl(
+                file.as_raw_fd(),
+                FS_IOC_FIEMAP,
+                std::ptr::addr_of_mut!(request),
+            )
+        };
+
+        if ret != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let extents = &request.extents[..request.mapped_extents as usize];
+
+        let Some(last) = extents.last() else {
+            return Ok(shared);
+        };
+
+        shared.extend(
+            extents
+                .iter()
+                .filter(|extent| extent.flags & FIEMAP_EXTENT_SHARED != 0)
+                .map(|extent| SharedExtent {
+                    physical: extent.physical,
+                    length: extent.length,
+                }),
+        );
+
+        if last.flags & FIEMAP_EXTENT_LAST != 0 {
+            return Ok(shared);
+        }
+
+        start = last.logical + last.length;
+    }
+}
+
+/// Records `extents` of a file on device `dev` as accounted for in `claimed` and returns how many
+/// bytes hadn't been claimed by another file yet, so that each shared extent is attributed once.
+pub fn claim(claimed: &mut HashSet<(u64, u64)>, dev: u64, extents: &[SharedExtent]) -> u64 {
+    extents
+        .iter()
+        .filter(|extent| claimed.insert((dev, extent.physical)))
+        .map(|extent| extent.length)
+        .sum()
+}
+
+#[test]
+fn claim_once() {
+    let mut claimed = HashSet::new();
+
+    let original = [
+        SharedExtent {
+            physical: 4096,
+            length: 8192,
+        },
+        SharedExtent {
+            physical: 65536,
+            length: 4096,
+        },
+    ];
+    let copy = [original[1]];
+
+    assert_eq!(claim(&mut claimed, 1, &original), 12288);
+    assert_eq!(claim(&mut claimed, 1, &copy), 0);
+    assert_eq!(claim(&mut claimed, 2, &copy), 4096);
+}
+
+#[test]
+fn request_layout() {
+    assert_eq!(std::mem::size_of::<Extent>(), 56);
+    assert_eq!(
+        std::mem::size_of::<Request>(),
+        32 + 56 * EXTENTS_PER_REQUEST as usize
+    );
+}
@@ -0,0 +1,33 @@
+use std::path::Path;
+
+/// Sniffs the leading bytes of the file at `path` to determine its MIME type, returning `None` if
+/// it couldn't be read or its content type isn't recognized.
+pub fn mime_type(path: &Path) -> Option<&'static str> {
+    infer::get_from_path(path)
+        .ok()
+        .flatten()
+        .map(|kind| kind.mime_type())
+}
+
+/// Checks whether `mime` satisfies `pattern`, where `pattern` is a MIME type whose type and/or
+/// subtype may be the wildcard '*', e.g. 'image/*'. Comparison is case-insensitive.
+pub fn mime_matches(pattern: &str, mime: &str) -> bool {
+    let (Some((pat_type, pat_subtype)), Some((mime_type, mime_subtype))) =
+        (pattern.split_once('/'), mime.split_once('/'))
+    else {
+        return false;
+    };
+
+    let matches = |pat: &str, part: &str| pat == "*" || pat.eq_ignore_ascii_case(part);
+
+    matches(pat_type, mime_type) && matches(pat_subtype, mime_subtype)
+}
+
+#[test]
+fn mime_matches_wildcards() {
+    assert!(mime_matches("image/*", "image/png"));
+    assert!(mime_matches("*/pdf", "application/pdf"));
+    assert!(mime_matches("VIDEO/MP4", "video/mp4"));
+    assert!(!mime_matches("image/*", "video/mp4"));
+    assert!(!mime_matches("image", "image/png"));
+}
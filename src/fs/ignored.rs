@@ -0,0 +1,112 @@
+use ignore::{
+    gitignore::{Gitignore, GitignoreBuilder},
+    Match,
+};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+/// The ignore rules that apply to a tree, used to tell which of its entries a walker that respects
+/// them would skip. The ignore files of each directory are only read the first time an entry
+/// beneath it is checked.
+pub struct Rules {
+    root: PathBuf,
+    global: Gitignore,
+    dirs: Mutex<HashMap<PathBuf, Arc<DirRules>>>,
+}
+
+/// The rules of the ignore files in a single directory.
+struct DirRules {
+    rules: Gitignore,
+
+    /// Whether the directory resides in a git repository.
+    in_repo: bool,
+}
+
+impl Rules {
+    /// Initializes [Rules] for the tree rooted at `root`, which is expected to be canonical.
+    pub fn new(root: PathBuf) -> Self {
+        let (global, _) = Gitignore::global();
+
+        Self {
+            root,
+            global,
+            dirs: Mutex::default(),
+        }
+    }
+
+    /// Whether `path` would be skipped, either because it's ignored itself or because one of the
+    /// directories between it and the root is.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+
+        let mut current = self.root.clone();
+        let mut components = relative.components().peekable();
+
+        while let Some(component) = components.next() {
+            current.push(component);
+
+            let is_last = components.peek().is_none();
+
+            if self.matched(&current, is_dir || !is_last) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Whether `path` itself is ignored. The rules of the nearest directory with a say in the
+    /// matter take precedence, just as they do for a walker.
+    fn matched(&self, path: &Path, is_dir: bool) -> bool {
+        let mut in_repo = false;
+
+        for (depth, dir) in path.ancestors().skip(1).enumerate() {
+            let dir_rules = self.rules_of(dir);
+
+            if depth == 0 {
+                in_repo = dir_rules.in_repo;
+            }
+
+            match dir_rules.rules.matched(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => (),
+            }
+        }
+
+        in_repo && self.global.matched(path, is_dir).is_ignore()
+    }
+
+    /// The combined rules of the ignore files in `dir`. Rules that come from git only apply inside
+    /// of a repository and those of '.ignore' take precedence over those of '.gitignore'.
+    fn rules_of(&self, dir: &Path) -> Arc<DirRules> {
+        let mut dirs = self.dirs.lock().unwrap();
+
+        if let Some(dir_rules) = dirs.get(dir) {
+            return Arc::clone(dir_rules);
+        }
+
+        let in_repo = dir.ancestors().any(|dir| dir.join(".git").exists());
+        let mut builder = GitignoreBuilder::new(dir);
+
+        if in_repo {
+            builder.add(dir.join(".git").join("info").join("exclude"));
+            builder.add(dir.join(".gitignore"));
+        }
+
+        builder.add(dir.join(".ignore"));
+
+        let rules = builder.build().unwrap_or_else(|_| Gitignore::empty());
+        let dir_rules = Arc::new(DirRules { rules, in_repo });
+
+        dirs.insert(dir.to_path_buf(), Arc::clone(&dir_rules));
+        drop(dirs);
+
+        dir_rules
+    }
+}
@@ -1,21 +1,61 @@
 use ignore::DirEntry;
 use std::{fs, path::PathBuf};
 
+/// Writing files such that they're either fully written or left untouched.
+pub mod atomic;
+
+/// Detecting files whose contents are only available from a cloud provider.
+pub mod cloud;
+
+/// Detecting extents that are shared between files on copy-on-write filesystems.
+#[cfg(target_os = "linux")]
+pub mod fiemap;
+
+/// Detecting the content type of files from their leading bytes.
+pub mod filetype;
+
+/// Telling which entries ignore files such as '.gitignore' would exclude.
+pub mod ignored;
+
 /// Operations pertaining to underlying inodes of files.
 pub mod inode;
 
+/// Making sense of paths that reside in the Nix store.
+#[cfg(feature = "nix")]
+pub mod nix;
+
 /// Unix file permissions.
 #[cfg(unix)]
 pub mod permissions;
 
+/// Querying disk quotas.
+#[cfg(target_os = "linux")]
+pub mod quota;
+
+/// Temporary read-only filesystem snapshots for point-in-time scans.
+#[cfg(target_os = "linux")]
+pub mod snapshot;
+
 /// Determining whether or not a file has extended attributes.
 #[cfg(unix)]
 pub mod xattr;
 
+/// Categorizing XDG base directories and their platform specific equivalents.
+pub mod xdg;
+
+/// Reading properties of ZFS datasets.
+#[cfg(feature = "zfs")]
+pub mod zfs;
+
 /// Concerned with determining group and owner of file.
 #[cfg(unix)]
 pub mod ug;
 
+/// Returns `true` if `dir_entry` is a symlink whose target does not exist.
+pub fn is_broken_symlink(dir_entry: &DirEntry) -> bool {
+    dir_entry.path_is_symlink() && fs::metadata(dir_entry.path()).is_err()
+}
+
 /// Returns the path to the target of the soft link. Returns `None` if provided `dir_entry` isn't a
 /// symlink.
 pub fn symlink_target(dir_entry: &DirEntry) -> Option<PathBuf> {
@@ -0,0 +1,102 @@
+use std::path::{Component, Path};
+
+/// Directory that houses the Nix store.
+pub const STORE_DIR: &str = "/nix/store";
+
+/// Length of the base-32 hash that prefixes the name of every entry of the Nix store.
+const HASH_LEN: usize = 32;
+
+/// Characters that make up Nix's base-32 alphabet which omits 'e', 'o', 'u', and 't'.
+const HASH_ALPHABET: &str = "0123456789abcdfghijklmnpqrsvwxyz";
+
+/// A top-level entry of the Nix store, i.e. the output of a derivation, split up into its hash and
+/// its human readable name.
+#[derive(Debug, PartialEq, Eq)]
+pub struct StorePath<'a> {
+    pub hash: &'a str,
+    pub name: &'a str,
+}
+
+impl<'a> StorePath<'a> {
+    /// Parses the store entry that `path` either is or resides in. Returns `None` if `path` isn't
+    /// inside of the Nix store.
+    pub fn parse(path: &'a Path) -> Option<Self> {
+        let mut components = path.strip_prefix(STORE_DIR).ok()?.components();
+
+        let Some(Component::Normal(entry)) = components.next() else {
+            return None;
+        };
+
+        Self::from_file_name(entry.to_str()?)
+    }
+
+    /// Splits `file_name` of the form `<hash>-<name>` into its constituent parts.
+    pub fn from_file_name(file_name: &'a str) -> Option<Self> {
+        let hash = file_name.get(..HASH_LEN)?;
+        let name = file_name.get(HASH_LEN..)?.strip_prefix('-')?;
+
+        if name.is_empty() || !hash.chars().all(|c| HASH_ALPHABET.contains(c)) {
+            return None;
+        }
+
+        Some(Self { hash, name })
+    }
+
+    /// Name of the derivation that this entry belongs to, which is shared by its outputs as well as
+    /// the `.drv` file that produced them.
+    pub fn derivation(&self) -> &'a str {
+        self.name.strip_suffix(".drv").unwrap_or(self.name)
+    }
+
+    /// Whether or not this entry is a store derivation, i.e. a `.drv` file, rather than an output.
+    pub fn is_drv(&self) -> bool {
+        self.name.strip_suffix(".drv").is_some()
+    }
+}
+
+/// Renders `path` with the hash of the store entry it resides in stripped out, e.g.
+/// `/nix/store/<hash>-hello-2.12.1/bin/hello` becomes `hello-2.12.1/bin/hello`. Returns `None` if
+/// `path` isn't inside of the Nix store.
+pub fn readable_path(path: &Path) -> Option<String> {
+    let store_path = StorePath::parse(path)?;
+
+    let rest = path
+        .strip_prefix(STORE_DIR)
+        .ok()?
+        .components()
+        .skip(1)
+        .collect::<std::path::PathBuf>();
+
+    Some(Path::new(store_path.name).join(rest).display().to_string())
+}
+
+#[test]
+fn parse_store_paths() {
+    let hello = Path::new("/nix/store/0c3s4vjzrnv8d8p2ihmvm7lk7lvlbqhv-hello-2.12.1/bin/hello");
+
+    assert_eq!(
+        StorePath::parse(hello),
+        Some(StorePath {
+            hash: "0c3s4vjzrnv8d8p2ihmvm7lk7lvlbqhv",
+            name: "hello-2.12.1",
+        })
+    );
+    assert_eq!(
+        readable_path(hello).as_deref(),
+        Some("hello-2.12.1/bin/hello")
+    );
+
+    let drv =
+        StorePath::from_file_name("9xb3wdn8k4zrk5vq6xnsl3xbdrgq3l2x-hello-2.12.1.drv").unwrap();
+
+    assert!(drv.is_drv());
+    assert_eq!(drv.derivation(), "hello-2.12.1");
+    assert_eq!(
+        StorePath::parse(hello).unwrap().derivation(),
+        "hello-2.12.1"
+    );
+
+    assert!(StorePath::parse(Path::new("/nix/store/.links")).is_none());
+    assert!(StorePath::parse(Path::new("/usr/bin/hello")).is_none());
+    assert!(StorePath::from_file_name("eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee-hello").is_none());
+}
@@ -0,0 +1,148 @@
+use crate::fs::ug;
+use std::fmt::{self, Display};
+
+/// Name of the extended attribute that holds the access ACL of a file.
+pub const ACCESS_XATTR: &str = "system.posix_acl_access";
+
+/// Name of the extended attribute that holds the default ACL of a directory, which is inherited by
+/// entries created within it.
+pub const DEFAULT_XATTR: &str = "system.posix_acl_default";
+
+/// Version of the binary format of ACLs as stored in extended attributes.
+const VERSION: u32 = 2;
+
+/// Size in bytes of the header and of each entry of the binary format.
+const HEADER_LEN: usize = 4;
+const ENTRY_LEN: usize = 8;
+
+/// Whom an [Entry] grants permissions to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tag {
+    /// The owner of the file.
+    UserObj,
+
+    /// A user identified by uid.
+    User(u32),
+
+    /// The group of the file.
+    GroupObj,
+
+    /// A group identified by gid.
+    Group(u32),
+
+    /// Upper bound on the permissions granted to named users and groups as well as the group of
+    /// the file.
+    Mask,
+
+    /// Everyone else.
+    Other,
+}
+
+/// A single entry of an ACL, displayed in the same format as `getfacl`, e.g. `user:alice:r-x`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub tag: Tag,
+    pub perms: u16,
+}
+
+/// Decodes the value of [`ACCESS_XATTR`] or [`DEFAULT_XATTR`] which is a little-endian header
+/// followed by entries each made up of a tag, permissions, and an ID. Returns `None` if `value`
+/// is malformed.
+pub fn decode(value: &[u8]) -> Option<Vec<Entry>> {
+    if value.len() < HEADER_LEN {
+        return None;
+    }
+
+    let (header, entries) = value.split_at(HEADER_LEN);
+
+    if u32::from_le_bytes(header.try_into().ok()?) != VERSION || entries.len() % ENTRY_LEN != 0 {
+        return None;
+    }
+
+    entries
+        .chunks_exact(ENTRY_LEN)
+        .map(|entry| {
+            let tag = u16::from_le_bytes([entry[0], entry[1]]);
+            let perms = u16::from_le_bytes([entry[2], entry[3]]);
+            let id = u32::from_le_bytes([entry[4], entry[5], entry[6], entry[7]]);
+
+            let tag = match tag {
+                0x01 => Tag::UserObj,
+                0x02 => Tag::User(id),
+                0x04 => Tag::GroupObj,
+                0x08 => Tag::Group(id),
+                0x10 => Tag::Mask,
+                0x20 => Tag::Other,
+                _ => return None,
+            };
+
+            Some(Entry { tag, perms })
+        })
+        .collect()
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (kind, qualifier) = match self.tag {
+            Tag::UserObj => ("user", String::new()),
+            Tag::User(uid) => (
+                "user",
+                ug::user_name(uid).unwrap_or_else(|| uid.to_string()),
+            ),
+            Tag::GroupObj => ("group", String::new()),
+            Tag::Group(gid) => (
+                "group",
+                ug::group_name(gid).unwrap_or_else(|| gid.to_string()),
+            ),
+            Tag::Mask => ("mask", String::new()),
+            Tag::Other => ("other", String::new()),
+        };
+
+        let perm = |bit, ch| if self.perms & bit == 0 { '-' } else { ch };
+
+        write!(
+            f,
+            "{kind}:{qualifier}:{}{}{}",
+            perm(0o4, 'r'),
+            perm(0o2, 'w'),
+            perm(0o1, 'x')
+        )
+    }
+}
+
+#[test]
+fn decode_acl() {
+    let mut value = VERSION.to_le_bytes().to_vec();
+
+    for (tag, perms, id) in [(0x01, 6, u32::MAX), (0x08, 5, 1000), (0x10, 7, u32::MAX)] {
+        value.extend(u16::to_le_bytes(tag));
+        value.extend(u16::to_le_bytes(perms));
+        value.extend(u32::to_le_bytes(id));
+    }
+
+    let entries = decode(&value).unwrap();
+
+    assert_eq!(
+        entries,
+        [
+            Entry {
+                tag: Tag::UserObj,
+                perms: 6
+            },
+            Entry {
+                tag: Tag::Group(1000),
+                perms: 5
+            },
+            Entry {
+                tag: Tag::Mask,
+                perms: 7
+            },
+        ]
+    );
+
+    assert_eq!(entries[0].to_string(), "user::rw-");
+    assert_eq!(entries[2].to_string(), "mask::rwx");
+
+    assert!(decode(&value[..value.len() - 1]).is_none());
+    assert!(decode(&[1, 0, 0, 0]).is_none());
+}
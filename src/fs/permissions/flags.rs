@@ -0,0 +1,105 @@
+use std::{
+    fmt::{self, Display},
+    fs::Metadata,
+};
+
+/// Do not dump the file.
+const UF_NODUMP: u32 = 0x0000_0001;
+
+/// The file may not be changed.
+const UF_IMMUTABLE: u32 = 0x0000_0002;
+
+/// Writes to the file may only append.
+const UF_APPEND: u32 = 0x0000_0004;
+
+/// The directory is opaque when viewed through a union mount.
+const UF_OPAQUE: u32 = 0x0000_0008;
+
+/// The file is hidden from graphical file managers.
+const UF_HIDDEN: u32 = 0x0000_8000;
+
+/// The file is archived.
+const SF_ARCHIVED: u32 = 0x0001_0000;
+
+/// The file may not be changed, not even by the superuser.
+const SF_IMMUTABLE: u32 = 0x0002_0000;
+
+/// Writes to the file may only append, even those of the superuser.
+const SF_APPEND: u32 = 0x0004_0000;
+
+/// Each flag along with the name that `chflags(1)` and `ls -lO` know it by.
+const NAMES: [(u32, &str); 8] = [
+    (UF_NODUMP, "nodump"),
+    (UF_IMMUTABLE, "uchg"),
+    (UF_APPEND, "uappnd"),
+    (UF_OPAQUE, "opaque"),
+    (UF_HIDDEN, "hidden"),
+    (SF_ARCHIVED, "arch"),
+    (SF_IMMUTABLE, "schg"),
+    (SF_APPEND, "sappnd"),
+];
+
+/// The file flags of a file as set by `chflags(1)`, such as `uchg` for files that are immutable.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Flags(u32);
+
+impl Flags {
+    /// Reads the flags of the file described by `metadata`.
+    pub fn of(metadata: &Metadata) -> Self {
+        #[cfg(target_os = "freebsd")]
+        use std::os::freebsd::fs::MetadataExt;
+        #[cfg(target_os = "macos")]
+        use std::os::macos::fs::MetadataExt;
+
+        Self(metadata.st_flags())
+    }
+
+    /// Whether or not every flag of `other` is also set in `self`.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+/// Parses the comma-separated flag names given to '--flags', e.g. `uchg` or `schg,hidden`.
+pub fn parse(s: &str) -> Result<Flags, String> {
+    s.split(',').try_fold(Flags::default(), |flags, name| {
+        NAMES
+            .iter()
+            .find(|(_, known)| *known == name)
+            .map(|(flag, _)| Flags(flags.0 | flag))
+            .ok_or_else(|| format!("'{name}' is not a file flag such as 'uchg' or 'hidden'"))
+    })
+}
+
+impl Display for Flags {
+    /// Lists the names of the flags that are set separated by commas, or `-` if there are none,
+    /// same as `ls -lO`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = NAMES
+            .iter()
+            .filter_map(|(flag, name)| (self.0 & flag != 0).then_some(*name))
+            .collect::<Vec<_>>();
+
+        if names.is_empty() {
+            return f.pad("-");
+        }
+
+        f.pad(&names.join(","))
+    }
+}
+
+#[test]
+fn parse_flags() {
+    assert_eq!(parse("uchg"), Ok(Flags(UF_IMMUTABLE)));
+    assert_eq!(parse("schg,hidden"), Ok(Flags(SF_IMMUTABLE | UF_HIDDEN)));
+    assert!(parse("immutable").is_err());
+    assert!(parse("").is_err());
+}
+
+#[test]
+fn display_flags() {
+    assert_eq!(Flags::default().to_string(), "-");
+    assert_eq!(Flags(UF_HIDDEN | UF_IMMUTABLE).to_string(), "uchg,hidden");
+    assert!(Flags(UF_IMMUTABLE | UF_HIDDEN).contains(Flags(UF_IMMUTABLE)));
+    assert!(!Flags(UF_HIDDEN).contains(Flags(UF_IMMUTABLE)));
+}
@@ -6,9 +6,17 @@ use std::{
     os::unix::fs::PermissionsExt,
 };
 
+/// Decoding POSIX access control lists.
+#[cfg(target_os = "linux")]
+pub mod acl;
+
 /// For working with permissions for a particular class i.e. user, group, or other.
 pub mod class;
 
+/// File flags as set by `chflags(1)` on macOS and FreeBSD.
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+pub mod flags;
+
 /// File permission related errors.
 pub mod error;
 
@@ -46,6 +54,10 @@ pub struct FileMode {
 /// attributes.
 pub struct FileModeXAttrs<'a>(pub &'a FileMode);
 
+/// Implements [Display] which presents symbolic notation of file permissions with an access
+/// control list.
+pub struct FileModeAcl<'a>(pub &'a FileMode);
+
 impl FileMode {
     /// Constructor for [`FileMode`].
     pub const fn new(
@@ -108,6 +120,14 @@ impl Display for FileModeXAttrs<'_> {
     }
 }
 
+/// For representing file permissions with an access control list in symbolic notation.
+impl Display for FileModeAcl<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mode = self.0;
+        write!(f, "{mode}+")
+    }
+}
+
 /// For the octal representation of permissions
 impl Octal for FileMode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
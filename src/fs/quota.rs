@@ -0,0 +1,101 @@
+use crate::disk_usage::{file_size::byte, units::PrefixKind};
+use std::{
+    fmt::{self, Display},
+    fs::File,
+    os::fd::AsRawFd,
+    path::Path,
+};
+
+/// Retrieves the disk quota of a given user.
+const Q_GETQUOTA: libc::c_uint = 0x80_0007;
+
+/// Type of quota that applies to a user.
+const USRQUOTA: libc::c_uint = 0;
+
+/// Set in `dqb_valid` if the block limits are valid.
+const QIF_BLIMITS: u32 = 1;
+
+/// Set in `dqb_valid` if the current space usage is valid.
+const QIF_SPACE: u32 = 4;
+
+/// The size in bytes of the blocks that block limits are expressed in.
+const QIF_DQBLKSIZE: u64 = 1024;
+
+/// The current user's disk usage against their quota on a given filesystem.
+pub struct Quota {
+    used: u64,
+    limit: u64,
+    prefix_kind: PrefixKind,
+}
+
+impl Quota {
+    /// Queries the quota of the current user on the filesystem that `path` resides on. Returns
+    /// `None` if quotas aren't enabled, no limit applies, or the query isn't permitted.
+    pub fn query(path: &Path, prefix_kind: PrefixKind) -> Option<Self> {
+        let file = File::open(path).ok()?;
+
+        unsafe { query_fd(&file) }.map(|(used, limit)| Self {
+            used,
+            limit,
+            prefix_kind,
+        })
+    }
+}
+
+impl Display for Quota {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fmt_bytes = |value| {
+            let mut metric = byte::Metric::init_empty_logical(true, self.prefix_kind);
+            metric.value = value;
+            metric.to_string()
+        };
+
+        let percent = self.used as f64 / self.limit as f64 * 100.0;
+
+        write!(
+            f,
+            "{} of {} quota used ({percent:.0}%)",
+            fmt_bytes(self.used),
+            fmt_bytes(self.limit)
+        )
+    }
+}
+
+/// Issues `quotactl_fd` for the filesystem of `file` and returns the used bytes alongside the
+/// limit in bytes. The hard limit is preferred over the soft limit if both are set.
+unsafe fn query_fd(file: &File) -> Option<(u64, u64)> {
+    let mut dqblk = std::mem::zeroed::<libc::dqblk>();
+
+    let cmd = (Q_GETQUOTA << 8) | USRQUOTA;
+
+    let ret = libc::syscall(
+        libc::SYS_quotactl_fd,
+        file.as_raw_fd(),
+        cmd,
+        libc::getuid(),
+        std::ptr::addr_of_mut!(dqblk),
+    );
+
+    if ret != 0 || dqblk.dqb_valid & (QIF_BLIMITS | QIF_SPACE) != QIF_BLIMITS | QIF_SPACE {
+        return None;
+    }
+
+    let limit = if dqblk.dqb_bhardlimit > 0 {
+        dqblk.dqb_bhardlimit
+    } else {
+        dqblk.dqb_bsoftlimit
+    };
+
+    (limit > 0).then_some((dqblk.dqb_curspace, limit * QIF_DQBLKSIZE))
+}
+
+#[test]
+fn display_quota() {
+    let quota = Quota {
+        used: 512,
+        limit: 2048,
+        prefix_kind: PrefixKind::Bin,
+    };
+
+    assert_eq!(format!("{quota}"), "512 B of 2.0 KiB quota used (25%)");
+}
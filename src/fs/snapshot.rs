@@ -0,0 +1,227 @@
+use std::{
+    ffi::CString,
+    fs, io, mem,
+    os::unix::{ffi::OsStrExt, fs::MetadataExt},
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+
+/// Value of `f_type` as reported by `statfs` for btrfs.
+const BTRFS_MAGIC: i64 = 0x9123_683E;
+
+/// Value of `f_type` as reported by `statfs` for ZFS.
+const ZFS_MAGIC: i64 = 0x2FC1_2FC1;
+
+/// Inode number of the root directory of every btrfs subvolume.
+const BTRFS_SUBVOLUME_INO: u64 = 256;
+
+/// Errors that may occur while creating a [Snapshot].
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("'--consistent' requires '{0}' to be a btrfs subvolume or to reside in a ZFS dataset")]
+    Unsupported(PathBuf),
+
+    #[error("Failed to create snapshot with '{0}': {1}")]
+    Command(String, String),
+
+    #[error("Failed to create snapshot: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// A temporary read-only snapshot of a directory which is destroyed once dropped. Scanning the
+/// snapshot rather than the live directory ensures that results reflect a single point in time.
+pub struct Snapshot {
+    kind: Kind,
+    path: PathBuf,
+}
+
+/// The mechanism by which a [Snapshot] was taken along with what's needed to destroy it.
+enum Kind {
+    /// A snapshot of a subvolume which is placed in a staging directory next to the subvolume.
+    Btrfs { staging: PathBuf },
+
+    /// A snapshot of a dataset identified by `<dataset>@<name>`.
+    Zfs { name: String },
+}
+
+impl Snapshot {
+    /// Takes a snapshot of `dir` which is expected to be canonical.
+    pub fn create(dir: &Path) -> Result<Self, Error> {
+        match fs_type(dir) {
+            Some(BTRFS_MAGIC) if fs::metadata(dir)?.ino() == BTRFS_SUBVOLUME_INO => {
+                Self::create_btrfs(dir)
+            },
+            Some(ZFS_MAGIC) => Self::create_zfs(dir),
+            _ => Err(Error::Unsupported(dir.to_path_buf())),
+        }
+    }
+
+    /// Path of the snapshotted directory which is to be traversed in place of the original.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The snapshot is placed in a hidden staging directory alongside `dir` so that the root of
+    /// the snapshot retains the name of `dir`.
+    fn create_btrfs(dir: &Path) -> Result<Self, Error> {
+        let (Some(parent), Some(file_name)) = (dir.parent(), dir.file_name()) else {
+            return Err(Error::Unsupported(dir.to_path_buf()));
+        };
+
+        let staging = parent.join(format!(".erd-snapshot-{}", process::id()));
+        fs::create_dir(&staging)?;
+
+        let path = staging.join(file_name);
+
+        let created = run(Command::new("btrfs")
+            .args(["subvolume", "snapshot", "-r"])
+            .arg(dir)
+            .arg(&path))
+        .map(drop);
+
+        if let Err(e) = created {
+            let _ = fs::remove_dir(&staging);
+            return Err(e);
+        }
+
+        Ok(Self {
+            kind: Kind::Btrfs { staging },
+            path,
+        })
+    }
+
+    /// Snapshots the dataset that `dir` resides in and locates `dir` within the snapshot through
+    /// the hidden `.zfs` directory at the root of the dataset.
+    fn create_zfs(dir: &Path) -> Result<Self, Error> {
+        let output = run(Command::new("zfs").args([
+            "list",
+            "-H",
+            "-o",
+            "name,mountpoint",
+            "-t",
+            "filesystem",
+        ]))?;
+
+        let datasets = parse_datasets(&output);
+
+        let Some((dataset, mountpoint)) = containing_dataset(&datasets, dir) else {
+            return Err(Error::Unsupported(dir.to_path_buf()));
+        };
+
+        let snapshot = format!("erd-{}", process::id());
+        let name = format!("{dataset}@{snapshot}");
+
+        run(Command::new("zfs").args(["snapshot", &name]))?;
+
+        let path = mountpoint
+            .join(".zfs/snapshot")
+            .join(snapshot)
+            .join(dir.strip_prefix(mountpoint).unwrap_or(dir));
+
+        Ok(Self {
+            kind: Kind::Zfs { name },
+            path,
+        })
+    }
+}
+
+impl Drop for Snapshot {
+    fn drop(&mut self) {
+        let destroyed = match &self.kind {
+            Kind::Btrfs { staging } => run(Command::new("btrfs")
+                .args(["subvolume", "delete"])
+                .arg(&self.path))
+            .and_then(|_| fs::remove_dir(staging).map_err(Error::from)),
+            Kind::Zfs { name } => run(Command::new("zfs").args(["destroy", name])).map(drop),
+        };
+
+        if let Err(e) = destroyed {
+            eprintln!(
+                "Failed to remove snapshot at '{}': {e}",
+                self.path.display()
+            );
+        }
+    }
+}
+
+/// Runs `cmd` to completion, returning its standard output or reporting its standard error if it
+/// fails.
+fn run(cmd: &mut Command) -> Result<String, Error> {
+    let output = cmd.output()?;
+
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+    }
+
+    let program = cmd.get_program().to_string_lossy().into_owned();
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_owned();
+
+    Err(Error::Command(program, stderr))
+}
+
+/// Returns the type of filesystem that `path` resides on as reported by `statfs`.
+// `f_type` is signed on glibc but unsigned on musl; filesystem magic numbers fit either way.
+#[allow(clippy::cast_possible_wrap, clippy::unnecessary_cast)]
+fn fs_type(path: &Path) -> Option<i64> {
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+
+    let mut buf = unsafe { mem::zeroed::<libc::statfs>() };
+
+    let ret = unsafe { libc::statfs(path.as_ptr(), std::ptr::addr_of_mut!(buf)) };
+
+    (ret == 0).then_some(buf.f_type as i64)
+}
+
+/// Parses the tab-separated output of `zfs list -H -o name,mountpoint`. Datasets that aren't
+/// mounted at an absolute path are skipped.
+fn parse_datasets(output: &str) -> Vec<(String, PathBuf)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, mountpoint) = line.split_once('\t')?;
+            mountpoint
+                .starts_with('/')
+                .then(|| (name.to_owned(), PathBuf::from(mountpoint)))
+        })
+        .collect()
+}
+
+/// Finds the dataset with the deepest mountpoint that `dir` resides in.
+fn containing_dataset<'a>(
+    datasets: &'a [(String, PathBuf)],
+    dir: &Path,
+) -> Option<(&'a str, &'a Path)> {
+    datasets
+        .iter()
+        .filter(|(_, mountpoint)| dir.starts_with(mountpoint))
+        .max_by_key(|(_, mountpoint)| mountpoint.components().count())
+        .map(|(name, mountpoint)| (name.as_str(), mountpoint.as_path()))
+}
+
+#[test]
+fn find_containing_dataset() {
+    let output = "tank\t/tank\ntank/home\t/home\ntank/home/alice\t/home/alice\ntank/vm\tnone\n";
+    let datasets = parse_datasets(output);
+
+    assert_eq!(datasets.len(), 3);
+
+    assert_eq!(
+        containing_dataset(&datasets, Path::new("/home/alice/src")),
+        Some(("tank/home/alice", Path::new("/home/alice")))
+    );
+    assert_eq!(
+        containing_dataset(&datasets, Path::new("/home/bob")),
+        Some(("tank/home", Path::new("/home")))
+    );
+    assert_eq!(containing_dataset(&datasets, Path::new("/usr")), None);
+}
+
+#[test]
+fn run_reports_failure() {
+    assert_eq!(run(Command::new("echo").arg("tank")).unwrap(), "tank\n");
+
+    assert!(matches!(
+        run(Command::new("sh").args(["-c", "echo 'no datasets' >&2; exit 1"])),
+        Err(Error::Command(program, stderr)) if program == "sh" && stderr == "no datasets"
+    ));
+}
@@ -1,35 +1,90 @@
+use crate::intern::Symbol;
 use errno::{errno, set_errno, Errno};
-use std::{ffi::CStr, fs::Metadata, os::unix::fs::MetadataExt};
+use std::{
+    collections::HashMap,
+    ffi::CStr,
+    fs::Metadata,
+    os::unix::fs::MetadataExt,
+    sync::{Mutex, OnceLock},
+};
 
-type Owner = String;
-type Group = String;
+type Owner = Symbol;
+type Group = Symbol;
+
+/// Outcomes of looking up users so far keyed by uid.
+static USERS: OnceLock<Mutex<Cache>> = OnceLock::new();
+
+/// Outcomes of looking up groups so far keyed by gid.
+static GROUPS: OnceLock<Mutex<Cache>> = OnceLock::new();
+
+/// Names keyed by id along with failed lookups so that ids without a name aren't looked up anew
+/// for every entry they own.
+type Cache = HashMap<u32, Result<Symbol, Error>>;
 
 impl UserGroupInfo for Metadata {}
 
 /// Trait that allows for files to query their owner and group.
 pub trait UserGroupInfo: MetadataExt {
     /// Attemps to query the owner of the implementor.
-    fn try_get_owner(&self) -> Result<String, Error> {
-        unsafe {
-            let uid = self.uid();
-            try_get_user(uid)
-        }
+    fn try_get_owner(&self) -> Result<Owner, Error> {
+        cached(&USERS, self.uid(), try_get_user)
     }
 
     /// Attempts to query both the owner and group of the implementor.
     fn try_get_owner_and_group(&self) -> Result<(Owner, Group), Error> {
-        unsafe {
-            let uid = self.uid();
-            let gid = self.gid();
-            let user = try_get_user(uid)?;
-            let group = try_get_group(gid)?;
-
-            Ok((user, group))
-        }
+        let user = cached(&USERS, self.uid(), try_get_user)?;
+        let group = cached(&GROUPS, self.gid(), try_get_group)?;
+
+        Ok((user, group))
+    }
+
+    /// The uid and gid of the implementor as they'd be shown by `ls -n`.
+    fn owner_and_group_ids(&self) -> (Owner, Group) {
+        let user = Symbol::intern(&self.uid().to_string());
+        let group = Symbol::intern(&self.gid().to_string());
+
+        (user, group)
+    }
+}
+
+/// Name of the user with `uid`, if there is one.
+#[cfg(target_os = "linux")]
+pub fn user_name(uid: u32) -> Option<String> {
+    cached(&USERS, uid, try_get_user)
+        .ok()
+        .map(|symbol| symbol.as_str().to_owned())
+}
+
+/// Name of the group with `gid`, if there is one.
+#[cfg(target_os = "linux")]
+pub fn group_name(gid: u32) -> Option<String> {
+    cached(&GROUPS, gid, try_get_group)
+        .ok()
+        .map(|symbol| symbol.as_str().to_owned())
+}
+
+/// Looks up the name associated with `id` using `lookup` unless it, or the failure to find it, is
+/// already in `cache`. Holding the lock during the lookup also serializes calls to `getpwuid` and
+/// `getgrgid` which aren't thread-safe.
+fn cached(
+    cache: &OnceLock<Mutex<Cache>>,
+    id: u32,
+    lookup: unsafe fn(u32) -> Result<String, Error>,
+) -> Result<Symbol, Error> {
+    let mut cache = cache.get_or_init(Mutex::default).lock().unwrap();
+
+    if let Some(outcome) = cache.get(&id) {
+        return *outcome;
     }
+
+    let outcome = unsafe { lookup(id) }.map(|name| Symbol::intern(&name));
+    cache.insert(id, outcome);
+    drop(cache);
+
+    outcome
 }
 
-#[derive(Debug, thiserror::Error)]
+#[derive(Clone, Copy, Debug, thiserror::Error)]
 pub enum Error {
     #[error("libc error")]
     LibC(Errno),
@@ -82,3 +137,20 @@ unsafe fn try_get_user(uid: libc::uid_t) -> Result<String, Error> {
 
     Ok(CStr::from_ptr(pw_name).to_string_lossy().to_string())
 }
+
+#[test]
+fn caches_failed_lookups() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static LOOKUPS: AtomicUsize = AtomicUsize::new(0);
+    static CACHE: OnceLock<Mutex<Cache>> = OnceLock::new();
+
+    unsafe fn missing(_id: u32) -> Result<String, Error> {
+        LOOKUPS.fetch_add(1, Ordering::Relaxed);
+        Err(Error::InvalidUser)
+    }
+
+    assert!(cached(&CACHE, 4242, missing).is_err());
+    assert!(cached(&CACHE, 4242, missing).is_err());
+    assert_eq!(LOOKUPS.load(Ordering::Relaxed), 1);
+}
@@ -1,5 +1,16 @@
 use ignore::DirEntry;
-use std::{os::unix::ffi::OsStrExt, path::Path, ptr};
+use std::{
+    ffi::{CString, OsStr, OsString},
+    os::unix::ffi::{OsStrExt, OsStringExt},
+    path::Path,
+    ptr,
+};
+
+/// A single extended attribute of a file.
+pub struct Attr {
+    pub name: OsString,
+    pub value: Vec<u8>,
+}
 
 /// Allow extended attributes to be queried directly from the directory entry.
 impl ExtendedAttr for DirEntry {
@@ -17,6 +28,25 @@ pub trait ExtendedAttr {
     fn has_xattrs(&self) -> bool {
         unsafe { has_xattrs(self.path()) }
     }
+
+    /// Queries the filesystem to check if the extended attribute `name` exists for the
+    /// implementor's path.
+    fn has_xattr(&self, name: &OsStr) -> bool {
+        unsafe { has_xattr(self.path(), name) }
+    }
+
+    /// Queries the filesystem for the value of the extended attribute `name` of the implementor's
+    /// path.
+    #[cfg(target_os = "linux")]
+    fn xattr(&self, name: &OsStr) -> Option<Vec<u8>> {
+        unsafe { xattr(self.path(), name) }
+    }
+
+    /// Queries the filesystem for the names and values of all extended attributes of the
+    /// implementor's path.
+    fn xattrs(&self) -> Vec<Attr> {
+        unsafe { xattrs(self.path()) }
+    }
 }
 
 /// Checks to see if a directory entry referred to by `path` has extended attributes. If the file
@@ -36,3 +66,93 @@ unsafe fn has_xattrs(path: &Path) -> bool {
     #[cfg(target_os = "macos")]
     return 0 < listxattr(path_ptr, ptr::null_mut::<c_char>(), 0, 0);
 }
+
+/// Checks to see if a directory entry referred to by `path` has the extended attribute `name`. If
+/// the file at the provided `path` is symlink the file it points to is interrogated.
+unsafe fn has_xattr(path: &Path, name: &OsStr) -> bool {
+    use libc::{c_void, getxattr};
+
+    let (Ok(path), Ok(name)) = (
+        CString::new(path.as_os_str().as_bytes()),
+        CString::new(name.as_bytes()),
+    ) else {
+        return false;
+    };
+
+    #[cfg(not(target_os = "macos"))]
+    return 0 <= getxattr(path.as_ptr(), name.as_ptr(), ptr::null_mut::<c_void>(), 0);
+
+    #[cfg(target_os = "macos")]
+    return 0
+        <= getxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            ptr::null_mut::<c_void>(),
+            0,
+            0,
+            0,
+        );
+}
+
+/// Lists the extended attributes of the file at `path` along with their values. Attributes whose
+/// values can't be read, such as those that are removed in the meantime, are skipped. If the file
+/// at the provided `path` is symlink the file it points to is interrogated.
+unsafe fn xattrs(path: &Path) -> Vec<Attr> {
+    use libc::{c_char, listxattr};
+
+    let Ok(c_path) = CString::new(path.as_os_str().as_bytes()) else {
+        return vec![];
+    };
+
+    #[cfg(not(target_os = "macos"))]
+    let list = |buf: *mut c_char, size| listxattr(c_path.as_ptr(), buf, size);
+
+    #[cfg(target_os = "macos")]
+    let list = |buf: *mut c_char, size| listxattr(c_path.as_ptr(), buf, size, 0);
+
+    let Ok(len) = usize::try_from(list(ptr::null_mut(), 0)) else {
+        return vec![];
+    };
+
+    let mut names = vec![0_u8; len];
+
+    let Ok(len) = usize::try_from(list(names.as_mut_ptr().cast(), names.len())) else {
+        return vec![];
+    };
+
+    names.truncate(len);
+
+    names
+        .split(|byte| *byte == 0)
+        .filter(|name| !name.is_empty())
+        .filter_map(|name| {
+            let name = OsString::from_vec(name.to_vec());
+            let value = xattr(path, &name)?;
+
+            Some(Attr { name, value })
+        })
+        .collect()
+}
+
+/// Reads the value of the extended attribute `name` of the file at `path`. If the file at the
+/// provided `path` is symlink the file it points to is interrogated.
+unsafe fn xattr(path: &Path, name: &OsStr) -> Option<Vec<u8>> {
+    use libc::{c_void, getxattr};
+
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let name = CString::new(name.as_bytes()).ok()?;
+
+    #[cfg(not(target_os = "macos"))]
+    let get = |buf: *mut c_void, size| getxattr(path.as_ptr(), name.as_ptr(), buf, size);
+
+    #[cfg(target_os = "macos")]
+    let get = |buf: *mut c_void, size| getxattr(path.as_ptr(), name.as_ptr(), buf, size, 0, 0);
+
+    let len = usize::try_from(get(ptr::null_mut(), 0)).ok()?;
+    let mut value = vec![0_u8; len];
+
+    let len = usize::try_from(get(value.as_mut_ptr().cast(), value.len())).ok()?;
+    value.truncate(len);
+
+    Some(value)
+}
@@ -0,0 +1,87 @@
+use std::{
+    env,
+    fmt::{self, Display},
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+/// Base directories as determined by `$XDG_CACHE_HOME`, `$XDG_CONFIG_HOME`, and friends or their
+/// platform specific equivalents such as the known folders on Windows.
+static BASE_DIRS: OnceLock<Vec<(Category, PathBuf)>> = OnceLock::new();
+
+/// The kind of data that lives in a given base directory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Category {
+    /// Non-essential data that can be safely reclaimed.
+    Cache,
+
+    /// User-specific configuration.
+    Config,
+
+    /// User-specific data files.
+    Data,
+
+    /// Data that should persist between restarts but isn't important enough for [`Self::Data`].
+    State,
+
+    /// Sockets, named pipes, and the like for the current session.
+    Runtime,
+
+    /// Files downloaded by the user which tend to accumulate.
+    Downloads,
+
+    /// Scratch space for temporary files such as `AppData\Local\Temp` on Windows.
+    Temp,
+}
+
+impl Category {
+    /// Returns the [Category] of `path` if it's one of the base directories or beneath one. If base
+    /// directories are nested then that of the innermost one applies.
+    pub fn of(path: &Path) -> Option<Self> {
+        BASE_DIRS
+            .get_or_init(init_base_dirs)
+            .iter()
+            .filter(|(_, dir)| path.starts_with(dir))
+            .max_by_key(|(_, dir)| dir.components().count())
+            .map(|(category, _)| *category)
+    }
+}
+
+impl Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::Cache => "cache",
+            Self::Config => "config",
+            Self::Data => "data",
+            Self::State => "state",
+            Self::Runtime => "runtime",
+            Self::Downloads => "downloads",
+            Self::Temp => "temp",
+        };
+
+        write!(f, "{label}")
+    }
+}
+
+/// Resolves every base directory that's available on the current platform. Paths are canonicalized
+/// so they can be compared against those of [`Node`]s.
+///
+/// [`Node`]: crate::tree::node::Node
+fn init_base_dirs() -> Vec<(Category, PathBuf)> {
+    [
+        (Category::Cache, dirs::cache_dir()),
+        (Category::Config, dirs::config_dir()),
+        (Category::Data, dirs::data_dir()),
+        (Category::State, dirs::state_dir()),
+        (Category::Runtime, dirs::runtime_dir()),
+        (Category::Downloads, dirs::download_dir()),
+        (Category::Temp, Some(env::temp_dir())),
+    ]
+    .into_iter()
+    .filter_map(|(category, dir)| {
+        let dir = dir?;
+        Some((category, fs::canonicalize(&dir).unwrap_or(dir)))
+    })
+    .collect()
+}
@@ -0,0 +1,98 @@
+use crate::disk_usage::{file_size::byte, units::PrefixKind};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::OnceLock,
+};
+
+/// Mounted ZFS datasets keyed by their mountpoint.
+static DATASETS: OnceLock<HashMap<PathBuf, Dataset>> = OnceLock::new();
+
+/// Properties of a ZFS dataset needed to reconcile tree totals with dataset accounting.
+#[derive(Debug, PartialEq)]
+pub struct Dataset {
+    compress_ratio: f64,
+    used: u64,
+    referenced: u64,
+}
+
+impl Dataset {
+    /// Returns the [Dataset] mounted at `path`, if any. Datasets are listed via `zfs list` the
+    /// first time this is called; if that fails then no dataset will ever be found.
+    pub fn at(path: &Path) -> Option<&'static Self> {
+        DATASETS.get_or_init(list).get(path)
+    }
+
+    /// Formats the properties of the [Dataset] for display, reporting sizes using `prefix_kind`.
+    pub fn annotation(&self, prefix_kind: PrefixKind) -> String {
+        let fmt_bytes = |value| {
+            let mut metric = byte::Metric::init_empty_logical(true, prefix_kind);
+            metric.value = value;
+            metric.to_string()
+        };
+
+        format!(
+            "[zfs {:.2}x, used {}, refer {}]",
+            self.compress_ratio,
+            fmt_bytes(self.used),
+            fmt_bytes(self.referenced)
+        )
+    }
+}
+
+/// Lists every mounted dataset.
+fn list() -> HashMap<PathBuf, Dataset> {
+    Command::new("zfs")
+        .args([
+            "list",
+            "-H",
+            "-p",
+            "-o",
+            "mountpoint,compressratio,used,referenced",
+        ])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| parse(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+/// Parses the tab-separated output of `zfs list -H -p`. Datasets that aren't mounted at an
+/// absolute path, e.g. those with a mountpoint of `none` or `legacy`, are skipped.
+fn parse(output: &str) -> HashMap<PathBuf, Dataset> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+
+            let mountpoint = fields.next().filter(|m| m.starts_with('/'))?;
+            let compress_ratio = fields.next()?.trim_end_matches('x').parse().ok()?;
+            let used = fields.next()?.parse().ok()?;
+            let referenced = fields.next()?.parse().ok()?;
+
+            let dataset = Dataset {
+                compress_ratio,
+                used,
+                referenced,
+            };
+
+            Some((PathBuf::from(mountpoint), dataset))
+        })
+        .collect()
+}
+
+#[test]
+fn parse_zfs_list() {
+    let output = "/tank\t1.52\t1073741824\t524288\n-\t1.00\t4096\t4096\nlegacy\t1.00\t0\t0\n";
+    let datasets = parse(output);
+
+    assert_eq!(datasets.len(), 1);
+
+    let tank = datasets.get(Path::new("/tank")).unwrap();
+
+    assert_eq!(
+        tank.annotation(PrefixKind::Bin),
+        "[zfs 1.52x, used 1.0 GiB, refer 512.0 KiB]"
+    );
+}
@@ -0,0 +1,63 @@
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+
+/// Global arena of interned strings which live for the remainder of the program.
+static INTERNER: OnceLock<RwLock<Interner>> = OnceLock::new();
+
+/// Maps each distinct string to its [Symbol] and back.
+#[derive(Default)]
+struct Interner {
+    symbols: HashMap<&'static str, Symbol>,
+    strings: Vec<&'static str>,
+}
+
+/// Index of a string in the global arena. Strings that repeat across many entries, such as the
+/// names of owners and groups, are stored once no matter how many [`Node`]s refer to them.
+///
+/// [`Node`]: crate::tree::node::Node
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Returns the [Symbol] of `string`, adding it to the arena if it hasn't been seen before.
+    pub fn intern(string: &str) -> Self {
+        let interner = INTERNER.get_or_init(RwLock::default);
+
+        if let Some(symbol) = interner.read().unwrap().symbols.get(string) {
+            return *symbol;
+        }
+
+        let mut interner = interner.write().unwrap();
+
+        // Another thread may have interned the same string while the lock was released.
+        if let Some(symbol) = interner.symbols.get(string) {
+            return *symbol;
+        }
+
+        let string: &'static str = Box::leak(Box::from(string));
+        let symbol = Self(u32::try_from(interner.strings.len()).unwrap());
+
+        interner.strings.push(string);
+        interner.symbols.insert(string, symbol);
+
+        symbol
+    }
+
+    /// Returns the string that the [Symbol] refers to.
+    pub fn as_str(self) -> &'static str {
+        INTERNER.get().unwrap().read().unwrap().strings[self.0 as usize]
+    }
+}
+
+#[test]
+fn intern() {
+    let root = Symbol::intern("root");
+    let wheel = Symbol::intern("wheel");
+
+    assert_ne!(root, wheel);
+    assert_eq!(Symbol::intern("root"), root);
+    assert_eq!(root.as_str(), "root");
+    assert_eq!(wheel.as_str(), "wheel");
+}
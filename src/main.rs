@@ -13,15 +13,17 @@
 #![allow(clippy::cast_precision_loss, clippy::struct_excessive_bools)]
 
 use clap::CommandFactory;
-use context::{layout, Context};
+use context::{layout, output, Context};
 use progress::{Indicator, IndicatorHandle, Message};
-use render::{Engine, Flat, FlatInverted, Inverted, Regular};
+use render::{Engine, Flat, FlatInverted, Inverted, Regular, TopFiles};
 use std::{
+    env,
     error::Error,
     io::{stdout, Write},
+    path::Path,
     process::ExitCode,
 };
-use tree::Tree;
+use tree::{cancel::Token, Tree};
 
 /// Operations to wrangle ANSI escaped strings.
 mod ansi;
@@ -38,6 +40,10 @@ mod fs;
 /// All things related to icons on how to map certain files to the appropriate icons.
 mod icons;
 
+/// Storing strings that repeat across many entries only once.
+#[cfg(unix)]
+mod intern;
+
 /// Concerned with displaying a progress indicator when stdout is a tty.
 mod progress;
 
@@ -57,6 +63,24 @@ mod tty;
 /// Common utilities across all modules.
 mod utils;
 
+/// Build and capability information reported by `--version`.
+mod version;
+
+/// Scans of large trees allocate heavily for paths and styled strings; mimalloc handles this
+/// better than most system allocators. Takes precedence over jemalloc if both are enabled.
+#[cfg(feature = "mimalloc")]
+#[global_allocator]
+static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
+
+/// Same as above but backed by jemalloc which isn't available for MSVC targets.
+#[cfg(all(
+    feature = "jemalloc",
+    not(feature = "mimalloc"),
+    not(target_env = "msvc")
+))]
+#[global_allocator]
+static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
+
 fn main() -> ExitCode {
     if let Err(e) = run() {
         eprintln!("{e}");
@@ -67,6 +91,15 @@ fn main() -> ExitCode {
 }
 
 fn run() -> Result<(), Box<dyn Error>> {
+    if let Some(json) = version::requested(env::args_os()) {
+        if json {
+            writeln!(stdout(), "{}", version::InfoJson)?;
+        } else {
+            writeln!(stdout(), "{}", version::Info)?;
+        }
+        return Ok(());
+    }
+
     let ctx = Context::try_init()?;
 
     if let Some(shell) = ctx.completions {
@@ -74,34 +107,85 @@ fn run() -> Result<(), Box<dyn Error>> {
         return Ok(());
     }
 
-    styles::init(ctx.no_color());
+    styles::init(ctx.no_color(), ctx.verbose);
 
-    let indicator = Indicator::maybe_init(&ctx);
+    // Destroyed once dropped at the end of the run.
+    #[cfg(target_os = "linux")]
+    let snapshot = ctx
+        .consistent
+        .then(|| fs::snapshot::Snapshot::create(&ctx.dir_canonical()))
+        .transpose()?;
 
-    let (tree, ctx) = {
-        match Tree::try_init(ctx, indicator.as_ref()) {
-            Ok(res) => res,
-            Err(err) => {
-                IndicatorHandle::terminate(indicator);
-                return Err(Box::new(err));
-            },
-        }
+    #[cfg(target_os = "linux")]
+    let ctx = match snapshot {
+        Some(ref snapshot) => {
+            let root_name = ctx.dir_canonical().file_name().map(ToOwned::to_owned);
+            ctx.with_dir(snapshot.path().to_path_buf(), root_name)
+        },
+        None => ctx,
+    };
+
+    let cancel = Token::default();
+
+    let indicator = Indicator::maybe_init(&ctx, &cancel);
+
+    let (tree, ctx) = match Tree::build_with_cancel(ctx, indicator.as_ref(), &cancel) {
+        // Bail on interrupts rather than render a partial tree, unwinding so that any snapshot
+        // taken for '--consistent' is destroyed.
+        Ok(_) if cancel.is_cancelled() => {
+            IndicatorHandle::terminate(indicator);
+            return Err(Box::new(tree::error::Error::Terminated));
+        },
+        Ok(res) => res,
+        Err(err) => {
+            IndicatorHandle::terminate(indicator);
+            return Err(Box::new(err));
+        },
     };
 
+    let oversized = ctx
+        .warn_size
+        .filter(|_| ctx.fail_on_warn)
+        .map(|threshold| tree.count_oversized(threshold))
+        .filter(|&count| count > 0);
+
+    // Records were already written while the tree was assembled, and anything else would make
+    // JSONL output unparseable.
+    let jsonl = ctx.output == output::Format::Jsonl;
+
+    let footers = footers(&tree, &ctx, jsonl);
+
+    let filtered = tree.filtered().map(ToString::to_string);
+
+    let output_file = ctx.output_file.clone();
+
     macro_rules! compute_output {
         ($t:ty) => {{
             let render = Engine::<$t>::new(tree, ctx);
-            format!("{render}")
+            let mut output = format!("{render}");
+
+            // The last row ends with a newline of its own if nothing follows it, such as when
+            // '--parseable' leaves out the tally.
+            output.truncate(output.trim_end_matches('\n').len());
+            output
         }};
     }
 
     let output = match ctx.layout {
-        layout::Type::Flat => compute_output!(Flat),
-        layout::Type::Iflat => compute_output!(FlatInverted),
-        layout::Type::Inverted => compute_output!(Inverted),
-        layout::Type::Regular => compute_output!(Regular),
+        _ if jsonl => None,
+        _ if ctx.top_files.is_some() => Some(compute_output!(TopFiles)),
+        layout::Type::Flat => Some(compute_output!(Flat)),
+        layout::Type::Iflat => Some(compute_output!(FlatInverted)),
+        layout::Type::Inverted => Some(compute_output!(Inverted)),
+        layout::Type::Regular => Some(compute_output!(Regular)),
     };
 
+    let output = output.map(|output| {
+        footers
+            .iter()
+            .fold(output, |output, footer| format!("{output}\n{footer}"))
+    });
+
     if let Some(mut progress) = indicator {
         progress
             .mailbox()
@@ -115,6 +199,68 @@ fn run() -> Result<(), Box<dyn Error>> {
             .transpose()?;
     }
 
+    if let Some(output) = output {
+        write_output(&output, output_file.as_deref())?;
+    }
+
+    if let Some(filtered) = filtered {
+        eprint!("{filtered}");
+    }
+
+    if let Some(count) = oversized {
+        let entries = if count == 1 { "entry" } else { "entries" };
+        return Err(Box::new(tree::error::Error::SizeExceeded(count, entries)));
+    }
+
+    Ok(())
+}
+
+/// Summaries that follow the output in the order that they're gathered. None of them are printed
+/// for JSONL output.
+fn footers(tree: &Tree, ctx: &Context, jsonl: bool) -> Vec<String> {
+    let mut footers = vec![];
+
+    if jsonl {
+        return footers;
+    }
+
+    if ctx.report {
+        footers.push(tree::report::Report::new(tree, ctx).to_string());
+    }
+
+    if ctx.xdg && ctx.measures_sizes() {
+        let summary = tree::xdg::Summary::new(tree, ctx);
+
+        if !summary.is_empty() {
+            footers.push(summary.to_string());
+        }
+    }
+
+    #[cfg(feature = "nix")]
+    if ctx.nix && ctx.measures_sizes() {
+        let summary = tree::nix::Summary::new(tree, ctx);
+
+        if !summary.is_empty() {
+            footers.push(summary.to_string());
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    if ctx.quota {
+        if let Some(quota) = fs::quota::Quota::query(&ctx.dir_canonical(), ctx.prefix_kind()) {
+            footers.push(quota.to_string());
+        }
+    }
+
+    footers
+}
+
+/// Writes `output` to `output_file` if provided, otherwise to stdout.
+fn write_output(output: &str, output_file: Option<&Path>) -> Result<(), fs::atomic::Error> {
+    if let Some(path) = output_file {
+        return fs::atomic::write(path, &format!("{output}\n"));
+    }
+
     #[cfg(debug_assertions)]
     {
         if std::env::var_os("ERDTREE_DEBUG").is_none() {
@@ -1,4 +1,8 @@
-use crate::{context::Context, tty};
+use crate::{
+    context::{output, Context},
+    tree::cancel::Token,
+    tty,
+};
 use crossterm::{
     cursor,
     terminal::{self, ClearType},
@@ -8,6 +12,7 @@ use std::{
     io::{self, Write},
     sync::mpsc::{self, SendError, SyncSender},
     thread::{self, JoinHandle},
+    time::{Duration, Instant},
 };
 
 /// Responsible for displying the progress indicator. This struct will be owned by a separate
@@ -15,6 +20,8 @@ use std::{
 /// is how the outside world will interact with it.
 pub struct Indicator<'a> {
     count: u64,
+    total: Option<u64>,
+    started: Instant,
     stdout: io::StdoutLock<'a>,
     state: IndicatorState,
 }
@@ -33,6 +40,10 @@ pub enum Message {
     /// Message that indicates that we are currently reading from disk and that a file was indexed.
     Index,
 
+    /// Message that carries the total amount of files expected to be indexed as counted ahead of
+    /// time by '--eta'.
+    Total(u64),
+
     /// Message that indicates that we are done reading from disk and are preparing the output.
     DoneIndexing,
 
@@ -74,6 +85,8 @@ impl Default for Indicator<'_> {
     fn default() -> Self {
         Self {
             count: u64::default(),
+            total: None,
+            started: Instant::now(),
             stdout: io::stdout().lock(),
             state: IndicatorState::default(),
         }
@@ -126,23 +139,40 @@ impl IndicatorHandle {
 impl<'a> Indicator<'a> {
     /// Initializes an [`Indicator`] returning an atomic reference counter of an [`IndicatorHandle`] if
     /// a progress indicator is enabled via [`Context`]. Upon initialization an interrupt handler is
-    /// also registered. Sources of panic can come from [`IndicatorHandle::terminate`] or
+    /// also registered which cancels any traversal holding `cancel`. The handler is registered for
+    /// '--consistent' even without an indicator so that an interrupt unwinds far enough for the
+    /// snapshot to be destroyed. Sources of panic can come from [`IndicatorHandle::terminate`] or
     /// [`ctrlc::set_handler`].
-    pub fn maybe_init(ctx: &Context) -> Option<IndicatorHandle> {
-        (ctx.stdout_is_tty && !ctx.no_progress)
-            .then(Indicator::measure)
-            .map(|indicator| {
-                let mailbox = indicator.mailbox();
+    pub fn maybe_init(ctx: &Context, cancel: &Token) -> Option<IndicatorHandle> {
+        // The indicator holds on to stdout which is where records are streamed to.
+        let streaming = ctx.output == output::Format::Jsonl && ctx.output_file.is_none();
+
+        let indicator =
+            (ctx.stdout_is_tty && !ctx.no_progress && !streaming).then(Indicator::measure);
+
+        #[cfg(target_os = "linux")]
+        let interruptible = indicator.is_some() || ctx.consistent;
+
+        #[cfg(not(target_os = "linux"))]
+        let interruptible = indicator.is_some();
+
+        if interruptible {
+            let mailbox = indicator.as_ref().map(IndicatorHandle::mailbox);
+            let cancel = Token::clone(cancel);
+
+            let int_handler = move || {
+                cancel.cancel();
 
-                let int_handler = move || {
+                if let Some(ref mailbox) = mailbox {
                     let _ = mailbox.try_send(Message::Finish);
                     tty::restore();
-                };
+                }
+            };
 
-                ctrlc::set_handler(int_handler).expect("Failed to set interrupt handler");
+            ctrlc::set_handler(int_handler).expect("Failed to set interrupt handler");
+        }
 
-                indicator
-            })
+        indicator
     }
 
     /// Initializes a worker thread that owns [`Indicator`] that awaits on [`Message`]s to traverse
@@ -160,6 +190,7 @@ impl<'a> Indicator<'a> {
             while let Ok(msg) = rx.recv() {
                 match msg {
                     Message::Index => indicator.index()?,
+                    Message::Total(total) => indicator.total = Some(total),
                     Message::DoneIndexing => {
                         indicator.update_state(IndicatorState::Rendering)?;
                     },
@@ -210,7 +241,25 @@ impl<'a> Indicator<'a> {
     #[inline]
     fn index(&mut self) -> Result<(), Error> {
         self.count += 1;
-        write!(self.stdout, "Indexing {} files...", self.count)?;
+
+        let Some(total) = self.total.filter(|total| *total >= self.count) else {
+            write!(self.stdout, "Indexing {} files...", self.count)?;
+            return Ok(());
+        };
+
+        let remaining = estimate_remaining(self.count, total, self.started.elapsed());
+
+        write!(
+            self.stdout,
+            "Indexing {} of {total} files, about {} remaining...",
+            self.count,
+            fmt_duration(remaining)
+        )?;
+
+        // The estimate can shrink in width between updates.
+        self.stdout
+            .execute(terminal::Clear(ClearType::UntilNewLine))?;
+
         Ok(())
     }
 
@@ -220,3 +269,33 @@ impl<'a> Indicator<'a> {
         write!(self.stdout, "Preparing output...").unwrap();
     }
 }
+
+/// Extrapolates how much longer indexing will take to reach `total` given that `count` files were
+/// indexed over `elapsed`.
+fn estimate_remaining(count: u64, total: u64, elapsed: Duration) -> Duration {
+    if count == 0 {
+        return Duration::ZERO;
+    }
+
+    elapsed.mul_f64((total - count) as f64 / count as f64)
+}
+
+/// Formats `duration` to the nearest second, e.g. `1m 05s`.
+fn fmt_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+
+    if secs < 60 {
+        format!("{secs}s")
+    } else {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    }
+}
+
+#[test]
+fn eta() {
+    let remaining = estimate_remaining(250, 1000, Duration::from_secs(10));
+    assert_eq!(remaining, Duration::from_secs(30));
+
+    assert_eq!(fmt_duration(remaining), "30s");
+    assert_eq!(fmt_duration(Duration::from_secs(65)), "1m 05s");
+}
@@ -0,0 +1,43 @@
+/// Number of columns that a full bar spans.
+pub const WIDTH: usize = 10;
+
+/// Partially filled cells in increments of an eighth, from one eighth up to seven eighths.
+const PARTIALS: [char; 7] = [
+    '\u{258F}', '\u{258E}', '\u{258D}', '\u{258C}', '\u{258B}', '\u{258A}', '\u{2589}',
+];
+
+/// Full block used for every completely filled cell.
+const FULL: char = '\u{2588}';
+
+/// Renders a bar of [`WIDTH`] columns filled in proportion to `fraction` which is clamped between
+/// zero and one. The unfilled remainder of the bar is padded with spaces.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn render(fraction: f64) -> String {
+    let eighths = (fraction.clamp(0.0, 1.0) * (WIDTH * 8) as f64).round() as usize;
+    let (full, partial) = (eighths / 8, eighths % 8);
+
+    let mut bar = String::with_capacity(WIDTH * FULL.len_utf8());
+
+    bar.extend(std::iter::repeat(FULL).take(full));
+
+    if partial > 0 {
+        bar.push(PARTIALS[partial - 1]);
+    }
+
+    let filled = full + usize::from(partial > 0);
+    bar.extend(std::iter::repeat(' ').take(WIDTH - filled));
+
+    bar
+}
+
+#[test]
+fn render_bars() {
+    assert_eq!(render(0.0), " ".repeat(WIDTH));
+    assert_eq!(render(1.0), "\u{2588}".repeat(WIDTH));
+    assert_eq!(
+        render(0.5),
+        format!("{}{}", "\u{2588}".repeat(5), " ".repeat(5))
+    );
+    assert_eq!(render(0.05), format!("\u{258C}{}", " ".repeat(9)));
+    assert_eq!(render(2.0), render(1.0));
+}
@@ -1,21 +1,24 @@
 use crate::{
-    context::Context,
+    context::{align::Alignment, size, Context},
     disk_usage::{
         file_size::{byte, DiskUsage, FileSize},
         units::{BinPrefix, PrefixKind, SiPrefix},
     },
-    render::theme,
+    fs::xdg,
+    render::{bar, theme},
     styles,
     tree::node::Node,
 };
+use ansi_term::Style;
 use std::{
+    borrow::Cow,
     ffi::OsStr,
     fmt::{self, Display},
     path::Path,
 };
 
 #[cfg(unix)]
-use chrono::{DateTime, Local};
+use chrono::{DateTime, FixedOffset, Local};
 
 #[cfg(unix)]
 use crate::{
@@ -52,6 +55,10 @@ pub enum Kind<'a> {
     Owner,
     #[cfg(unix)]
     Group,
+    #[cfg(target_os = "linux")]
+    SecurityContext,
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    Flags,
 }
 
 impl<'a> Cell<'a> {
@@ -70,7 +77,61 @@ impl<'a> Cell<'a> {
         match self.kind {
             Kind::FileName { prefix } => {
                 let pre = prefix.unwrap_or_default();
-                let name = theme::stylize_file_name(node);
+                let mut name = Self::warning_style(node, ctx).map_or_else(
+                    || theme::stylize_file_name(node, ctx),
+                    |style| Cow::from(style.paint(node.file_name().to_string_lossy()).to_string()),
+                );
+
+                // Entries anchored to the root by '--min-depth' need their ancestry for context.
+                let anchored = ctx.min_depth() > 1 && node.depth() == ctx.min_depth();
+
+                if anchored || (ctx.leaf_paths && !node.is_dir()) {
+                    let parent = node
+                        .parent_path()
+                        .and_then(|p| p.strip_prefix(ctx.dir_canonical()).ok())
+                        .unwrap_or_else(|| Path::new(""));
+
+                    name = Cow::from(format!("{}{name}", parent.join("").display()));
+                }
+
+                // Files whose contents are only available from a cloud provider get a cloud.
+                if node.is_placeholder() {
+                    name = Cow::from(format!("{name} \u{2601}"));
+                }
+
+                if node.is_vanished() {
+                    name = Cow::from(format!("{name} [vanished]"));
+                }
+
+                if let Some(contents) = Self::contents(node, ctx) {
+                    name = Cow::from(format!("{name} {contents}"));
+                }
+
+                if let Some(share) = node.share() {
+                    name = Cow::from(format!("{name} ({:.1}%)", share * 100.0));
+                }
+
+                // Only the outermost entry of each category is annotated, its descendants being
+                // understood to share it.
+                if ctx.xdg {
+                    let category = xdg::Category::of(node.path());
+
+                    let enclosing = (node.depth() > 0)
+                        .then(|| node.parent_path().and_then(xdg::Category::of))
+                        .flatten();
+
+                    if let Some(category) = category.filter(|&c| enclosing != Some(c)) {
+                        name = Cow::from(format!("{name} [{category}]"));
+                    }
+                }
+
+                #[cfg(feature = "zfs")]
+                if ctx.zfs {
+                    if let Some(dataset) = crate::fs::zfs::Dataset::at(node.path()) {
+                        name =
+                            Cow::from(format!("{name} {}", dataset.annotation(ctx.prefix_kind())));
+                    }
+                }
 
                 if !ctx.icons {
                     return write!(f, "{pre}{name}");
@@ -101,10 +162,25 @@ impl<'a> Cell<'a> {
                 .display()
         };
 
-        let formatted_path = node.style().map_or_else(
-            || path.to_string(),
-            |style| format!("{}", style.paint(path.to_string())),
-        );
+        let mut formatted_path = Self::warning_style(node, ctx)
+            .copied()
+            .or_else(|| node.style())
+            .map_or_else(
+                || path.to_string(),
+                |style| format!("{}", style.paint(path.to_string())),
+            );
+
+        if node.is_vanished() {
+            formatted_path = format!("{formatted_path} [vanished]");
+        }
+
+        if let Some(contents) = Self::contents(node, ctx) {
+            formatted_path = format!("{formatted_path} {contents}");
+        }
+
+        if let Some(share) = node.share() {
+            formatted_path = format!("{formatted_path} ({:.1}%)", share * 100.0);
+        }
 
         if !ctx.icons {
             return write!(f, "{formatted_path}");
@@ -115,23 +191,89 @@ impl<'a> Cell<'a> {
         write!(f, "{icon} {formatted_path}")
     }
 
+    /// How many files and directories a directory contains when using '--count'.
+    fn contents(node: &Node, ctx: &Context) -> Option<String> {
+        if !ctx.count || !node.is_dir() {
+            return None;
+        }
+
+        let dirs = node.descendant_dirs();
+        let files = node.descendants() - dirs;
+
+        let plural = |count: usize, noun: &str| {
+            if count == 1 {
+                format!("{count} {noun}")
+            } else {
+                format!("{count} {noun}s")
+            }
+        };
+
+        Some(format!(
+            "({}, {})",
+            plural(files, "file"),
+            plural(dirs, "dir")
+        ))
+    }
+
     /// Rules on how to render the file size.
     #[inline]
     fn fmt_file_size(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let node = self.node;
         let ctx = self.ctx;
 
-        let Some(file_size) = node.file_size() else {
-            return Self::fmt_size_placeholder(f, ctx)
+        // Devices have no size to speak of so their numbers are shown in its place like 'ls -l'.
+        #[cfg(unix)]
+        if let Some(rdev) = node.rdev().filter(|_| ctx.long()) {
+            Self::fmt_device(f, rdev, ctx)?;
+            return Self::fmt_bar(f, 0, ctx);
+        }
+
+        let file_size = match ctx.size_policy().state(node.file_size()) {
+            size::State::Suppressed => return Ok(()),
+            size::State::Pending => {
+                Self::fmt_size_placeholder(f, ctx)?;
+                return Self::fmt_bar(f, 0, ctx);
+            },
+            size::State::Zero(file_size) | size::State::Measured(file_size) => file_size,
         };
 
         match file_size {
-            FileSize::Byte(metric) => Self::fmt_bytes(f, metric, ctx),
-            FileSize::Line(metric) => Self::fmt_unitless_disk_usage(f, metric, ctx),
-            FileSize::Word(metric) => Self::fmt_unitless_disk_usage(f, metric, ctx),
+            FileSize::Byte(metric) => Self::fmt_bytes(f, metric, ctx)?,
+            FileSize::Both(logical, physical) => {
+                Self::fmt_bytes(f, logical, ctx)?;
+                write!(f, " ")?;
+                Self::fmt_bytes(f, physical, ctx)?;
+            },
+            FileSize::Line(metric) => Self::fmt_unitless_disk_usage(f, metric, ctx)?,
+            FileSize::Word(metric) => Self::fmt_unitless_disk_usage(f, metric, ctx)?,
+            FileSize::Inodes(metric) => Self::fmt_unitless_disk_usage(f, metric, ctx)?,
 
             #[cfg(unix)]
-            FileSize::Block(metric) => Self::fmt_block_usage(f, metric, ctx),
+            FileSize::Block(metric) => Self::fmt_block_usage(f, metric, ctx)?,
+        }
+
+        Self::fmt_bar(f, file_size.value(), ctx)
+    }
+
+    /// Rules on how to render the usage bar that follows the file size if '--bar' is enabled.
+    #[inline]
+    fn fmt_bar(f: &mut fmt::Formatter<'_>, size: u64, ctx: &Context) -> fmt::Result {
+        if !ctx.bar {
+            return Ok(());
+        }
+
+        let fraction = if ctx.root_size == 0 {
+            0.0
+        } else {
+            size as f64 / ctx.root_size as f64
+        };
+
+        let out = bar::render(fraction);
+
+        if let Ok(style) = styles::get_placeholder_style() {
+            write!(f, " {}", style.paint(out))
+        } else {
+            write!(f, " {out}")
         }
     }
 
@@ -213,6 +355,29 @@ impl<'a> Cell<'a> {
         write!(f, "{group:>max_group_width$}")
     }
 
+    /// Rules on how to format the `SELinux` security context.
+    #[cfg(target_os = "linux")]
+    #[inline]
+    fn fmt_security_context(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let max_width = self.ctx.max_security_context_width;
+
+        let context = self.node.security_context().unwrap_or(styles::PLACEHOLDER);
+
+        write!(f, "{context:<max_width$}")
+    }
+
+    /// Rules on how to format file flags.
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    #[inline]
+    fn fmt_flags(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let max_width = self.ctx.max_flags_width;
+
+        match self.node.flags() {
+            Some(flags) => write!(f, "{flags:<max_width$}"),
+            None => write!(f, "{:<max_width$}", styles::PLACEHOLDER),
+        }
+    }
+
     /// Rules on how to format datetime for rendering.
     #[cfg(unix)]
     #[inline]
@@ -222,14 +387,17 @@ impl<'a> Cell<'a> {
 
         let datetime = match ctx.time() {
             time::Stamp::Create => node.created(),
+            time::Stamp::Change => node.changed(),
             time::Stamp::Access => node.accessed(),
             time::Stamp::Mod => node.modified(),
         };
 
-        let out = datetime.map(DateTime::<Local>::from).map_or_else(
-            || format!("{PLACEHOLDER:>12}"),
-            |dt| format!("{:>12}", self.fmt_timestamp(dt)),
-        );
+        let out = datetime
+            .map(|t| time::localize(t, ctx.timezone()))
+            .map_or_else(
+                || format!("{PLACEHOLDER:>12}"),
+                |dt| format!("{:>12}", self.fmt_timestamp(dt)),
+            );
 
         let formatted_datetime = if let Ok(style) = styles::get_datetime_style() {
             style.paint(out).to_string()
@@ -243,16 +411,25 @@ impl<'a> Cell<'a> {
     /// Rules on how to format timestamp
     #[cfg(unix)]
     #[inline]
-    fn fmt_timestamp(&self, dt: DateTime<Local>) -> String {
-        let time_format = self.ctx.time_format();
-        let delayed_format = match time_format {
-            time::Format::Default => dt.format("%d %h %H:%M %g"),
-            time::Format::Iso => dt.format("%Y-%m-%d %H:%M:%S"),
-            time::Format::IsoStrict => dt.format("%Y-%m-%dT%H:%M:%S%Z"),
-            time::Format::Short => dt.format("%Y-%m-%d"),
+    fn fmt_timestamp(&self, dt: DateTime<FixedOffset>) -> String {
+        let preset = match self.ctx.time_format() {
+            time::Spec::Custom(fmt) => return format!("{:>12}", dt.format(fmt)),
+            // Timestamps need to be a single field if output is meant to be parsed.
+            time::Spec::Preset(
+                time::Format::Default | time::Format::Iso | time::Format::Relative,
+            ) if self.ctx.parseable => time::Format::IsoStrict,
+            time::Spec::Preset(preset) => *preset,
+        };
+
+        let formatted = match preset {
+            time::Format::Default => dt.format("%d %h %H:%M %g").to_string(),
+            time::Format::Iso => dt.format("%Y-%m-%d %H:%M:%S").to_string(),
+            time::Format::IsoStrict => dt.format("%Y-%m-%dT%H:%M:%S%Z").to_string(),
+            time::Format::Short => dt.format("%Y-%m-%d").to_string(),
+            time::Format::Relative => time::relative(dt, Local::now().with_timezone(dt.offset())),
         };
 
-        format!("{delayed_format:>12}")
+        format!("{formatted:>12}")
     }
 
     /// Rules on how to format permissions for rendering
@@ -262,6 +439,12 @@ impl<'a> Cell<'a> {
         let node = self.node;
         let ctx = self.ctx;
 
+        // Entries that vanished mid-scan have no permissions to speak of.
+        if node.mode().is_err() {
+            let width = if ctx.octal { 4 } else { 11 };
+            return write!(f, "{PLACEHOLDER:>width$}");
+        }
+
         let formatted_perms = if ctx.octal {
             theme::style_oct_permissions(node)
         } else {
@@ -271,24 +454,33 @@ impl<'a> Cell<'a> {
         write!(f, "{formatted_perms}")
     }
 
+    /// Formatter for the major and minor numbers of a device in place of its size.
+    #[cfg(unix)]
+    #[inline]
+    fn fmt_device(f: &mut fmt::Formatter<'_>, rdev: u64, ctx: &Context) -> fmt::Result {
+        let dev = rdev as libc::dev_t;
+        let (major, minor) = (libc::major(dev), libc::minor(dev));
+
+        // Parseable output needs the pair to be a single field.
+        if ctx.parseable {
+            return write!(f, "{major},{minor}");
+        }
+
+        let out = format!("{major}, {minor}");
+
+        let width = Self::size_column_width(ctx);
+
+        write!(f, "{out:>width$}")
+    }
+
     /// Formatter for the placeholder for file sizes.
     #[inline]
     fn fmt_size_placeholder(f: &mut fmt::Formatter<'_>, ctx: &Context) -> fmt::Result {
-        if ctx.suppress_size || ctx.max_size_width == 0 {
+        if ctx.max_size_width == 0 {
             return write!(f, "");
         }
 
-        let mut padding = ctx.max_size_width + 1;
-
-        match ctx.disk_usage {
-            DiskUsage::Logical | DiskUsage::Physical => match ctx.unit {
-                PrefixKind::Si if ctx.human => padding += 2,
-                PrefixKind::Bin if ctx.human => padding += 3,
-                PrefixKind::Si => padding += 0,
-                PrefixKind::Bin => padding += 1,
-            },
-            _ => padding -= 1,
-        }
+        let padding = Self::size_column_width(ctx);
 
         let formatted_placeholder = format!("{:>padding$}", styles::PLACEHOLDER);
 
@@ -299,6 +491,80 @@ impl<'a> Cell<'a> {
         }
     }
 
+    /// Width of the size column including its unit.
+    #[inline]
+    const fn size_column_width(ctx: &Context) -> usize {
+        let mut padding = ctx.max_size_width + 1;
+
+        if ctx.byte_metric() {
+            match (ctx.fixed_unit(), ctx.prefix_kind()) {
+                (Some(unit), _) => padding += unit.as_str().len(),
+                (
+                    None,
+                    PrefixKind::Raw
+                    | PrefixKind::RawGrouped
+                    | PrefixKind::Sectors
+                    | PrefixKind::Blocks
+                    | PrefixKind::Pages,
+                ) => padding -= 1,
+                (None, PrefixKind::Si) if ctx.human => padding += 2,
+                (None, PrefixKind::Bin) if ctx.human => padding += 3,
+                (None, PrefixKind::Si) => padding += 0,
+                (None, PrefixKind::Bin) => padding += 1,
+            }
+        } else {
+            padding -= 1;
+        }
+
+        // The placeholder spans both the logical and physical size columns.
+        if matches!(ctx.disk_usage, DiskUsage::Both) {
+            padding = padding * 2 + 1;
+        }
+
+        padding
+    }
+
+    /// Style of entries whose size exceeds '--warn-size'.
+    #[inline]
+    fn warning_style(node: &Node, ctx: &Context) -> Option<&'static Style> {
+        let threshold = ctx.warn_size?;
+
+        if ctx.no_color() || !node.exceeds(threshold) {
+            return None;
+        }
+
+        styles::get_warning_style().ok()
+    }
+
+    /// Style of a size of `bytes` when '--gradient' is enabled.
+    #[inline]
+    fn gradient_style(bytes: u64, ctx: &Context) -> Option<&'static Style> {
+        if !ctx.gradient {
+            return None;
+        }
+
+        let [small, large, very_large] = styles::get_size_gradient().ok()?;
+        let thresholds = ctx.gradient_thresholds;
+
+        if bytes >= thresholds.red {
+            Some(very_large)
+        } else if bytes >= thresholds.yellow {
+            Some(large)
+        } else {
+            Some(small)
+        }
+    }
+
+    /// Pads `text` to `width` columns as determined by '--size-align'.
+    #[inline]
+    fn align(text: &str, width: usize, ctx: &Context) -> String {
+        match ctx.size_align {
+            Alignment::Right => format!("{text:>width$}"),
+            Alignment::Left => format!("{text:<width$}"),
+            Alignment::None => text.to_owned(),
+        }
+    }
+
     /// Rules to format disk usage as bytes
     #[inline]
     fn fmt_bytes(f: &mut fmt::Formatter<'_>, metric: &byte::Metric, ctx: &Context) -> fmt::Result {
@@ -306,30 +572,49 @@ impl<'a> Cell<'a> {
         let max_unit_width = ctx.max_size_unit_width;
         let out = format!("{metric}");
 
-        let [size, unit]: [&str; 2] = out.split(' ').collect::<Vec<&str>>().try_into().unwrap();
+        // Raw byte counts are reported without a unit.
+        let (size, unit) = out.split_once(' ').unwrap_or((&out, ""));
+
+        let out = if unit.is_empty() {
+            Self::align(size, max_size_width, ctx)
+        } else if ctx.size_align == Alignment::Left {
+            // Padded as a whole so that the unit stays beside its number.
+            Self::align(&out, max_size_width + 1 + max_unit_width, ctx)
+        } else {
+            let size = Self::align(size, max_size_width, ctx);
+            let unit = Self::align(unit, max_unit_width, ctx);
+            format!("{size} {unit}")
+        };
 
         if ctx.no_color() {
-            return write!(f, "{size:>max_size_width$} {unit:>max_unit_width$}");
+            return write!(f, "{out}");
+        }
+
+        if let Some(style) = Self::gradient_style(metric.value, ctx) {
+            return write!(f, "{}", style.paint(out));
         }
 
-        let color = if metric.human_readable {
+        let color = if metric.human_readable && !unit.is_empty() {
             styles::get_du_theme().unwrap().get(unit).unwrap()
         } else {
-            match ctx.unit {
+            match ctx.prefix_kind() {
                 PrefixKind::Si => {
                     let pre = SiPrefix::from(metric.value);
                     styles::get_du_theme().unwrap().get(pre.as_str()).unwrap()
                 },
-                PrefixKind::Bin => {
+                PrefixKind::Bin
+                | PrefixKind::Raw
+                | PrefixKind::RawGrouped
+                | PrefixKind::Sectors
+                | PrefixKind::Blocks
+                | PrefixKind::Pages => {
                     let pre = BinPrefix::from(metric.value);
                     styles::get_du_theme().unwrap().get(pre.as_str()).unwrap()
                 },
             }
         };
 
-        let out = color.paint(format!("{size:>max_size_width$} {unit:>max_unit_width$}"));
-
-        write!(f, "{out}")
+        write!(f, "{}", color.paint(out))
     }
 
     #[inline]
@@ -339,28 +624,35 @@ impl<'a> Cell<'a> {
         metric: &block::Metric,
         ctx: &Context,
     ) -> fmt::Result {
-        let max_size_width = ctx.max_size_width;
+        let out = Self::align(&metric.to_string(), ctx.max_size_width, ctx);
 
         if ctx.no_color() {
-            return write!(f, "{metric:>max_size_width$}");
+            return write!(f, "{out}");
         }
 
         let bytes = metric.value * u64::from(BLOCK_SIZE_BYTES);
 
-        let color = match ctx.unit {
+        if let Some(style) = Self::gradient_style(bytes, ctx) {
+            return write!(f, "{}", style.paint(out));
+        }
+
+        let color = match ctx.prefix_kind() {
             PrefixKind::Si => {
                 let pre = SiPrefix::from(bytes);
                 styles::get_du_theme().unwrap().get(pre.as_str()).unwrap()
             },
-            PrefixKind::Bin => {
+            PrefixKind::Bin
+            | PrefixKind::Raw
+            | PrefixKind::RawGrouped
+            | PrefixKind::Sectors
+            | PrefixKind::Blocks
+            | PrefixKind::Pages => {
                 let pre = BinPrefix::from(bytes);
                 styles::get_du_theme().unwrap().get(pre.as_str()).unwrap()
             },
         };
 
-        let out = color.paint(format!("{metric:>max_size_width$}"));
-
-        write!(f, "{out}")
+        write!(f, "{}", color.paint(out))
     }
 
     /// Rules to format disk usage as unit-less values such as word count, lines, and blocks (unix).
@@ -370,14 +662,14 @@ impl<'a> Cell<'a> {
         metric: &M,
         ctx: &Context,
     ) -> fmt::Result {
-        let max_size_width = ctx.max_size_width;
+        let out = Self::align(&metric.to_string(), ctx.max_size_width, ctx);
 
         if ctx.no_color() {
-            return write!(f, "{metric:>max_size_width$}");
+            return write!(f, "{out}");
         }
         let color = styles::get_du_theme().unwrap().get("B").unwrap();
 
-        write!(f, "{}", color.paint(format!("{metric:>max_size_width$}")))
+        write!(f, "{}", color.paint(out))
     }
 }
 
@@ -405,6 +697,12 @@ impl Display for Cell<'_> {
 
             #[cfg(unix)]
             Kind::Group => self.fmt_group(f),
+
+            #[cfg(target_os = "linux")]
+            Kind::SecurityContext => self.fmt_security_context(f),
+
+            #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+            Kind::Flags => self.fmt_flags(f),
         }
     }
 }
@@ -1,4 +1,9 @@
-use crate::{ansi::Escaped, tree::node::Node, Context};
+use crate::{
+    ansi::Escaped,
+    styles,
+    tree::{collapsed::Collapsed, node::Node},
+    Context,
+};
 use cell::Cell;
 use std::{
     fmt::{self, Display},
@@ -8,6 +13,12 @@ use std::{
 #[cfg(unix)]
 use super::long;
 
+#[cfg(unix)]
+use crate::fs::xattr;
+
+#[cfg(unix)]
+use std::{fmt::Write as _, path::Path};
+
 /// Concerned with rules to construct and a single cell in a given row.
 pub mod cell;
 
@@ -18,6 +29,29 @@ pub struct Row<'a, T> {
     layout: PhantomData<T>,
 }
 
+/// A row that stands in for the children of `parent` that were collapsed by `--top` or
+/// `--no-artifacts`. The summary is lined up with the file-name column of regular rows.
+pub struct Summary<'a, T> {
+    prefix: &'a str,
+    ctx: &'a Context,
+    parent: &'a Node,
+    collapsed: &'a Collapsed,
+    layout: PhantomData<T>,
+}
+
+/// Labels of the columns of the long view printed above the output by '--header'. Each label is
+/// padded to the width of its column as rendered for `root`.
+#[cfg(unix)]
+pub struct Header<'a, T> {
+    ctx: &'a Context,
+    root: &'a Node,
+    layout: PhantomData<T>,
+}
+
+/// Width to align sizes against when `--aligned-sizes` is used but the width of the terminal is
+/// unknown, such as when output is piped.
+const DEFAULT_ALIGNED_WIDTH: usize = 80;
+
 /// For both the [`super::Regular`] and [`super::Inverted`] layout variants.
 pub struct Tree;
 
@@ -35,6 +69,191 @@ impl<'a, T> Row<'a, T> {
     }
 }
 
+#[cfg(unix)]
+impl<'a, T> Header<'a, T> {
+    pub const fn new(root: &'a Node, ctx: &'a Context) -> Self {
+        Self {
+            ctx,
+            root,
+            layout: PhantomData,
+        }
+    }
+
+    /// Labels of the long view columns followed by that of disk usage if it's shown in its usual
+    /// place, each separated by `sep`.
+    fn labels(&self, sep: &str) -> String {
+        let ctx = self.ctx;
+
+        let label = |label: &str, kind| {
+            if ctx.parseable {
+                return label.to_owned();
+            }
+
+            let left_aligned = matches!(kind, cell::Kind::Permissions);
+
+            #[cfg(target_os = "linux")]
+            let left_aligned = left_aligned || matches!(kind, cell::Kind::SecurityContext);
+
+            #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+            let left_aligned = left_aligned || matches!(kind, cell::Kind::Flags);
+
+            let cell = Cell::new(self.root, ctx, kind).to_string();
+            let width = <str as Escaped>::visible_len(&cell);
+
+            if left_aligned {
+                format!("{label:<width$}")
+            } else {
+                format!("{label:>width$}")
+            }
+        };
+
+        let columns = ctx
+            .long_columns()
+            .iter()
+            .map(|column| label(column.label(), long::cell_kind(*column)))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        if ctx.long_columns_selected() {
+            format!("{columns}{sep}")
+        } else if ctx.parseable {
+            let size = if ctx.measures_sizes() { "Size " } else { "" };
+            format!("{columns}{sep}{size}")
+        } else if ctx.aligned_sizes {
+            format!("{columns} ")
+        } else {
+            let size = if ctx.measures_sizes() { "Size" } else { "" };
+            format!("{columns}{sep}{} ", label(size, cell::Kind::FileSize))
+        }
+    }
+}
+
+impl<'a, T> Summary<'a, T> {
+    pub const fn new(
+        parent: &'a Node,
+        collapsed: &'a Collapsed,
+        ctx: &'a Context,
+        prefix: &'a str,
+    ) -> Self {
+        Self {
+            prefix,
+            ctx,
+            parent,
+            collapsed,
+            layout: PhantomData,
+        }
+    }
+
+    /// The summary of collapsed entries, styled distinctly from regular file names.
+    fn collapsed(&self) -> String {
+        styles::get_collapsed_style().map_or_else(
+            |_| self.collapsed.to_string(),
+            |style| style.paint(self.collapsed.to_string()).to_string(),
+        )
+    }
+
+    /// Writes out `row`, truncating it if necessary.
+    fn write_row(&self, f: &mut fmt::Formatter<'_>, row: &str) -> fmt::Result {
+        match self.ctx.window_width {
+            Some(window_width) if self.ctx.truncate => {
+                let out = <str as Escaped>::truncate(row, window_width);
+                write!(f, "{out}")
+            },
+            _ => write!(f, "{row}"),
+        }
+    }
+}
+
+impl Display for Summary<'_, Tree> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let node = self.parent;
+        let ctx = self.ctx;
+
+        let size = Cell::new(node, ctx, cell::Kind::FileSize);
+
+        #[cfg(unix)]
+        let leading = if ctx.long() {
+            let long_display = long::Display::new(node, ctx);
+
+            if ctx.aligned_sizes || ctx.long_columns_selected() {
+                format!("{long_display} ")
+            } else {
+                format!("{long_display} {size} ")
+            }
+        } else if ctx.aligned_sizes {
+            leading_columns(node, ctx)
+        } else {
+            format!("{}{size} ", leading_columns(node, ctx))
+        };
+
+        #[cfg(not(unix))]
+        let leading = if ctx.aligned_sizes {
+            String::new()
+        } else {
+            format!("{size} ")
+        };
+
+        let padding = " ".repeat(<str as Escaped>::visible_len(&leading));
+
+        self.write_row(f, &format!("{padding}{}{}", self.prefix, self.collapsed()))
+    }
+}
+
+impl Display for Summary<'_, Flat> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let node = self.parent;
+        let ctx = self.ctx;
+
+        let size = Cell::new(node, ctx, cell::Kind::FileSize);
+
+        #[cfg(unix)]
+        let leading = if ctx.long() {
+            let long_display = long::Display::new(node, ctx);
+
+            if ctx.long_columns_selected() {
+                format!("{long_display}   ")
+            } else if ctx.aligned_sizes {
+                format!("{long_display} ")
+            } else {
+                format!("{long_display}   {size} ")
+            }
+        } else if ctx.aligned_sizes {
+            leading_columns(node, ctx)
+        } else {
+            format!("{}{size}   ", leading_columns(node, ctx))
+        };
+
+        #[cfg(not(unix))]
+        let leading = if ctx.aligned_sizes {
+            String::new()
+        } else {
+            format!("{size}   ")
+        };
+
+        let padding = " ".repeat(<str as Escaped>::visible_len(&leading));
+
+        self.write_row(f, &format!("{padding}{}{}", self.prefix, self.collapsed()))
+    }
+}
+
+#[cfg(unix)]
+impl Display for Header<'_, Tree> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctx.parseable {
+            return write!(f, "{}Path", self.labels(" "));
+        }
+        write!(f, "{}Name", self.labels(" "))
+    }
+}
+
+#[cfg(unix)]
+impl Display for Header<'_, Flat> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sep = if self.ctx.parseable { " " } else { "   " };
+        write!(f, "{}Path", self.labels(sep))
+    }
+}
+
 #[cfg(unix)]
 impl Display for Row<'_, Tree> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -50,22 +269,40 @@ impl Display for Row<'_, Tree> {
             },
         );
 
-        let row = if ctx.long {
-            let optionals = long::Optionals::from(ctx);
-            let long_display = long::Display::new(optionals, node, ctx);
+        let row = if ctx.parseable {
+            parseable_row(node, ctx)
+        } else if ctx.long() {
+            let long_display = long::Display::new(node, ctx);
 
-            format!("{long_display} {size} {name}")
+            if ctx.long_columns_selected() {
+                format!("{long_display} {name}")
+            } else if ctx.aligned_sizes {
+                align_size(ctx, &format!("{long_display} {name}"), &size)
+            } else {
+                format!("{long_display} {size} {name}")
+            }
+        } else if ctx.aligned_sizes {
+            align_size(ctx, &format!("{}{name}", leading_columns(node, ctx)), &size)
         } else {
-            format!("{size} {name}")
+            format!("{}{size} {name}", leading_columns(node, ctx))
         };
 
-        if ctx.truncate && ctx.window_width.is_some() {
+        if ctx.truncate && ctx.window_width.is_some() && !ctx.parseable {
             let window_width = ctx.window_width.unwrap();
             let out = <str as Escaped>::truncate(&row, window_width);
-            write!(f, "{out}")
+            write!(f, "{out}")?;
         } else {
-            write!(f, "{row}")
+            write!(f, "{row}")?;
+        }
+
+        if ctx.has_details() {
+            for line in detail_lines(node, ctx, &row, &name.to_string(), self.prefix) {
+                writeln!(f)?;
+                write!(f, "{line}")?;
+            }
         }
+
+        Ok(())
     }
 }
 
@@ -78,22 +315,40 @@ impl Display for Row<'_, Flat> {
         let size = Cell::new(node, ctx, cell::Kind::FileSize);
         let path = Cell::new(node, ctx, cell::Kind::FilePath);
 
-        let row = if ctx.long {
-            let optionals = long::Optionals::from(ctx);
-            let long_display = long::Display::new(optionals, node, ctx);
+        let row = if ctx.parseable {
+            parseable_row(node, ctx)
+        } else if ctx.long() {
+            let long_display = long::Display::new(node, ctx);
 
-            format!("{long_display}   {size} {path}")
+            if ctx.long_columns_selected() {
+                format!("{long_display}   {path}")
+            } else if ctx.aligned_sizes {
+                align_size(ctx, &format!("{long_display} {path}"), &size)
+            } else {
+                format!("{long_display}   {size} {path}")
+            }
+        } else if ctx.aligned_sizes {
+            align_size(ctx, &format!("{}{path}", leading_columns(node, ctx)), &size)
         } else {
-            format!("{size}   {path}")
+            format!("{}{size}   {path}", leading_columns(node, ctx))
         };
 
-        if ctx.truncate && ctx.window_width.is_some() {
+        if ctx.truncate && ctx.window_width.is_some() && !ctx.parseable {
             let window_width = ctx.window_width.unwrap();
             let out = <str as Escaped>::truncate(&row, window_width);
-            write!(f, "{out}")
+            write!(f, "{out}")?;
         } else {
-            write!(f, "{row}")
+            write!(f, "{row}")?;
+        }
+
+        if ctx.has_details() {
+            for line in detail_lines(node, ctx, &row, &path.to_string(), None) {
+                writeln!(f)?;
+                write!(f, "{line}")?;
+            }
         }
+
+        Ok(())
     }
 }
 
@@ -112,7 +367,11 @@ impl Display for Row<'_, Tree> {
             },
         );
 
-        let row = format!("{size} {name}");
+        let row = if ctx.aligned_sizes {
+            align_size(ctx, &name.to_string(), &size)
+        } else {
+            format!("{size} {name}")
+        };
 
         if ctx.truncate && ctx.window_width.is_some() {
             let window_width = ctx.window_width.unwrap();
@@ -133,7 +392,11 @@ impl Display for Row<'_, Flat> {
         let size = Cell::new(node, ctx, cell::Kind::FileSize);
         let path = Cell::new(node, ctx, cell::Kind::FilePath);
 
-        let row = format!("{size}   {path}");
+        let row = if ctx.aligned_sizes {
+            align_size(ctx, &path.to_string(), &size)
+        } else {
+            format!("{size}   {path}")
+        };
 
         if ctx.truncate && ctx.window_width.is_some() {
             let window_width = ctx.window_width.unwrap();
@@ -144,3 +407,126 @@ impl Display for Row<'_, Flat> {
         }
     }
 }
+
+/// Lines beneath `row` that list the extended attributes of `node` for '--list-xattrs' and the
+/// entries of its access control lists for '--acl'. They line up with `name` within `row`,
+/// continuing the tree drawn by `prefix` if there is one.
+#[cfg(unix)]
+fn detail_lines(
+    node: &Node,
+    ctx: &Context,
+    row: &str,
+    name: &str,
+    prefix: Option<&str>,
+) -> Vec<String> {
+    let mut details = vec![];
+
+    if ctx.list_xattrs {
+        details.extend(
+            node.xattrs()
+                .into_iter()
+                .map(|xattr::Attr { name, value }| {
+                    let name = name.to_string_lossy();
+
+                    if ctx.xattr_values {
+                        format!("{name} = \"{}\"", value.escape_ascii())
+                    } else {
+                        format!("{name} ({} B)", value.len())
+                    }
+                }),
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    if ctx.acl {
+        details.extend(node.acl_entries());
+    }
+
+    if details.is_empty() {
+        return details;
+    }
+
+    let indent = row
+        .find(name)
+        .map_or(0, |i| <str as Escaped>::visible_len(&row[..i]));
+
+    let continuation = prefix
+        .unwrap_or_default()
+        .chars()
+        .map(|c| match c {
+            '\u{251C}' | '\u{250C}' => '\u{2502}',
+            '\u{2514}' | '\u{2500}' => ' ',
+            _ => c,
+        })
+        .collect::<String>();
+
+    details
+        .into_iter()
+        .map(|detail| format!("{:indent$}{continuation}{detail}", ""))
+        .collect()
+}
+
+/// A row of '--parseable' output: The attributes of the long view followed by disk usage, unless
+/// it's suppressed or among the selected columns, and finally the path of `node` relative to the
+/// root, free of icons and annotations. Fields are separated by exactly one space, with neither
+/// padding nor tree prefixes.
+#[cfg(unix)]
+fn parseable_row(node: &Node, ctx: &Context) -> String {
+    let mut fields = vec![long::Display::new(node, ctx).to_string()];
+
+    if ctx.measures_sizes() && !ctx.long_columns_selected() {
+        let size = Cell::new(node, ctx, cell::Kind::FileSize).to_string();
+        fields.push(long::field(&size));
+    }
+
+    let path = if node.depth() == 0 {
+        Path::new(node.file_name()).display()
+    } else {
+        node.path()
+            .strip_prefix(ctx.dir_canonical())
+            .unwrap_or_else(|_| node.path())
+            .display()
+    };
+    fields.push(path.to_string());
+
+    fields.join(" ")
+}
+
+/// The inode number and security context that lead rows outside of long view when '--ino' and
+/// '--context' are used. Long view places them among its own attributes.
+#[cfg(unix)]
+fn leading_columns(node: &Node, ctx: &Context) -> String {
+    let mut out = String::new();
+
+    if ctx.long() {
+        return out;
+    }
+
+    if ctx.ino {
+        let _ = write!(out, "{} ", Cell::new(node, ctx, cell::Kind::Ino));
+    }
+
+    #[cfg(target_os = "linux")]
+    if ctx.selinux {
+        let _ = write!(
+            out,
+            "{} ",
+            Cell::new(node, ctx, cell::Kind::SecurityContext)
+        );
+    }
+
+    out
+}
+
+/// Lays out `lhs` followed by `size` such that `size` is flush against the right edge of the
+/// terminal, filling the space in between with dot leaders.
+fn align_size(ctx: &Context, lhs: &str, size: &Cell) -> String {
+    let size = size.to_string();
+    let width = ctx.window_width.unwrap_or(DEFAULT_ALIGNED_WIDTH);
+    let used = <str as Escaped>::visible_len(lhs) + <str as Escaped>::visible_len(&size) + 2;
+
+    match width.checked_sub(used) {
+        Some(n) if n > 0 => format!("{lhs} {} {size}", ".".repeat(n)),
+        _ => format!("{lhs} {size}"),
+    }
+}
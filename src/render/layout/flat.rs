@@ -1,6 +1,8 @@
 use crate::{
     render::{
-        grid::{self, Row},
+        grid::{self, Row, Summary},
+        layout::sections::Sections,
+        stripe::Stripe,
         Engine, Flat,
     },
     tree::{count::FileCount, Tree},
@@ -16,6 +18,23 @@ impl Display for Engine<Flat> {
         let root_id = tree.root_id();
         let max_depth = ctx.level();
         let mut file_count_data = vec![];
+        let mut stripe = Stripe::new(ctx);
+        let mut sections = Sections::new(tree, ctx);
+
+        #[cfg(unix)]
+        if ctx.header {
+            let header = grid::Header::<grid::Flat>::new(arena[root_id].get(), ctx);
+            writeln!(f, "{header}")?;
+        }
+
+        let root = arena[root_id].get();
+        let summary = root
+            .collapsed()
+            .map(|collapsed| Summary::<grid::Flat>::new(root, collapsed, ctx, ""));
+
+        if let Some(ref summary) = summary {
+            writeln!(f, "{}", stripe.paint(summary, 1))?;
+        }
 
         for edge in root_id.reverse_traverse(arena) {
             let node_id = match edge {
@@ -30,13 +49,24 @@ impl Display for Engine<Flat> {
                 continue;
             }
 
+            // Rows are written bottom-up so each heading follows the rows of its section.
+            if let Some(ref mut sections) = sections {
+                if node.depth() == 0 || sections.starts_section(node) {
+                    if let Some(heading) = sections.heading() {
+                        writeln!(f, "{heading}")?;
+                    }
+                    sections.enter(node);
+                }
+            }
+
             let row = Row::<grid::Flat>::new(node, ctx, None);
 
-            writeln!(f, "{row}")?;
+            writeln!(f, "{}", stripe.paint(&row, node.depth()))?;
         }
 
-        if !file_count_data.is_empty() {
-            write!(f, "\n{}", FileCount::from(file_count_data))?;
+        if ctx.tallies() && !file_count_data.is_empty() {
+            let file_count = FileCount::from(file_count_data).with_prefix_kind(ctx.prefix_kind());
+            write!(f, "\n{file_count}")?;
         }
 
         Ok(())
@@ -1,11 +1,14 @@
 use crate::{
     render::{
-        grid::{self, Row},
+        grid::{self, Row, Summary},
+        prefix,
+        stripe::Stripe,
         theme, Engine, Inverted,
     },
     styles,
     tree::{count::FileCount, Tree},
 };
+use indextree::NodeEdge;
 use std::fmt::{self, Display};
 
 impl Display for Engine<Inverted> {
@@ -17,11 +20,16 @@ impl Display for Engine<Inverted> {
         let arena = tree.arena();
         let level = ctx.level();
         let mut file_count_data = vec![];
+        let mut stripe = Stripe::new(ctx);
 
-        let mut descendants = root_id.descendants(arena).skip(1).peekable();
+        #[cfg(unix)]
+        if ctx.header {
+            let header = grid::Header::<grid::Tree>::new(arena[root_id].get(), ctx);
+            writeln!(f, "{header}")?;
+        }
 
         let root = Row::<grid::Tree>::new(arena[root_id].get(), ctx, Some(""));
-        writeln!(f, "{root}")?;
+        writeln!(f, "{}", stripe.paint(&root, 0))?;
 
         file_count_data.push(Tree::compute_file_count(root_id, arena));
 
@@ -31,60 +39,81 @@ impl Display for Engine<Inverted> {
             theme::regular_theme_getter()
         };
 
-        let mut base_prefix_components = vec![""];
+        let mut prefixes = prefix::Stack::default();
 
-        while let Some(current_node_id) = descendants.next() {
-            file_count_data.push(Tree::compute_file_count(current_node_id, arena));
+        for node_edge in root_id.traverse(arena).skip(1) {
+            match node_edge {
+                NodeEdge::Start(current_node_id) => {
+                    file_count_data.push(Tree::compute_file_count(current_node_id, arena));
 
-            let current_node = arena[current_node_id].get();
+                    let current_node = arena[current_node_id].get();
 
-            let current_depth = current_node.depth();
+                    let current_depth = current_node.depth();
 
-            let mut siblings = current_node_id.following_siblings(arena).skip(1).peekable();
+                    let last_sibling = Tree::is_last_sibling(current_node_id, arena);
 
-            let last_sibling = siblings.peek().is_none();
+                    let theme = get_theme(current_node);
 
-            let theme = get_theme(current_node);
+                    if current_depth <= level {
+                        let prefix_part = if last_sibling {
+                            theme.get("uprt").unwrap()
+                        } else {
+                            theme.get("vtrt").unwrap()
+                        };
 
-            if current_depth <= level {
-                let prefix_part = if last_sibling {
-                    theme.get("uprt").unwrap()
-                } else {
-                    theme.get("vtrt").unwrap()
-                };
+                        prefixes.with(prefix_part, |prefix| {
+                            let row = Row::<grid::Tree>::new(current_node, ctx, Some(prefix));
+                            writeln!(f, "{}", stripe.paint(&row, current_depth))
+                        })?;
+                    }
 
-                let mut current_prefix_components = base_prefix_components.clone();
+                    let num_children = current_node_id.children(arena).count();
 
-                current_prefix_components.push(prefix_part);
+                    if current_node.has_visible_contents(num_children) {
+                        if last_sibling {
+                            prefixes.push(styles::SEP);
+                        } else {
+                            prefixes.push(theme.get("vt").unwrap());
+                        }
+                    }
+                },
 
-                let prefix = current_prefix_components.join("");
+                NodeEdge::End(current_node_id) => {
+                    let current_node = arena[current_node_id].get();
 
-                let row = Row::<grid::Tree>::new(current_node, ctx, Some(&prefix));
-                writeln!(f, "{row}")?;
-            }
+                    let num_children = current_node_id.children(arena).count();
 
-            if let Some(next_id) = descendants.peek() {
-                let next_node = arena[*next_id].get();
-
-                let next_depth = next_node.depth();
+                    if !current_node.has_visible_contents(num_children) {
+                        continue;
+                    }
 
-                if next_depth == current_depth + 1 {
-                    if last_sibling {
-                        base_prefix_components.push(styles::SEP);
-                    } else {
-                        let prefix = theme.get("vt").unwrap();
-                        base_prefix_components.push(prefix);
+                    if let Some(collapsed) = current_node.collapsed() {
+                        if current_node.depth() < level {
+                            let uprt = styles::get_tree_theme().unwrap().get("uprt").unwrap();
+
+                            prefixes.with(uprt, |prefix| {
+                                let summary = Summary::<grid::Tree>::new(
+                                    current_node,
+                                    collapsed,
+                                    ctx,
+                                    prefix,
+                                );
+                                let depth = current_node.depth() + 1;
+                                writeln!(f, "{}", stripe.paint(&summary, depth))
+                            })?;
+                        }
                     }
-                } else if next_depth < current_depth {
-                    let depth_delta = current_depth - next_depth;
 
-                    base_prefix_components.truncate(base_prefix_components.len() - depth_delta);
-                }
+                    if current_node_id != root_id {
+                        prefixes.pop();
+                    }
+                },
             }
         }
 
-        if !file_count_data.is_empty() {
-            write!(f, "\n{}", FileCount::from(file_count_data))?;
+        if ctx.tallies() && !file_count_data.is_empty() {
+            let file_count = FileCount::from(file_count_data).with_prefix_kind(ctx.prefix_kind());
+            write!(f, "\n{file_count}")?;
         }
 
         Ok(())
@@ -9,3 +9,9 @@ pub mod flat_inverted;
 
 /// See [`super::Inverted`]
 pub mod inverted;
+
+/// See [`super::TopFiles`]
+pub mod top_files;
+
+/// Sections of flat layouts when using '--group-by'.
+pub mod sections;
@@ -1,6 +1,8 @@
 use crate::{
     render::{
-        grid::{self, Row},
+        grid::{self, Row, Summary},
+        prefix,
+        stripe::Stripe,
         theme, Engine, Regular,
     },
     styles,
@@ -17,6 +19,7 @@ impl Display for Engine<Regular> {
         let arena = tree.arena();
         let max_depth = ctx.level();
         let mut file_count_data = vec![];
+        let mut stripe = Stripe::new(ctx);
 
         let mut get_theme = if ctx.follow {
             theme::link_theme_getter()
@@ -24,7 +27,23 @@ impl Display for Engine<Regular> {
             theme::regular_theme_getter()
         };
 
-        let mut base_prefix_components = vec![""];
+        let mut prefixes = prefix::Stack::default();
+
+        #[cfg(unix)]
+        if ctx.header {
+            let header = grid::Header::<grid::Tree>::new(arena[root_id].get(), ctx);
+            writeln!(f, "{header}")?;
+        }
+
+        let root = arena[root_id].get();
+
+        if let Some(collapsed) = root.collapsed() {
+            if max_depth > 0 {
+                let prefix = styles::get_tree_theme().unwrap().get("drt").unwrap();
+                let summary = Summary::<grid::Tree>::new(root, collapsed, ctx, prefix);
+                writeln!(f, "{}", stripe.paint(&summary, 1))?;
+            }
+        }
 
         let mut tree_edges = root_id.reverse_traverse(arena).skip(1).peekable();
 
@@ -34,19 +53,43 @@ impl Display for Engine<Regular> {
 
                 NodeEdge::End(id) => {
                     let current_node = arena[id].get();
+                    let num_children = id.children(arena).count();
 
-                    if !current_node.is_dir() || id.children(arena).count() == 0 {
+                    if !current_node.has_visible_contents(num_children) {
                         continue;
                     }
 
                     let theme = get_theme(current_node);
 
-                    let topmost_sibling = id.following_siblings(arena).nth(1).is_none();
+                    let topmost_sibling = Tree::is_last_sibling(id, arena);
 
-                    if topmost_sibling {
-                        base_prefix_components.push(styles::SEP);
+                    let continuation = if topmost_sibling {
+                        styles::SEP
                     } else {
-                        base_prefix_components.push(theme.get("vt").unwrap());
+                        theme.get("vt").unwrap().as_str()
+                    };
+
+                    if let Some(collapsed) = current_node.collapsed() {
+                        if current_node.depth() < max_depth {
+                            prefixes.push(continuation);
+
+                            prefixes.with(theme.get("drt").unwrap(), |prefix| {
+                                let summary = Summary::<grid::Tree>::new(
+                                    current_node,
+                                    collapsed,
+                                    ctx,
+                                    prefix,
+                                );
+                                let depth = current_node.depth() + 1;
+                                writeln!(f, "{}", stripe.paint(&summary, depth))
+                            })?;
+
+                            prefixes.pop();
+                        }
+                    }
+
+                    if num_children > 0 {
+                        prefixes.push(continuation);
                     }
 
                     continue;
@@ -59,14 +102,14 @@ impl Display for Engine<Regular> {
 
             let node_depth = current_node.depth();
 
-            let topmost_sibling = current_node_id.following_siblings(arena).nth(1).is_none();
+            let topmost_sibling = Tree::is_last_sibling(current_node_id, arena);
 
             let theme = get_theme(current_node);
 
             if node_depth <= max_depth {
                 if node_depth == 0 {
                     let row = Row::<grid::Tree>::new(current_node, ctx, Some(""));
-                    writeln!(f, "{row}")?;
+                    writeln!(f, "{}", stripe.paint(&row, node_depth))?;
                 } else {
                     let prefix_part = if topmost_sibling {
                         theme.get("drt").unwrap()
@@ -74,14 +117,10 @@ impl Display for Engine<Regular> {
                         theme.get("vtrt").unwrap()
                     };
 
-                    let mut current_prefix_components = base_prefix_components.clone();
-
-                    current_prefix_components.push(prefix_part);
-
-                    let prefix = current_prefix_components.join("");
-
-                    let row = Row::<grid::Tree>::new(current_node, ctx, Some(&prefix));
-                    writeln!(f, "{row}")?;
+                    prefixes.with(prefix_part, |prefix| {
+                        let row = Row::<grid::Tree>::new(current_node, ctx, Some(prefix));
+                        writeln!(f, "{}", stripe.paint(&row, node_depth))
+                    })?;
                 }
             }
 
@@ -89,13 +128,14 @@ impl Display for Engine<Regular> {
                 let next_node = arena[*next_id].get();
 
                 if next_node.depth() < node_depth {
-                    base_prefix_components.pop();
+                    prefixes.pop();
                 }
             }
         }
 
-        if !file_count_data.is_empty() {
-            write!(f, "\n{}", FileCount::from(file_count_data))?;
+        if ctx.tallies() && !file_count_data.is_empty() {
+            let file_count = FileCount::from(file_count_data).with_prefix_kind(ctx.prefix_kind());
+            write!(f, "\n{file_count}")?;
         }
 
         Ok(())
@@ -0,0 +1,67 @@
+use crate::{
+    context::{group::Key, Context},
+    styles,
+    tree::{
+        collapsed::Collapsed,
+        group::{self, Label},
+        node::Node,
+        Tree,
+    },
+};
+use std::{collections::HashMap, path::PathBuf};
+
+/// Keeps track of which '--group-by' section consecutive rows of a flat layout belong to so that
+/// each section can be set apart by a heading with its subtotal.
+pub struct Sections<'a> {
+    ctx: &'a Context,
+    key: Key,
+    root: PathBuf,
+    subtotals: HashMap<Label, Collapsed>,
+    current: Option<Label>,
+}
+
+impl<'a> Sections<'a> {
+    /// Returns `None` unless '--group-by' is in effect.
+    pub fn new(tree: &Tree, ctx: &'a Context) -> Option<Self> {
+        let key = ctx.group_by?;
+        let root = ctx.dir_canonical();
+        let subtotals = group::subtotals(tree, key, &root, ctx);
+
+        Some(Self {
+            ctx,
+            key,
+            root,
+            subtotals,
+            current: None,
+        })
+    }
+
+    /// Whether or not `node` belongs to a different section than the previous row. The root is
+    /// never part of a section.
+    pub fn starts_section(&self, node: &Node) -> bool {
+        node.depth() > 0 && self.current.as_ref() != Some(&Label::of(node, self.key, &self.root))
+    }
+
+    /// Moves on to the section of `node`.
+    pub fn enter(&mut self, node: &Node) {
+        self.current = Some(Label::of(node, self.key, &self.root));
+    }
+
+    /// The heading of the current section along with its subtotal, if it has one.
+    pub fn heading(&self) -> Option<String> {
+        let label = self.current.as_ref()?;
+
+        let heading = match self.subtotals.get(label).and_then(Collapsed::file_size) {
+            _ if *label == Label::Directories => format!("[{label}]"),
+            Some(file_size) if !self.ctx.suppress_size => format!("[{label}] {file_size}"),
+            _ => format!("[{label}]"),
+        };
+
+        let heading = styles::get_collapsed_style().map_or_else(
+            |_| heading.clone(),
+            |style| style.paint(&heading).to_string(),
+        );
+
+        Some(heading)
+    }
+}
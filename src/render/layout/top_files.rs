@@ -0,0 +1,32 @@
+use crate::render::{
+    grid::cell::{self, Cell},
+    Engine, TopFiles,
+};
+use std::fmt::{self, Display};
+
+impl Display for Engine<TopFiles> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ctx = self.context();
+        let tree = self.tree();
+        let arena = tree.arena();
+        let n = ctx.top_files.map_or(0, usize::from);
+
+        let rows = tree
+            .largest_files(n)
+            .into_iter()
+            .map(|node_id| {
+                let node = arena[node_id].get();
+                let size = Cell::new(node, ctx, cell::Kind::FileSize);
+                let path = node.path().display().to_string();
+
+                let path = node
+                    .style()
+                    .map_or_else(|| path.clone(), |style| style.paint(&path).to_string());
+
+                format!("{size}   {path}")
+            })
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", rows.join("\n"))
+    }
+}
@@ -1,138 +1,78 @@
 use super::grid::cell::{self, Cell};
-use crate::{context::Context, tree::node::Node};
-use std::{convert::From, fmt};
+use crate::{
+    ansi::Escaped,
+    context::{long::Column, Context},
+    tree::node::Node,
+};
+use std::fmt;
 
 /// Concerned with displaying that actual attributes associated with the long view.
 pub struct Display<'a> {
     node: &'a Node,
     ctx: &'a Context,
-    optional: Optionals,
-}
-
-/// Optionals fields that are displayed when `--long` is specified. Each field is a boolean,
-/// specifying whether or not a particular field should be included in the output when the long
-/// view is enabled.
-pub struct Optionals {
-    #[allow(dead_code)]
-    perms: bool,
-    #[allow(dead_code)]
-    owner: bool,
-    group: bool,
-    ino: bool,
-    #[allow(dead_code)]
-    nlink: bool,
-    #[allow(dead_code)]
-    time: bool,
 }
 
 impl<'a> Display<'a> {
     /// Constructor for [`Display`].
-    pub const fn new(optional: Optionals, node: &'a Node, ctx: &'a Context) -> Self {
-        Self {
-            node,
-            ctx,
-            optional,
-        }
+    pub const fn new(node: &'a Node, ctx: &'a Context) -> Self {
+        Self { node, ctx }
     }
 }
 
-/// Default implementation for [`Optionals`]. Fields that are `true` are the default fields that
-/// should display when the long view is enabled.
-impl Default for Optionals {
-    fn default() -> Self {
-        Self {
-            perms: true,
-            owner: true,
-            group: false,
-            ino: false,
-            nlink: false,
-            time: true,
-        }
+/// The kind of [Cell] that renders `column`.
+pub const fn cell_kind(column: Column) -> cell::Kind<'static> {
+    match column {
+        Column::Ino => cell::Kind::Ino,
+        Column::Perms => cell::Kind::Permissions,
+        Column::Nlink => cell::Kind::Nlink,
+        Column::Owner => cell::Kind::Owner,
+        Column::Group => cell::Kind::Group,
+        #[cfg(target_os = "linux")]
+        Column::Context => cell::Kind::SecurityContext,
+        #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+        Column::Flags => cell::Kind::Flags,
+        Column::Size => cell::Kind::FileSize,
+        Column::Time => cell::Kind::Datetime,
     }
 }
 
 impl fmt::Display for Display<'_> {
-    /// Formatting the attributes associated with the long view.
+    /// Formatting the attributes associated with the long view in the order given by
+    /// [`Context::long_columns`]. If `--parseable` is enabled then attributes are stripped of
+    /// styling and alignment such that each one is a single field separated by exactly one space.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Optionals {
-            group, ino, nlink, ..
-        } = self.optional;
         let node = self.node;
         let ctx = self.ctx;
 
-        let perms = Cell::new(node, ctx, cell::Kind::Permissions);
-        let owner = Cell::new(node, ctx, cell::Kind::Owner);
-        let time = Cell::new(node, ctx, cell::Kind::Datetime);
-
-        match (group, ino, nlink) {
-            (false, false, false) => {
-                write!(f, "{perms} {owner} {time}")
-            },
-
-            (true, true, true) => {
-                let group_out = Cell::new(node, ctx, cell::Kind::Group);
-                let ino_out = Cell::new(node, ctx, cell::Kind::Ino);
-                let nlink_out = Cell::new(node, ctx, cell::Kind::Nlink);
-
-                write!(
-                    f,
-                    "{ino_out} {perms} {nlink_out} {owner} {group_out} {time}"
-                )
-            },
-
-            (true, false, false) => {
-                let group_out = Cell::new(node, ctx, cell::Kind::Group);
-
-                write!(f, "{perms} {owner} {group_out} {time}")
-            },
-
-            (true, true, false) => {
-                let group_out = Cell::new(node, ctx, cell::Kind::Group);
-                let ino_out = Cell::new(node, ctx, cell::Kind::Ino);
-
-                write!(f, "{ino_out} {perms} {owner} {group_out} {time}")
-            },
-
-            (false, false, true) => {
-                let nlink_out = Cell::new(node, ctx, cell::Kind::Nlink);
-
-                write!(f, "{perms} {nlink_out} {owner} {time}")
-            },
-
-            (true, false, true) => {
-                let group_out = Cell::new(node, ctx, cell::Kind::Group);
-                let nlink_out = Cell::new(node, ctx, cell::Kind::Nlink);
-
-                write!(f, "{perms} {nlink_out} {owner} {group_out} {time}")
-            },
-
-            (false, true, false) => {
-                let ino_out = Cell::new(node, ctx, cell::Kind::Ino);
-
-                write!(f, "{ino_out} {perms} {owner} {time}")
-            },
-
-            (false, true, true) => {
-                let ino_out = Cell::new(node, ctx, cell::Kind::Ino);
-                let nlink_out = Cell::new(node, ctx, cell::Kind::Nlink);
-
-                write!(f, "{ino_out} {perms} {nlink_out} {owner} {time}")
-            },
-        }
+        let out = ctx
+            .long_columns()
+            .iter()
+            .map(|column| {
+                let out = Cell::new(node, ctx, cell_kind(*column)).to_string();
+
+                if ctx.parseable {
+                    field(&out)
+                } else {
+                    out
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        write!(f, "{out}")
     }
 }
 
-impl From<&Context> for Optionals {
-    fn from(ctx: &Context) -> Self {
-        let Context {
-            group, ino, nlink, ..
-        } = *ctx;
-
-        Self {
-            group,
-            ino,
-            nlink,
-            ..Self::default()
-        }
+/// `cell` as a single field of '--parseable' output: Stripped of styling and whitespace, or `-`
+/// if nothing remains.
+pub fn field(cell: &str) -> String {
+    let out = <str as Escaped>::strip(cell)
+        .split_whitespace()
+        .collect::<String>();
+
+    if out.is_empty() {
+        String::from("-")
+    } else {
+        out
     }
 }
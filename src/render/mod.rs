@@ -1,12 +1,21 @@
 use crate::{context::Context, tree::Tree};
 use std::marker::PhantomData;
 
+/// Proportional usage bars drawn with block characters.
+pub mod bar;
+
 /// Module containing all of the layout variants.
 pub mod layout;
 
 /// Concerned with how to construct a single row in the output grid.
 pub mod grid;
 
+/// Reusable buffer for the box-drawing prefixes of rows.
+pub mod prefix;
+
+/// Alternating background shading of rows.
+pub mod stripe;
+
 /// Utility module to fetch the appropriate theme used to paint the box-drawing characters of the
 /// output tree.
 pub mod theme;
@@ -36,6 +45,9 @@ pub struct Regular;
 /// `tree` command.
 pub struct Inverted;
 
+/// The largest files beneath the root directory in descending order of size.
+pub struct TopFiles;
+
 impl<T> Engine<T> {
     /// Initializes a new [Engine].
     pub const fn new(tree: Tree, ctx: Context) -> Self {
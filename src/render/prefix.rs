@@ -0,0 +1,51 @@
+/// The box-drawing prefix of tree rows built up from one segment per level of depth. Segments are
+/// appended to and truncated from a single buffer as the tree is traversed so that rows borrow
+/// their prefix rather than each one joining every segment into a freshly allocated string.
+#[derive(Default)]
+pub struct Stack {
+    buf: String,
+    starts: Vec<usize>,
+}
+
+impl Stack {
+    /// Appends `segment` to the prefix.
+    pub fn push(&mut self, segment: &str) {
+        self.starts.push(self.buf.len());
+        self.buf.push_str(segment);
+    }
+
+    /// Removes the most recently pushed segment, if any.
+    pub fn pop(&mut self) {
+        if let Some(start) = self.starts.pop() {
+            self.buf.truncate(start);
+        }
+    }
+
+    /// Calls `op` with the prefix followed by `segment`, which is only kept for the duration of
+    /// the call.
+    pub fn with<R>(&mut self, segment: &str, op: impl FnOnce(&str) -> R) -> R {
+        self.push(segment);
+        let out = op(&self.buf);
+        self.pop();
+        out
+    }
+}
+
+#[test]
+fn push_and_pop() {
+    let mut stack = Stack::default();
+
+    stack.push("│  ");
+    stack.push("   ");
+
+    assert_eq!(stack.with("└─ ", str::to_owned), "│     └─ ");
+
+    stack.pop();
+
+    assert_eq!(stack.with("├─ ", str::to_owned), "│  ├─ ");
+
+    stack.pop();
+    stack.pop();
+
+    assert_eq!(stack.with("", str::to_owned), "");
+}
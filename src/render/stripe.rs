@@ -0,0 +1,62 @@
+use crate::{
+    ansi::Escaped,
+    context::{stripe, Context},
+    styles,
+};
+use ansi_term::Style;
+use std::fmt::Display;
+
+/// Applies alternating background shading to rows of the output as determined by `--stripe`.
+/// Shading is only ever applied if colorization is enabled.
+pub struct Stripe {
+    kind: Option<stripe::Type>,
+    style: Option<&'static Style>,
+    window_width: Option<usize>,
+    rows: usize,
+}
+
+impl Stripe {
+    /// Initializes a new [`Stripe`]; expects to be used for a single rendering of the output.
+    pub fn new(ctx: &Context) -> Self {
+        Self {
+            kind: ctx.stripe,
+            style: styles::get_stripe_style().ok(),
+            window_width: ctx.window_width,
+            rows: 0,
+        }
+    }
+
+    /// Renders `row`, shading it if it falls on a stripe. `depth` is the depth of the entry that
+    /// `row` represents. Shaded rows are padded out to the width of the terminal if known.
+    pub fn paint(&mut self, row: &impl Display, depth: usize) -> String {
+        let out = row.to_string();
+
+        let shade = match self.kind {
+            Some(stripe::Type::Depth) => depth % 2 == 1,
+            Some(stripe::Type::Row) => self.rows % 2 == 1,
+            None => false,
+        };
+
+        self.rows += 1;
+
+        let Some(style) = self.style.filter(|_| shade) else {
+            return out;
+        };
+
+        let prefix = style.prefix().to_string();
+        let reset = style.suffix().to_string();
+
+        // Resets within the row would otherwise clear the background.
+        let mut painted = out.replace(&reset, &format!("{reset}{prefix}"));
+
+        if let Some(window_width) = self.window_width {
+            let len = <str as Escaped>::visible_len(&painted);
+
+            if len < window_width {
+                painted.push_str(&" ".repeat(window_width - len));
+            }
+        }
+
+        format!("{prefix}{painted}{reset}")
+    }
+}
@@ -1,10 +1,14 @@
 use crate::{
+    context::Context,
     styles::{self, ThemesMap},
     tree::node::Node,
 };
 use ansi_term::{Color, Style};
 use std::borrow::Cow;
 
+#[cfg(feature = "nix")]
+use std::{ffi::OsStr, path::Path};
+
 type Theme = Box<dyn FnMut(&Node) -> &'static ThemesMap>;
 
 /// Returns a closure that retrieves the regular theme.
@@ -41,10 +45,17 @@ pub fn link_theme_getter() -> Theme {
 /// Stylizes the input `file_name` with the provided `style`. If `None` is provided then the
 /// underlying `String` is returned unmodified as a [Cow]. If the provided [Node] is a symlink then
 /// it will be styled accordingly.
-pub fn stylize_file_name(node: &Node) -> Cow<'_, str> {
+pub fn stylize_file_name<'a>(node: &'a Node, ctx: &Context) -> Cow<'a, str> {
     let name = node.file_name();
     let style = node.style();
 
+    #[cfg(feature = "nix")]
+    let name = if ctx.nix {
+        nix_name(node).map_or(name, OsStr::new)
+    } else {
+        name
+    };
+
     let Some(target_name) = node.symlink_target_file_name() else {
         if let Some(Style {foreground: Some(ref fg), .. }) = style {
             let file_name = name.to_string_lossy();
@@ -55,26 +66,61 @@ pub fn stylize_file_name(node: &Node) -> Cow<'_, str> {
         return name.to_string_lossy();
     };
 
+    #[cfg(feature = "nix")]
+    let target_name = match crate::fs::nix::readable_path(Path::new(target_name)) {
+        Some(readable) if ctx.nix => Cow::from(readable),
+        _ => target_name.to_string_lossy(),
+    };
+
+    #[cfg(not(feature = "nix"))]
+    let target_name = target_name.to_string_lossy();
+
+    // Broken links point somewhere that doesn't exist so they get a crossed-out arrow whereas links
+    // whose sizes are attributed to where they appear via '--logical-layout' get a double arrow.
+    let (arrow, target_style) = if node.is_broken_symlink() {
+        ('\u{219b}', Color::Red.strikethrough())
+    } else if ctx.logical_layout {
+        ('\u{21d2}', Color::Red.normal())
+    } else {
+        ('\u{2192}', Color::Red.normal())
+    };
+
     if let Some(color) = style {
         let styled_name = color.paint(name.to_string_lossy());
-        let target_name = Color::Red.paint(format!("\u{2192} {}", target_name.to_string_lossy()));
+        let target_name = target_style.paint(format!("{arrow} {target_name}"));
 
         return Cow::from(format!("{styled_name} {target_name}"));
     }
 
     let link = name.to_string_lossy();
-    let target = target_name.to_string_lossy();
-    Cow::from(format!("{link} \u{2192} {target}"))
+    Cow::from(format!("{link} {arrow} {target_name}"))
+}
+
+/// Name of `node` without its hash if it's a top-level entry of the Nix store.
+#[cfg(feature = "nix")]
+fn nix_name(node: &Node) -> Option<&str> {
+    use crate::fs::nix::{StorePath, STORE_DIR};
+
+    if node.parent_path() != Some(Path::new(STORE_DIR)) {
+        return None;
+    }
+
+    node.file_name()
+        .to_str()
+        .and_then(StorePath::from_file_name)
+        .map(|store_path| store_path.name)
 }
 
 /// Styles the symbolic notation of file permissions.
 #[cfg(unix)]
 pub fn style_sym_permissions(node: &Node) -> String {
-    use crate::fs::permissions::FileModeXAttrs;
+    use crate::fs::permissions::{FileModeAcl, FileModeXAttrs};
 
     let perms = node.mode().expect("Expected permissions to be initialized");
 
-    let symb = if node.has_xattrs() {
+    let symb = if node.has_acl() {
+        format!("{}", FileModeAcl(&perms))
+    } else if node.has_xattrs() {
         let perm_xattr = FileModeXAttrs(&perms);
         format!("{perm_xattr}")
     } else {
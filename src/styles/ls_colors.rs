@@ -0,0 +1,39 @@
+/// Indicators whose values are raw escape sequences rather than SGR parameters.
+const RAW_INDICATORS: [&str; 4] = ["lc", "rc", "ec", "cl"];
+
+/// Splits `input` into its well-formed entries, rejoined so they can be handed to `lscolors`, and
+/// those that are malformed. Empty entries such as those left by a trailing `:` are dropped.
+pub fn sanitize(input: &str) -> (String, Vec<&str>) {
+    let (valid, malformed): (Vec<_>, Vec<_>) = input
+        .split(':')
+        .filter(|entry| !entry.is_empty())
+        .partition(|entry| is_well_formed(entry));
+
+    (valid.join(":"), malformed)
+}
+
+/// An entry is well-formed if it is of the form `key=value` where `value` is a `;` separated list
+/// of SGR parameters, e.g. `di=01;34` or `*.rs=38;5;208`.
+fn is_well_formed(entry: &str) -> bool {
+    let Some((key, value)) = entry.split_once('=') else {
+        return false;
+    };
+
+    if key.is_empty() || key == "*" || value.contains('=') {
+        return false;
+    }
+
+    RAW_INDICATORS.contains(&key)
+        || value
+            .split(';')
+            .all(|param| param.is_empty() || param.parse::<u8>().is_ok())
+}
+
+#[test]
+fn sanitize_ls_colors() {
+    let (valid, malformed) =
+        sanitize("di=01;34:ln=01;3x:lc=\x1b[:*.rs=38;5;208:ex:=32:*=1:or=1=2:");
+
+    assert_eq!(valid, "di=01;34:lc=\x1b[:*.rs=38;5;208");
+    assert_eq!(malformed, ["ln=01;3x", "ex", "=32", "*=1", "or=1=2"]);
+}
@@ -2,11 +2,14 @@ use crate::hash;
 use ansi_term::{Color, Style};
 use error::Error;
 use lscolors::LsColors;
-use std::{collections::HashMap, sync::OnceLock};
+use std::{collections::HashMap, env, sync::OnceLock};
 
 /// Errors for this module.
 pub mod error;
 
+/// Tolerant parsing of `LS_COLORS`.
+mod ls_colors;
+
 /// Used as general placeholder for an empty field.
 pub const PLACEHOLDER: &str = "-";
 
@@ -53,6 +56,19 @@ static PERMISSIONS_THEME: OnceLock<HashMap<char, Style>> = OnceLock::new();
 #[cfg(unix)]
 static OCTAL_PERMISSIONS_STYLE: OnceLock<Style> = OnceLock::new();
 
+/// Runtime evaluated static that contains the style of rows summarizing collapsed entries.
+static COLLAPSED_STYLE: OnceLock<Style> = OnceLock::new();
+
+/// Runtime evaluated static that contains the background style of shaded rows.
+static STRIPE_STYLE: OnceLock<Style> = OnceLock::new();
+
+/// Runtime evaluated static that contains the styles of small, large, and very large sizes when
+/// colored by magnitude.
+static SIZE_GRADIENT: OnceLock<[Style; 3]> = OnceLock::new();
+
+/// Runtime evaluated static that contains the style of entries that exceed '--warn-size'.
+static WARNING_STYLE: OnceLock<Style> = OnceLock::new();
+
 /// Runtime evaluated static that contains style for the general use placeholder "-".
 static PLACEHOLDER_STYLE: OnceLock<Style> = OnceLock::new();
 
@@ -80,15 +96,16 @@ static DATETIME_STYLE: OnceLock<Style> = OnceLock::new();
 pub type ThemesMap = HashMap<&'static str, String>;
 
 /// Initializes both [`LS_COLORS`] and all themes. If `plain` argument is `true` then plain colorless
-/// themes are used and [`LS_COLORS`] won't be initialized.
-pub fn init(plain: bool) {
+/// themes are used and [`LS_COLORS`] won't be initialized. If `verbose` is `true` then malformed
+/// entries of `LS_COLORS` are reported on stderr.
+pub fn init(plain: bool, verbose: bool) {
     #[cfg(windows)]
     let _ = ansi_term::enable_ansi_support();
 
     if plain {
         init_plain();
     } else {
-        init_ls_colors();
+        init_ls_colors(verbose);
         init_themes();
     }
 }
@@ -105,6 +122,14 @@ pub fn get_du_theme() -> Result<&'static HashMap<&'static str, Style>, Error<'st
     DU_THEME.get().ok_or(Error::Uninitialized("DU_THEME"))
 }
 
+/// Getter for [`SIZE_GRADIENT`]. Returns an error if not initialized.
+#[inline]
+pub fn get_size_gradient() -> Result<&'static [Style; 3], Error<'static>> {
+    SIZE_GRADIENT
+        .get()
+        .ok_or(Error::Uninitialized("SIZE_GRADIENT"))
+}
+
 /// Getter for [`TREE_THEME`]. Returns an error if not initialized.
 #[inline]
 pub fn get_tree_theme() -> Result<&'static ThemesMap, Error<'static>> {
@@ -143,6 +168,30 @@ pub fn get_placeholder_style() -> Result<&'static Style, Error<'static>> {
         .ok_or(Error::Uninitialized("PLACEHOLDER_STYLE"))
 }
 
+/// Getter for [`WARNING_STYLE`]. Returns an error if not initialized.
+#[inline]
+pub fn get_warning_style() -> Result<&'static Style, Error<'static>> {
+    WARNING_STYLE
+        .get()
+        .ok_or(Error::Uninitialized("WARNING_STYLE"))
+}
+
+/// Getter for [`COLLAPSED_STYLE`]. Returns an error if not initialized.
+#[inline]
+pub fn get_collapsed_style() -> Result<&'static Style, Error<'static>> {
+    COLLAPSED_STYLE
+        .get()
+        .ok_or(Error::Uninitialized("COLLAPSED_STYLE"))
+}
+
+/// Getter for [`STRIPE_STYLE`]. Returns an error if not initialized.
+#[inline]
+pub fn get_stripe_style() -> Result<&'static Style, Error<'static>> {
+    STRIPE_STYLE
+        .get()
+        .ok_or(Error::Uninitialized("STRIPE_STYLE"))
+}
+
 /// Getter for [`INO_STYLE`]. Returns an error if not initialized.
 #[cfg(unix)]
 #[inline]
@@ -181,11 +230,25 @@ pub fn get_datetime_style() -> Result<&'static Style, Error<'static>> {
 }
 
 /// Initializes [`LS_COLORS`] by reading in the `LS_COLORS` environment variable. If it isn't set, a
-/// default determined by `lscolors` crate will be used.
-fn init_ls_colors() {
-    LS_COLORS
-        .set(LsColors::from_env().unwrap_or_default())
-        .unwrap();
+/// default determined by `lscolors` crate will be used. Malformed entries are skipped rather than
+/// allowed to clobber the defaults, and are reported on stderr if `verbose` is `true`.
+fn init_ls_colors(verbose: bool) {
+    let ls_colors = env::var("LS_COLORS").map_or_else(
+        |_| LsColors::default(),
+        |input| {
+            let (valid, malformed) = ls_colors::sanitize(&input);
+
+            if verbose {
+                for entry in malformed {
+                    eprintln!("Skipping malformed LS_COLORS entry '{entry}'");
+                }
+            }
+
+            LsColors::from_string(&valid)
+        },
+    );
+
+    LS_COLORS.set(ls_colors).unwrap();
 }
 
 /// Colorless themes
@@ -218,7 +281,7 @@ fn init_themes_for_long_view() {
         'r' => Color::Green.bold(),
         'w' => Color::Yellow.bold(),
         'x' | 's' | 'S' | 't' | 'T' => Color::Red.bold(),
-        '@' => Color::Cyan.bold(),
+        '@' | '+' => Color::Cyan.bold(),
         ' ' => Color::White.normal()
     };
     PERMISSIONS_THEME.set(permissions_theme).unwrap();
@@ -271,9 +334,21 @@ fn init_themes() {
     };
     DU_THEME.set(du_theme).unwrap();
 
+    let size_gradient = [Color::Green.bold(), Color::Yellow.bold(), Color::Red.bold()];
+    SIZE_GRADIENT.set(size_gradient).unwrap();
+
+    let warning_style = Color::Red.bold();
+    WARNING_STYLE.set(warning_style).unwrap();
+
     let placeholder_style = Color::Purple.normal();
     PLACEHOLDER_STYLE.set(placeholder_style).unwrap();
 
+    let collapsed_style = Color::Fixed(244).italic();
+    COLLAPSED_STYLE.set(collapsed_style).unwrap();
+
+    let stripe_style = Style::new().on(Color::Fixed(236));
+    STRIPE_STYLE.set(stripe_style).unwrap();
+
     #[cfg(unix)]
     init_themes_for_long_view();
 }
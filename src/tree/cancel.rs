@@ -0,0 +1,21 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Handle used to request that an ongoing traversal stop early. Clones share the same underlying
+/// state so a [`Token`] can be handed off to other threads such as an interrupt handler.
+#[derive(Clone, Debug, Default)]
+pub struct Token(Arc<AtomicBool>);
+
+impl Token {
+    /// Signals to any traversal holding this [`Token`] that it should stop as soon as possible.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether or not [`Token::cancel`] has been called on this [`Token`] or any of its clones.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
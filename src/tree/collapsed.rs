@@ -0,0 +1,67 @@
+use super::node::Node;
+use crate::{
+    context::{layout, Context},
+    disk_usage::file_size::FileSize,
+};
+use std::fmt::{self, Display};
+
+/// Summary of the children of a directory that were dropped from the output because they fell
+/// outside of the limit imposed by `--top` or because the directory is an artifact hidden by
+/// `--no-artifacts`. The size of these children still counts towards the size of their parent.
+pub struct Collapsed {
+    count: usize,
+    file_size: Option<FileSize>,
+}
+
+impl Collapsed {
+    /// Summarizes the provided `nodes` which are about to be removed from the [`Tree`].
+    ///
+    /// [`Tree`]: super::Tree
+    pub fn new<'a>(nodes: impl Iterator<Item = &'a Node>, ctx: &Context) -> Self {
+        let mut count = 0;
+        let mut file_size = FileSize::from(ctx);
+
+        // Flat layouts list directories alongside their contents so only non-directories
+        // contribute to the size lest they get counted twice.
+        let flat = matches!(ctx.layout, layout::Type::Flat | layout::Type::Iflat);
+
+        for node in nodes {
+            count += 1;
+
+            if flat && node.is_dir() {
+                continue;
+            }
+
+            if let Some(size) = node.file_size() {
+                file_size += size;
+            }
+        }
+
+        Self {
+            count,
+            file_size: ctx.measures_sizes().then_some(file_size),
+        }
+    }
+
+    /// The amount of entries that were collapsed.
+    pub const fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The aggregate size of all collapsed entries.
+    pub const fn file_size(&self) -> Option<&FileSize> {
+        self.file_size.as_ref()
+    }
+}
+
+impl Display for Collapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\u{2026} {} more", self.count())?;
+
+        if let Some(file_size) = self.file_size() {
+            write!(f, " ({file_size})")?;
+        }
+
+        Ok(())
+    }
+}
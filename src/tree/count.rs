@@ -1,4 +1,5 @@
 use super::Node;
+use crate::disk_usage::{file_size::byte::Metric, units::PrefixKind};
 use std::{
     convert::From,
     fmt::{self, Display},
@@ -12,17 +13,40 @@ pub struct FileCount {
     pub num_dirs: usize,
     pub num_files: usize,
     pub num_links: usize,
+    pub num_placeholders: usize,
+    pub num_vanished: usize,
+
+    /// Logical size of every placeholder, i.e. the data that's only available from the cloud.
+    pub placeholder_bytes: u64,
+
+    /// Units with which to report `placeholder_bytes`.
+    pub prefix_kind: PrefixKind,
+}
+
+impl FileCount {
+    /// Report `placeholder_bytes` with the units of `prefix_kind`.
+    pub const fn with_prefix_kind(mut self, prefix_kind: PrefixKind) -> Self {
+        self.prefix_kind = prefix_kind;
+        self
+    }
 }
 
 impl AddAssign<&Node> for FileCount {
     /// Update [Self] with information from [Node].
     fn add_assign(&mut self, rhs: &Node) {
-        if rhs.is_dir() {
+        if rhs.is_vanished() {
+            self.num_vanished += 1;
+        } else if rhs.is_dir() {
             self.num_dirs += 1;
         } else if rhs.is_symlink() {
             self.num_links += 1;
         } else {
             self.num_files += 1;
+
+            if let Some(bytes) = rhs.placeholder_bytes() {
+                self.num_placeholders += 1;
+                self.placeholder_bytes += bytes;
+            }
         }
     }
 }
@@ -30,7 +54,12 @@ impl Add<&Node> for FileCount {
     type Output = Self;
     /// Update [Self] with information from [Node].
     fn add(self, rhs: &Node) -> Self::Output {
-        if rhs.is_dir() {
+        if rhs.is_vanished() {
+            Self {
+                num_vanished: self.num_vanished + 1,
+                ..self
+            }
+        } else if rhs.is_dir() {
             Self {
                 num_dirs: self.num_dirs + 1,
                 ..self
@@ -41,8 +70,12 @@ impl Add<&Node> for FileCount {
                 ..self
             }
         } else {
+            let placeholder_bytes = rhs.placeholder_bytes();
+
             Self {
                 num_files: self.num_files + 1,
+                num_placeholders: self.num_placeholders + usize::from(placeholder_bytes.is_some()),
+                placeholder_bytes: self.placeholder_bytes + placeholder_bytes.unwrap_or_default(),
                 ..self
             }
         }
@@ -55,6 +88,9 @@ impl AddAssign for FileCount {
         self.num_dirs += rhs.num_dirs;
         self.num_links += rhs.num_links;
         self.num_files += rhs.num_files;
+        self.num_placeholders += rhs.num_placeholders;
+        self.num_vanished += rhs.num_vanished;
+        self.placeholder_bytes += rhs.placeholder_bytes;
     }
 }
 impl Add for FileCount {
@@ -65,6 +101,10 @@ impl Add for FileCount {
             num_dirs: self.num_dirs + rhs.num_dirs,
             num_links: self.num_links + rhs.num_links,
             num_files: self.num_files + rhs.num_files,
+            num_placeholders: self.num_placeholders + rhs.num_placeholders,
+            num_vanished: self.num_vanished + rhs.num_vanished,
+            placeholder_bytes: self.placeholder_bytes + rhs.placeholder_bytes,
+            prefix_kind: self.prefix_kind,
         }
     }
 }
@@ -114,6 +154,47 @@ impl Display for FileCount {
             components.push(output);
         }
 
+        if self.num_placeholders > 0 {
+            let mut remote = Metric::init_empty_logical(true, self.prefix_kind);
+            remote.value = self.placeholder_bytes;
+
+            components.push(format!(
+                "{} cloud-only ({remote} not stored locally)",
+                self.num_placeholders
+            ));
+        }
+
+        if self.num_vanished > 0 {
+            components.push(format!("{} vanished", self.num_vanished));
+        }
+
         write!(f, "{}", components.join(", "))
     }
 }
+
+#[test]
+fn display_placeholders() {
+    let file_count = FileCount {
+        num_files: 3,
+        num_placeholders: 2,
+        placeholder_bytes: 3 * 1024,
+        ..FileCount::default()
+    };
+
+    assert_eq!(
+        file_count.to_string(),
+        "3 files, 2 cloud-only (3.0 KiB not stored locally)"
+    );
+}
+
+#[test]
+fn display_vanished() {
+    let file_count = FileCount {
+        num_dirs: 1,
+        num_files: 2,
+        num_vanished: 3,
+        ..FileCount::default()
+    };
+
+    assert_eq!(file_count.to_string(), "1 directory, 2 files, 3 vanished");
+}
@@ -1,4 +1,7 @@
-use crate::{context::error::Error as CtxError, styles::error::Error as StyleError};
+use crate::{
+    context::error::Error as CtxError, fs::atomic::Error as AtomicError,
+    styles::error::Error as StyleError,
+};
 use ignore::Error as IgnoreError;
 use std::io::Error as IoError;
 
@@ -16,18 +19,28 @@ pub enum Error {
     #[error("{0}")]
     DirNotFound(String),
 
+    #[error("Failed to load exclusion patterns: {0}")]
+    ExcludeFrom(IgnoreError),
+
     #[error("File expected to have parent")]
     ExpectedParent,
 
     #[error("Invalid glob patterns: {0}")]
     InvalidGlobPatterns(#[from] IgnoreError),
 
+    #[cfg(unix)]
+    #[error("Metadata was not collected for this entry.")]
+    MissingMetadata,
+
     #[error("Failed to compute root node.")]
     MissingRoot,
 
     #[error("No entries to show with given arguments.")]
     NoMatches,
 
+    #[error("{0}")]
+    Output(#[from] AtomicError),
+
     #[error("{0}")]
     PathCanonicalization(#[from] IoError),
 
@@ -35,9 +48,15 @@ pub enum Error {
     #[error("{0}")]
     Permissions(#[from] PermissionsError),
 
+    #[error("Failed to write records: {0}")]
+    Records(IoError),
+
     #[error("{0}")]
     UninitializedTheme(#[from] StyleError<'static>),
 
+    #[error("{0} {1} exceeded the size given to '--warn-size'")]
+    SizeExceeded(usize, &'static str),
+
     #[error("Terminated erdtree...")]
     Terminated,
 }
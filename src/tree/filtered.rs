@@ -0,0 +1,235 @@
+use crate::fs::inode::Inode;
+use ignore::DirEntry;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Display},
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// The rule by which an entry was filtered out during traversal.
+#[derive(Clone, Copy, Debug)]
+pub enum Rule {
+    /// Hidden files, i.e. dot-files, which are shown with '--hidden'.
+    Hidden,
+
+    /// Entries matched by `.gitignore`, `.ignore`, '--exclude-from', and the like.
+    Ignored,
+
+    /// Entries that don't match '--pattern'.
+    Unmatched,
+
+    /// Entries rejected by any other filter such as '--ext' or '--mime'.
+    Other,
+}
+
+/// Tallies of the entries that were filtered out of a single directory by each [Rule].
+#[derive(Default)]
+struct Counts {
+    hidden: usize,
+    ignored: usize,
+    unmatched: usize,
+    other: usize,
+}
+
+/// Per-directory tallies of entries that were filtered out during traversal, reported by
+/// '--verbose' to explain entries that are unexpectedly missing from the output.
+pub struct Stats {
+    root: PathBuf,
+    dirs: Mutex<BTreeMap<PathBuf, Counts>>,
+
+    /// Directories that the walker reads along with how many of their entries made it past its
+    /// hidden and ignore rules, such that the rest were skipped by them.
+    read: Mutex<HashMap<PathBuf, usize>>,
+}
+
+impl Stats {
+    /// Initializes empty [Stats] for a traversal of `root`.
+    pub fn new(root: PathBuf) -> Self {
+        let read = HashMap::from([(root.clone(), 0)]);
+
+        Self {
+            root,
+            dirs: Mutex::new(BTreeMap::new()),
+            read: Mutex::new(read),
+        }
+    }
+
+    /// Tallies `dir_entry` against its parent directory as having been filtered out by `rule`.
+    pub fn record(&self, dir_entry: &DirEntry, rule: Rule) {
+        if let Some(parent) = dir_entry.path().parent() {
+            self.add(parent, rule, 1);
+        }
+    }
+
+    /// Notes that `dir_entry` made it past the hidden and ignore rules of the walker.
+    pub fn reached(&self, dir_entry: &DirEntry) {
+        let Some(parent) = dir_entry.path().parent() else {
+            return;
+        };
+
+        if let Some(reached) = self.read.lock().unwrap().get_mut(parent) {
+            *reached += 1;
+        }
+    }
+
+    /// Notes that the walker is going to read the entries of `dir`.
+    pub fn will_read(&self, dir: &Path) {
+        self.read.lock().unwrap().insert(dir.to_path_buf(), 0);
+    }
+
+    /// Tallies the entries of every directory that was read which never made it past the hidden
+    /// and ignore rules of the walker. Those that are hidden are attributed to [`Rule::Hidden`]
+    /// if `hidden` and the rest to [`Rule::Ignored`]. Directories on other file systems aren't
+    /// read by the walker if `same_fs`.
+    pub fn tally_skipped(&self, hidden: bool, same_fs: bool) {
+        let read = std::mem::take(&mut *self.read.lock().unwrap());
+
+        let device = |path: &Path| {
+            fs::metadata(path)
+                .ok()
+                .and_then(|md| Inode::try_from(&md).ok())
+                .map(|inode| inode.dev)
+        };
+
+        let root_device = same_fs.then(|| device(&self.root)).flatten();
+
+        for (dir, reached) in read {
+            if root_device.is_some() && device(&dir) != root_device {
+                continue;
+            }
+
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+
+            let (mut total, mut hidden_total) = (0_usize, 0_usize);
+
+            for entry in entries.flatten() {
+                total += 1;
+                hidden_total += usize::from(hidden && is_hidden(&entry));
+            }
+
+            let skipped = total.saturating_sub(reached);
+            let skipped_hidden = hidden_total.min(skipped);
+
+            self.add(&dir, Rule::Hidden, skipped_hidden);
+            self.add(&dir, Rule::Ignored, skipped - skipped_hidden);
+        }
+    }
+
+    /// Tallies `count` entries of `dir` as having been filtered out by `rule`.
+    fn add(&self, dir: &Path, rule: Rule, count: usize) {
+        if count == 0 {
+            return;
+        }
+
+        let mut dirs = self.dirs.lock().unwrap();
+        let counts = dirs.entry(dir.to_path_buf()).or_default();
+
+        match rule {
+            Rule::Hidden => counts.hidden += count,
+            Rule::Ignored => counts.ignored += count,
+            Rule::Unmatched => counts.unmatched += count,
+            Rule::Other => counts.other += count,
+        }
+
+        drop(dirs);
+    }
+}
+
+/// Whether or not `dir_entry` would be skipped by the hidden file rules of [`ignore::WalkBuilder`].
+fn is_hidden(dir_entry: &fs::DirEntry) -> bool {
+    if dir_entry.file_name().to_string_lossy().starts_with('.') {
+        return true;
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+
+        /// Set in the attributes of files that are hidden.
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+
+        if let Ok(md) = dir_entry.metadata() {
+            return md.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0;
+        }
+    }
+
+    false
+}
+
+impl Display for Counts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tallies = [
+            (self.hidden, "hidden"),
+            (self.ignored, "ignored"),
+            (self.unmatched, "unmatched"),
+            (self.other, "other"),
+        ];
+
+        let out = tallies
+            .iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, rule)| format!("{count} {rule}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        write!(f, "{out}")
+    }
+}
+
+impl Display for Stats {
+    /// One line per directory that had entries filtered out, relative to the root of traversal.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dirs = self.dirs.lock().unwrap();
+
+        for (dir, counts) in dirs.iter() {
+            let dir = dir
+                .strip_prefix(&self.root)
+                .ok()
+                .filter(|rel| !rel.as_os_str().is_empty())
+                .unwrap_or_else(|| Path::new("."));
+
+            writeln!(f, "{}: {counts}", dir.display())?;
+        }
+
+        drop(dirs);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn display_stats() {
+    let root = PathBuf::from("/data");
+
+    let stats = Stats::new(root.clone());
+
+    {
+        let mut dirs = stats.dirs.lock().unwrap();
+
+        dirs.insert(
+            root.clone(),
+            Counts {
+                hidden: 2,
+                ignored: 1,
+                ..Counts::default()
+            },
+        );
+
+        dirs.insert(
+            root.join("lipsum"),
+            Counts {
+                unmatched: 3,
+                ..Counts::default()
+            },
+        );
+    }
+
+    assert_eq!(
+        stats.to_string(),
+        ".: 2 hidden, 1 ignored\nlipsum: 3 unmatched\n"
+    );
+}
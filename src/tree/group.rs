@@ -0,0 +1,84 @@
+use super::{collapsed::Collapsed, node::Node, Tree};
+use crate::context::{group::Key, Context};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    path::Path,
+};
+
+/// Identifies the section of a flat layout that a [Node] belongs to when using '--group-by'.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Label {
+    /// Entries at a given depth which are ordered numerically rather than lexicographically.
+    Depth(usize),
+
+    /// Directories when grouping by extension. They're set apart from files without extensions
+    /// and go without a subtotal as their sizes are already accounted for by their contents.
+    Directories,
+
+    /// Entries that share an owner, extension, or parent directory.
+    Text(Key, String),
+}
+
+impl Label {
+    /// Determines the [Label] of `node` where `root` is the canonical path of the root directory.
+    pub fn of(node: &Node, key: Key, root: &Path) -> Self {
+        let text = match key {
+            Key::Depth => return Self::Depth(node.depth()),
+
+            #[cfg(unix)]
+            Key::Owner => node.owner().unwrap_or("-").to_owned(),
+
+            Key::Ext if node.is_dir() => return Self::Directories,
+
+            Key::Ext => Path::new(node.file_name()).extension().map_or_else(
+                || String::from("-"),
+                |ext| ext.to_string_lossy().into_owned(),
+            ),
+
+            Key::Parent => node
+                .parent_path()
+                .and_then(|parent| parent.strip_prefix(root).ok())
+                .filter(|parent| !parent.as_os_str().is_empty())
+                .map_or_else(|| String::from("."), |parent| parent.display().to_string()),
+        };
+
+        Self::Text(key, text)
+    }
+}
+
+impl Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Depth(depth) => write!(f, "depth: {depth}"),
+            Self::Directories => write!(f, "directories"),
+
+            #[cfg(unix)]
+            Self::Text(Key::Owner, owner) => write!(f, "owner: {owner}"),
+
+            Self::Text(Key::Ext, ext) => write!(f, "ext: {ext}"),
+            Self::Text(_, parent) => write!(f, "parent: {parent}"),
+        }
+    }
+}
+
+/// Aggregates every entry beneath the root of `tree` by its [Label], reusing [Collapsed] so that
+/// directories don't get counted twice alongside their contents.
+pub fn subtotals(tree: &Tree, key: Key, root: &Path, ctx: &Context) -> HashMap<Label, Collapsed> {
+    let arena = tree.arena();
+    let mut members = HashMap::<Label, Vec<&Node>>::new();
+
+    for node_id in tree.root_id().descendants(arena).skip(1) {
+        let node = arena[node_id].get();
+
+        members
+            .entry(Label::of(node, key, root))
+            .or_default()
+            .push(node);
+    }
+
+    members
+        .into_iter()
+        .map(|(label, nodes)| (label, Collapsed::new(nodes.into_iter(), ctx)))
+        .collect()
+}
@@ -0,0 +1,222 @@
+use super::{count::FileCount, node::Node};
+use crate::{
+    context::{time, Context},
+    disk_usage::file_size::FileSize,
+    fs::{atomic::AtomicFile, inode::Inode},
+};
+use chrono::SecondsFormat;
+use indextree::{Arena, NodeId};
+use serde::Serialize;
+use std::{
+    borrow::Cow,
+    io::{self, BufWriter, Stdout, Write},
+    path::{Path, PathBuf},
+};
+
+/// The record of a single entry.
+#[derive(Serialize)]
+struct Record<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    id: String,
+    path: Cow<'a, str>,
+    name: Cow<'a, str>,
+    depth: usize,
+    size: Option<u64>,
+    mtime: Option<String>,
+}
+
+/// The record that follows all others, summarizing the whole of the output.
+#[derive(Serialize)]
+struct Summary {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    dirs: usize,
+    files: usize,
+    links: usize,
+    size: Option<u64>,
+}
+
+/// Where records are written to.
+enum Sink {
+    Stdout(BufWriter<Stdout>),
+    File(AtomicFile),
+}
+
+/// Writes the records of '--output jsonl' while the [`Tree`] is being assembled rather than once
+/// it's complete. The entries of a directory are written, in the order of '--sort', as soon as the
+/// directory is totaled, and a directory's own record comes with those of its siblings. The root's
+/// record comes last followed by a record summarizing the whole of the output.
+///
+/// [`Tree`]: super::Tree
+pub struct Stream {
+    sink: Sink,
+    root_path: PathBuf,
+    level: usize,
+    tz: Option<time::Zone>,
+    file_count: FileCount,
+    error: Option<io::Error>,
+}
+
+impl Stream {
+    /// Writes to '--output-file' if provided, otherwise to stdout. Records written to a file only
+    /// take its place once the stream is finished.
+    pub fn init(ctx: &Context) -> Result<Self, crate::fs::atomic::Error> {
+        let sink = match ctx.output_file {
+            Some(ref path) => Sink::File(AtomicFile::create(path)?),
+            None => Sink::Stdout(BufWriter::new(io::stdout())),
+        };
+
+        Ok(Self {
+            sink,
+            root_path: ctx.dir_canonical(),
+            level: ctx.level(),
+            tz: ctx.timezone(),
+            file_count: FileCount::default(),
+            error: None,
+        })
+    }
+
+    /// Writes a record for each of `children` of a directory that was just totaled, provided they
+    /// are within '--level'. The first failure to write is held on to until [`Stream::finish`].
+    pub fn directory(&mut self, arena: &Arena<Node>, children: &[NodeId]) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let mut written = Ok(());
+
+        for &child_id in children {
+            let node = arena[child_id].get();
+
+            if node.depth() > self.level {
+                continue;
+            }
+
+            self.file_count += node;
+
+            written = write_record(&mut self.sink, &record(node, &self.root_path, self.tz));
+
+            if written.is_err() {
+                break;
+            }
+        }
+
+        if let Err(e) = written.and_then(|()| self.sink.flush()) {
+            self.error = Some(e);
+        }
+    }
+
+    /// Writes the record of `root` followed by the summary, committing the records to
+    /// '--output-file' if one was provided.
+    pub fn finish(mut self, root: &Node) -> Result<(), super::error::Error> {
+        if let Some(e) = self.error.take() {
+            return Err(super::error::Error::Records(e));
+        }
+
+        let file_count = &self.file_count;
+
+        let summary = Summary {
+            kind: "summary",
+            dirs: file_count.num_dirs,
+            files: file_count.num_files,
+            links: file_count.num_links,
+            size: size(root),
+        };
+
+        write_record(&mut self.sink, &record(root, &self.root_path, self.tz))
+            .and_then(|()| write_record(&mut self.sink, &summary))
+            .map_err(super::error::Error::Records)?;
+
+        match self.sink {
+            Sink::Stdout(mut stdout) => stdout.flush().map_err(super::error::Error::Records),
+            Sink::File(file) => Ok(file.commit()?),
+        }
+    }
+}
+
+impl Write for Sink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Stdout(stdout) => stdout.write(buf),
+            Self::File(file) => file.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Stdout(stdout) => stdout.flush(),
+            Self::File(file) => file.flush(),
+        }
+    }
+}
+
+/// Writes `record` to `sink` as a single line of JSON.
+fn write_record(sink: &mut Sink, record: &impl Serialize) -> io::Result<()> {
+    serde_json::to_writer(&mut *sink, record)?;
+    writeln!(sink)
+}
+
+/// A single entry whose path is relative to that of the root and whose time of last modification
+/// is in the time zone `tz`.
+fn record<'a>(node: &'a Node, root_path: &Path, tz: Option<time::Zone>) -> Record<'a> {
+    let kind = if node.is_dir() {
+        "dir"
+    } else if node.is_symlink() {
+        "link"
+    } else {
+        "file"
+    };
+
+    let path = node
+        .path()
+        .strip_prefix(root_path)
+        .ok()
+        .filter(|rel| !rel.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+
+    Record {
+        kind,
+        id: id(node, path),
+        path: path.to_string_lossy(),
+        name: node.file_name().to_string_lossy(),
+        depth: node.depth(),
+        size: size(node),
+        mtime: mtime(node, tz),
+    }
+}
+
+/// Identifier of `node` that stays the same across runs so that records can be correlated, which
+/// is the device and inode number if available and otherwise a hash of `rel_path`. Hard links
+/// to files share the former so the hash is added to tell them apart.
+fn id(node: &Node, rel_path: &Path) -> String {
+    match node.inode() {
+        Some(Inode { dev, ino, nlink }) if nlink > 1 && !node.is_dir() => {
+            format!("{dev}:{ino}:{:016x}", path_hash(rel_path))
+        },
+        Some(Inode { dev, ino, .. }) => format!("{dev}:{ino}"),
+        None => format!("path:{:016x}", path_hash(rel_path)),
+    }
+}
+
+/// FNV-1a hash of `rel_path`.
+fn path_hash(rel_path: &Path) -> u64 {
+    rel_path
+        .to_string_lossy()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+/// Size of `node` which is `None` if it wasn't measured.
+fn size(node: &Node) -> Option<u64> {
+    node.file_size().map(FileSize::value)
+}
+
+/// Time of last modification of `node` in the time zone `tz` in RFC 3339 format, which is `None`
+/// if it couldn't be read.
+fn mtime(node: &Node, tz: Option<time::Zone>) -> Option<String> {
+    node.modified()
+        .map(|time| time::localize(time, tz).to_rfc3339_opts(SecondsFormat::Secs, true))
+}
@@ -1,62 +1,152 @@
 use crate::{
-    context::{column, layout, Context},
+    context::{column, layout, output, percent, sort, Context, Filter},
     disk_usage::file_size::FileSize,
     fs::inode::Inode,
     progress::{IndicatorHandle, Message},
     utils,
 };
 
+use cancel::Token;
+use collapsed::Collapsed;
 use count::FileCount;
 use error::Error;
-use ignore::{WalkBuilder, WalkParallel};
+use ignore::{gitignore::GitignoreBuilder, WalkBuilder, WalkParallel, WalkState};
 use indextree::{Arena, NodeId};
+use jsonl::Stream;
 use node::{cmp::NodeComparator, Node};
 use std::{
-    collections::{HashMap, HashSet},
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
     convert::TryFrom,
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
     result::Result as StdResult,
-    sync::mpsc::{self, Sender},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::{self, Sender, SyncSender},
+        Arc,
+    },
     thread,
 };
 use visitor::{BranchVisitorBuilder, TraversalState};
 
+/// Cooperative cancellation of an ongoing traversal.
+pub mod cancel;
+
+/// Summarizing directory entries that were omitted from the output.
+pub mod collapsed;
+
 /// Operations to handle and display aggregate file counts based on their type.
 pub mod count;
 
 /// Errors related to traversal, [Tree] construction, and the like.
 pub mod error;
 
+/// Tallies of entries that were filtered out during traversal.
+pub mod filtered;
+
+/// Partitioning of flat layouts into sections with '--group-by'.
+pub mod group;
+
+/// Records of '--output jsonl' that are written while the [`Tree`] is assembled.
+pub mod jsonl;
+
 /// Contains components of the [`Tree`] data structure that derive from [`ignore::DirEntry`].
 pub mod node;
 
+/// Disk usage per derivation of the Nix store for '--nix'.
+#[cfg(feature = "nix")]
+pub mod nix;
+
+/// Grand totals of everything that was traversed.
+pub mod report;
+
+/// Disk usage per XDG category for '--xdg'.
+pub mod xdg;
+
 /// Custom visitor that operates on each thread during filesystem traversal.
 mod visitor;
 
+/// Storage that's already been counted towards a directory's size during [`Tree`] assembly so that
+/// it isn't counted twice.
+#[derive(Default)]
+struct Accounted {
+    /// Hard-linked files.
+    inodes: HashSet<Inode>,
+
+    /// Shared extents keyed by device and physical offset.
+    #[cfg(target_os = "linux")]
+    extents: HashSet<(u64, u64)>,
+}
+
 /// Virtual data structure that represents local file-system hierarchy.
 pub struct Tree {
     arena: Arena<Node>,
     root_id: NodeId,
+    errors: usize,
+    filtered: Option<filtered::Stats>,
+    scanned: FileCount,
 }
 
 pub type Result<T> = StdResult<T, Error>;
 
 impl Tree {
     /// Constructor for [Tree].
-    pub const fn new(arena: Arena<Node>, root_id: NodeId) -> Self {
-        Self { arena, root_id }
+    pub const fn new(
+        arena: Arena<Node>,
+        root_id: NodeId,
+        errors: usize,
+        filtered: Option<filtered::Stats>,
+        scanned: FileCount,
+    ) -> Self {
+        Self {
+            arena,
+            root_id,
+            errors,
+            filtered,
+            scanned,
+        }
     }
 
     /// Initiates file-system traversal and [Tree] as well as updates the [Context] object with
-    /// various properties necessary to render output.
-    pub fn try_init(
+    /// various properties necessary to render output. If `cancel` is triggered while traversal is
+    /// ongoing then traversal stops early and the [Tree] is assembled from whatever entries were
+    /// read up until that point.
+    pub fn build_with_cancel(
         mut ctx: Context,
         indicator: Option<&IndicatorHandle>,
+        cancel: &Token,
     ) -> Result<(Self, Context)> {
         let mut column_properties = column::Properties::from(&ctx);
 
-        let (mut arena, root_id) = Self::traverse(&ctx, &mut column_properties, indicator)?;
+        let filtered = ctx
+            .verbose
+            .then(|| Arc::new(filtered::Stats::new(ctx.dir_canonical())));
+
+        let stream = (ctx.output == output::Format::Jsonl)
+            .then(|| Stream::init(&ctx))
+            .transpose()?;
+
+        let (mut arena, root_id, errors, scanned) = Self::traverse(
+            &ctx,
+            &mut column_properties,
+            indicator,
+            cancel,
+            filtered.clone(),
+            stream,
+        )?;
+
+        // The walker, which held the only other reference to the tallies, is gone by now.
+        let filtered = filtered.and_then(Arc::into_inner);
+
+        if let Some(ref stats) = filtered {
+            stats.tally_skipped(!ctx.hidden, ctx.same_fs);
+        }
+
+        // Shares are computed before flat layouts detach nodes from their parents.
+        if let Some(basis) = ctx.percent {
+            Self::compute_shares(&mut arena, root_id, basis);
+        }
 
         match ctx.layout {
             layout::Type::Flat | layout::Type::Iflat => {
@@ -64,7 +154,11 @@ impl Tree {
                 for child in root_id.children(&arena).into_iter() {
                     nodes.push(child)
                 }
-                let node_comparator = node::cmp::comparator(&ctx);
+                let node_comparator = if ctx.sort_by.is_empty() {
+                    node::cmp::comparator(&ctx)
+                } else {
+                    node::cmp::column_comparator(&ctx.sort_by)
+                };
 
                 nodes.sort_by(|&id_a, &id_b| {
                     let node_a = arena.get(id_a).unwrap().get();
@@ -72,6 +166,14 @@ impl Tree {
                     node_comparator(node_a, node_b)
                 });
 
+                // Sections are kept contiguous while preserving the order within each.
+                if let Some(key) = ctx.group_by {
+                    let root = ctx.dir_canonical();
+                    nodes.sort_by_cached_key(|&id| group::Label::of(arena[id].get(), key, &root));
+                }
+
+                Self::collapse_children(&mut arena, root_id, &mut nodes, &ctx);
+
                 for node in nodes.iter() {
                     root_id.append(*node, &mut arena)
                 }
@@ -81,11 +183,13 @@ impl Tree {
 
         ctx.update_column_properties(&column_properties);
 
-        if ctx.truncate {
+        ctx.root_size = arena[root_id].get().file_size().map_or(0, FileSize::value);
+
+        if ctx.truncate || ctx.stripe.is_some() || ctx.aligned_sizes {
             ctx.set_window_width();
         }
 
-        let tree = Self::new(arena, root_id);
+        let tree = Self::new(arena, root_id, errors, filtered, scanned);
         if tree.is_stump() {
             return Err(Error::NoMatches);
         }
@@ -93,6 +197,37 @@ impl Tree {
         Ok((tree, ctx))
     }
 
+    /// Sets the share of each node's size relative to either its parent's or the root's total
+    /// size. Nodes without a size, or whose basis has no size, are left without a share.
+    fn compute_shares(tree: &mut Arena<Node>, root_id: NodeId, basis: percent::Basis) {
+        let size_of = |tree: &Arena<Node>, node_id: NodeId| {
+            tree[node_id].get().file_size().map(FileSize::value)
+        };
+
+        let root_size = size_of(tree, root_id);
+
+        let node_ids = root_id.descendants(tree).collect::<Vec<_>>();
+
+        for node_id in node_ids {
+            let total = match basis {
+                percent::Basis::Root => root_size,
+                percent::Basis::Parent => tree[node_id]
+                    .parent()
+                    .map_or(root_size, |parent_id| size_of(tree, parent_id)),
+            };
+
+            let (Some(size), Some(total)) = (size_of(tree, node_id), total) else {
+                continue;
+            };
+
+            if total > 0 {
+                tree[node_id]
+                    .get_mut()
+                    .set_share(size as f64 / total as f64);
+            }
+        }
+    }
+
     /// Returns `true` if there are no entries to show excluding the `root_id`.
     pub fn is_stump(&self) -> bool {
         self.root_id
@@ -113,6 +248,59 @@ impl Tree {
         &self.arena
     }
 
+    /// Returns the `n` largest files beneath the root in descending order of size. Only a bounded
+    /// heap of `n` entries is maintained while scanning the [Tree].
+    pub fn largest_files(&self, n: usize) -> Vec<NodeId> {
+        let mut heap = BinaryHeap::with_capacity(n + 1);
+
+        for node_id in self.root_id.descendants(&self.arena) {
+            let node = self.arena[node_id].get();
+
+            if node.is_dir() {
+                continue;
+            }
+
+            let Some(size) = node.file_size().map(FileSize::value) else {
+                continue;
+            };
+
+            heap.push(Reverse((size, node_id)));
+
+            if heap.len() > n {
+                heap.pop();
+            }
+        }
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((_, node_id))| node_id)
+            .collect()
+    }
+
+    /// The number of entries, directories included, whose size exceeds `threshold` bytes.
+    pub fn count_oversized(&self, threshold: u64) -> usize {
+        self.root_id
+            .descendants(&self.arena)
+            .filter(|&node_id| self.arena[node_id].get().exceeds(threshold))
+            .count()
+    }
+
+    /// The number of entries that couldn't be read during traversal.
+    pub const fn errors(&self) -> usize {
+        self.errors
+    }
+
+    /// Tallies of every entry beneath the root that traversal yielded, taken before anything is
+    /// collapsed or pruned. Only gathered if '--report' was used.
+    pub const fn scanned(&self) -> &FileCount {
+        &self.scanned
+    }
+
+    /// Tallies of entries that were filtered out during traversal if '--verbose' was used.
+    pub const fn filtered(&self) -> Option<&filtered::Stats> {
+        self.filtered.as_ref()
+    }
+
     /// Parallel traversal of the `root_id` directory and its contents. Parallel traversal relies on
     /// `WalkParallel`. Any filesystem I/O or related system calls are expected to occur during
     /// parallel traversal; post-processing post-processing of all directory entries should
@@ -121,19 +309,40 @@ impl Tree {
         ctx: &Context,
         column_properties: &mut column::Properties,
         indicator: Option<&IndicatorHandle>,
-    ) -> Result<(Arena<Node>, NodeId)> {
-        let walker = WalkParallel::try_from(ctx)?;
+        cancel: &Token,
+        filtered: Option<Arc<filtered::Stats>>,
+        stream: Option<Stream>,
+    ) -> Result<(Arena<Node>, NodeId, usize, FileCount)> {
+        let walker = Self::walker(ctx, filtered)?;
         let (tx, rx) = mpsc::channel();
 
         let progress_indicator_mailbox = indicator.map(IndicatorHandle::mailbox);
 
+        let counter = match indicator {
+            Some(handle) if ctx.eta => Some((WalkParallel::try_from(ctx)?, handle.mailbox())),
+            _ => None,
+        };
+
+        let indexed = AtomicBool::new(false);
+
         thread::scope(|s| {
             let res = s.spawn(move || {
                 let mut tree = Arena::new();
                 let mut branches: HashMap<PathBuf, Vec<NodeId>> = HashMap::new();
                 let mut root_id = None;
+                let mut errors = 0;
+                let mut scanned = FileCount::default();
+
+                loop {
+                    let node = match rx.recv() {
+                        Ok(TraversalState::Ongoing(node)) => node,
+                        Ok(TraversalState::Error) => {
+                            errors += 1;
+                            continue;
+                        },
+                        Ok(TraversalState::Done) | Err(_) => break,
+                    };
 
-                while let Ok(TraversalState::Ongoing(node)) = rx.recv() {
                     if let Some(ref mailbox) = progress_indicator_mailbox {
                         if mailbox.send(Message::Index).is_err() {
                             return Err(Error::Terminated);
@@ -148,6 +357,12 @@ impl Tree {
                         }
 
                         if node.depth() == 0 {
+                            let mut node = node;
+
+                            if let Some(ref name) = ctx.root_name {
+                                node.set_file_name(name.clone());
+                            }
+
                             root_id = Some(tree.new_node(node));
                             continue;
                         }
@@ -155,6 +370,10 @@ impl Tree {
 
                     let parent = node.parent_path().ok_or(Error::ExpectedParent)?.to_owned();
 
+                    if ctx.report {
+                        scanned += &node;
+                    }
+
                     let node_id = tree.new_node(node);
 
                     if branches
@@ -174,50 +393,125 @@ impl Tree {
 
                 let root_id = root_id.ok_or(Error::MissingRoot)?;
                 let node_comparator = node::cmp::comparator(ctx);
-                let mut inodes = HashSet::new();
+                let mut accounted = Accounted::default();
+                let mut stream = stream;
 
                 Self::assemble_tree(
                     &mut tree,
                     root_id,
                     &mut branches,
                     &node_comparator,
-                    &mut inodes,
+                    &mut accounted,
                     column_properties,
+                    &mut stream,
                     ctx,
                     root_id,
                 );
 
-                if ctx.prune || ctx.pattern.is_some() {
-                    Self::prune_directories(root_id, &mut tree);
+                // Records of an interrupted traversal are left incomplete.
+                if let Some(stream) = stream.filter(|_| !cancel.is_cancelled()) {
+                    stream.finish(tree[root_id].get())?;
                 }
 
-                if ctx.dirs_only {
-                    Self::filter_directories(root_id, &mut tree);
-                }
+                Self::reshape(root_id, &mut tree, &node_comparator, ctx);
 
-                Ok((tree, root_id))
+                Ok((tree, root_id, errors, scanned))
             });
 
-            let mut visitor_builder = BranchVisitorBuilder::new(ctx, Sender::clone(&tx));
+            if let Some((counter, mailbox)) = counter {
+                let indexed = &indexed;
+                s.spawn(move || Self::count_entries(counter, indexed, cancel, &mailbox));
+            }
+
+            let mut visitor_builder = BranchVisitorBuilder::new(ctx, Sender::clone(&tx), cancel);
 
             walker.visit(&mut visitor_builder);
 
+            indexed.store(true, Ordering::Relaxed);
+
             let _ = tx.send(TraversalState::Done);
 
             res.join().unwrap()
         })
     }
 
+    /// Removes and re-anchors nodes of the assembled [Tree] as the various filtering and layout
+    /// options call for.
+    fn reshape(
+        root_id: NodeId,
+        tree: &mut Arena<Node>,
+        node_comparator: &NodeComparator,
+        ctx: &Context,
+    ) {
+        let flat = matches!(ctx.layout, layout::Type::Flat | layout::Type::Iflat);
+
+        if flat && ctx.no_artifacts {
+            Self::remove_artifact_contents(root_id, tree, ctx);
+        }
+
+        if ctx.prune || ctx.filtered() {
+            Self::prune_directories(root_id, tree, ctx);
+        }
+
+        if ctx.dirs_only {
+            Self::filter_directories(root_id, tree);
+        }
+
+        if ctx.find.is_some() {
+            Self::retain_matches(root_id, tree, |node| ctx.find_matches(node.file_name()));
+        }
+
+        if ctx.min_depth() > 1 {
+            Self::anchor_min_depth(root_id, tree, ctx.min_depth(), node_comparator);
+        }
+
+        if ctx.leaf_only {
+            Self::anchor_leaves(root_id, tree, node_comparator);
+        }
+    }
+
+    /// Counts the entries that traversal is expected to index ahead of time so that the progress
+    /// indicator can estimate how much time remains. Counting stops early if traversal finishes
+    /// first as the estimate would no longer be of any use.
+    fn count_entries(
+        walker: WalkParallel,
+        indexed: &AtomicBool,
+        cancel: &Token,
+        mailbox: &SyncSender<Message>,
+    ) {
+        let count = AtomicU64::new(0);
+
+        walker.run(|| {
+            Box::new(|entry| {
+                if indexed.load(Ordering::Relaxed) || cancel.is_cancelled() {
+                    return WalkState::Quit;
+                }
+
+                if entry.is_ok() {
+                    count.fetch_add(1, Ordering::Relaxed);
+                }
+
+                WalkState::Continue
+            })
+        });
+
+        if !indexed.load(Ordering::Relaxed) && !cancel.is_cancelled() {
+            let _ = mailbox.send(Message::Total(count.into_inner()));
+        }
+    }
+
     /// Takes the results of the parallel traversal and uses it to construct the [Tree] data
     /// structure. Sorting occurs if specified. The amount of columns needed to fit all of the disk
-    /// usages is also computed here.
+    /// usages is also computed here, and records of '--output jsonl' are written for the children
+    /// of each directory once it's totaled.
     fn assemble_tree(
         tree: &mut Arena<Node>,
         current_node_id: NodeId,
         branches: &mut HashMap<PathBuf, Vec<NodeId>>,
         node_comparator: &NodeComparator,
-        inode_set: &mut HashSet<Inode>,
+        accounted: &mut Accounted,
         column_properties: &mut column::Properties,
+        stream: &mut Option<Stream>,
         ctx: &Context,
         root_id: NodeId,
     ) {
@@ -226,6 +520,8 @@ impl Tree {
         let mut children = branches.remove(current_node.path()).unwrap();
 
         let mut dir_size = FileSize::from(ctx);
+        let mut descendants = 0;
+        let mut descendant_dirs = 0;
 
         for child_id in &children {
             let index = *child_id;
@@ -236,8 +532,9 @@ impl Tree {
                     index,
                     branches,
                     node_comparator,
-                    inode_set,
+                    accounted,
                     column_properties,
+                    stream,
                     ctx,
                     root_id,
                 );
@@ -245,30 +542,59 @@ impl Tree {
 
             let node = tree[index].get();
 
+            descendants += 1 + node.descendants();
+            descendant_dirs += usize::from(node.is_dir()) + node.descendant_dirs();
+
             #[cfg(unix)]
             Self::update_column_properties(column_properties, node, ctx);
 
             #[cfg(not(unix))]
             Self::update_column_properties(column_properties, node, ctx);
 
-            // If a hard-link is already accounted for then don't increment parent dir size.
+            // If a hard-link is already accounted for then don't increment parent dir size. With
+            // '--logical-layout' the same goes for files reached both through a followed symlink
+            // and at their physical location, whereas plain '--follow' counts them at each path.
+            // Directories can't be hard-linked so if one is encountered twice while following
+            // symlinks, attribution is left to their contents.
             if let Some(inode) = node.inode() {
-                if inode.nlink > 1 && !inode_set.insert(inode) {
+                let exempt = ctx.follow && node.is_dir();
+                let shared = inode.nlink > 1 || ctx.logical_layout;
+
+                if shared && !exempt && !accounted.inodes.insert(inode) {
                     continue;
                 }
             }
 
+            // Shared extents count towards the first file they're encountered in.
+            #[cfg(target_os = "linux")]
+            tree[index]
+                .get_mut()
+                .claim_shared_extents(&mut accounted.extents);
+
+            let node = tree[index].get();
+
             if let Some(file_size) = node.file_size() {
                 dir_size += file_size;
             }
         }
 
-        if dir_size.value() > 0 {
+        // Unlike bytes and the like, directories occupy an inode of their own.
+        if let FileSize::Inodes(ref mut metric) = dir_size {
+            metric.value += 1;
+        }
+
+        // Directories are given a size even if it's zero so that empty directories aren't mistaken
+        // for ones that couldn't be measured.
+        if ctx.measures_sizes() {
             let dir = tree[current_node_id].get_mut();
 
             dir.set_file_size(dir_size);
         }
 
+        let dir = tree[current_node_id].get_mut();
+        dir.set_descendants(descendants);
+        dir.set_descendant_dirs(descendant_dirs);
+
         let dir = tree[current_node_id].get();
 
         Self::update_column_properties(column_properties, dir, ctx);
@@ -277,15 +603,38 @@ impl Tree {
             layout::Type::Flat | layout::Type::Iflat => {
                 // don't bother sorting, flat layouts will need to be resorted downstream
             },
+            _ if current_node_id != root_id && ctx.is_artifact(dir.file_name()) => {
+                Self::collapse_artifact(tree, current_node_id, &mut children, ctx);
+            },
             _ => {
                 children.sort_by(|&id_a, &id_b| {
                     let node_a = tree[id_a].get();
                     let node_b = tree[id_b].get();
                     node_comparator(node_a, node_b)
                 });
+
+                Self::collapse_children(tree, current_node_id, &mut children, ctx);
             },
         }
 
+        if let Some(stream) = stream {
+            // Records are written before the tree is reshaped, so directories are pruned here
+            // instead. Their contents have already been assembled so pruning carries upwards.
+            if ctx.prune || ctx.filtered() {
+                children.retain(|&child_id| {
+                    let prunable = Self::is_prunable(child_id, tree, ctx);
+
+                    if prunable {
+                        child_id.remove_subtree(tree);
+                    }
+
+                    !prunable
+                });
+            }
+
+            stream.directory(tree, &children);
+        }
+
         // Append children to current node.
         for child_id in children {
             match ctx.layout {
@@ -295,29 +644,112 @@ impl Tree {
         }
     }
 
-    /// Function to remove empty directories.
-    fn prune_directories(root_id: NodeId, tree: &mut Arena<Node>) {
-        let to_prune = root_id
-            .descendants(tree)
-            .skip(1)
+    /// Removes all but the first `--top` entries of `children` from the tree, leaving behind a
+    /// [`Collapsed`] summary on the parent. Expects `children` to already be sorted.
+    fn collapse_children(
+        tree: &mut Arena<Node>,
+        parent_id: NodeId,
+        children: &mut Vec<NodeId>,
+        ctx: &Context,
+    ) {
+        let Some(top) = ctx.top() else {
+            return;
+        };
+
+        if children.len() <= top {
+            return;
+        }
+
+        let collapsed_ids = children.split_off(top);
+        let collapsed = Collapsed::new(collapsed_ids.iter().map(|id| tree[*id].get()), ctx);
+
+        tree[parent_id].get_mut().set_collapsed(collapsed);
+
+        for node_id in collapsed_ids {
+            node_id.remove_subtree(tree);
+        }
+    }
+
+    /// Removes all of `children` from the tree, leaving behind a [`Collapsed`] summary on the
+    /// artifact directory they belong to.
+    fn collapse_artifact(
+        tree: &mut Arena<Node>,
+        dir_id: NodeId,
+        children: &mut Vec<NodeId>,
+        ctx: &Context,
+    ) {
+        if children.is_empty() {
+            return;
+        }
+
+        let collapsed = Collapsed::new(children.iter().map(|id| tree[*id].get()), ctx);
+
+        tree[dir_id].get_mut().set_collapsed(collapsed);
+
+        for node_id in children.drain(..) {
+            node_id.remove_subtree(tree);
+        }
+    }
+
+    /// Flat layouts list everything as a child of the root so the contents of artifact
+    /// directories are removed by checking whether any of their ancestors is an artifact.
+    fn remove_artifact_contents(root_id: NodeId, tree: &mut Arena<Node>, ctx: &Context) {
+        let root_path = tree[root_id].get().path().to_owned();
+
+        let to_remove = root_id
+            .children(tree)
             .filter(|node_id| {
                 tree[*node_id]
                     .get()
-                    .is_dir()
-                    .then(|| node_id.children(tree).count() == 0)
-                    .unwrap_or(false)
+                    .path()
+                    .strip_prefix(&root_path)
+                    .ok()
+                    .and_then(Path::parent)
+                    .is_some_and(|parent| parent.iter().any(|name| ctx.is_artifact(name)))
             })
             .collect::<Vec<_>>();
 
-        if to_prune.is_empty() {
-            return;
+        for node_id in to_remove {
+            node_id.remove_subtree(tree);
+        }
+    }
+
+    /// Function to remove empty directories. Directories whose children were collapsed into a
+    /// summary, such as artifacts with '--no-artifacts', aren't empty. Directories carrying the
+    /// extended attribute sought by '--xattr' are kept as they match in their own right.
+    #[cfg_attr(not(unix), allow(unused_variables))]
+    fn prune_directories(root_id: NodeId, tree: &mut Arena<Node>, ctx: &Context) {
+        loop {
+            let to_prune = root_id
+                .descendants(tree)
+                .skip(1)
+                .filter(|&node_id| Self::is_prunable(node_id, tree, ctx))
+                .collect::<Vec<_>>();
+
+            if to_prune.is_empty() {
+                return;
+            }
+
+            to_prune
+                .iter()
+                .for_each(|node_id| node_id.remove_subtree(tree));
         }
+    }
 
-        to_prune
-            .iter()
-            .for_each(|node_id| node_id.remove_subtree(tree));
+    /// Whether or not `node_id` is a directory left without any entries that pruning removes.
+    fn is_prunable(node_id: NodeId, tree: &Arena<Node>, ctx: &Context) -> bool {
+        let node = tree[node_id].get();
 
-        Self::prune_directories(root_id, tree);
+        if !node.is_dir() || node.collapsed().is_some() || node_id.children(tree).count() > 0 {
+            return false;
+        }
+
+        #[cfg(unix)]
+        if ctx.xattr_matches(node.dir_entry()) {
+            return false;
+        }
+
+        true
     }
 
     /// Filter `arena` for only directories.
@@ -335,6 +767,106 @@ impl Tree {
         to_detach.iter().for_each(|node_id| node_id.detach(tree));
     }
 
+    /// Removes every entry that neither satisfies `is_match` nor is the ancestor of an entry that
+    /// does.
+    fn retain_matches<P>(root_id: NodeId, tree: &mut Arena<Node>, is_match: P)
+    where
+        P: Fn(&Node) -> bool,
+    {
+        let mut to_keep = HashSet::from([root_id]);
+
+        for node_id in root_id.descendants(tree).skip(1) {
+            if !to_keep.contains(&node_id) && is_match(tree[node_id].get()) {
+                to_keep.extend(node_id.ancestors(tree));
+            }
+        }
+
+        // Descendants of an entry that isn't kept aren't kept either, so only the topmost ones
+        // need removing.
+        let to_remove = root_id
+            .descendants(tree)
+            .skip(1)
+            .filter(|node_id| {
+                !to_keep.contains(node_id)
+                    && tree[*node_id]
+                        .parent()
+                        .is_some_and(|parent_id| to_keep.contains(&parent_id))
+            })
+            .collect::<Vec<_>>();
+
+        for node_id in to_remove {
+            node_id.remove_subtree(tree);
+        }
+    }
+
+    /// Hides all entries shallower than `min_depth` by moving every entry at `min_depth` to be a
+    /// direct child of the root and removing whatever remains in between. The entries that end up
+    /// beneath the root are sorted as a whole with `node_comparator`.
+    fn anchor_min_depth(
+        root_id: NodeId,
+        tree: &mut Arena<Node>,
+        min_depth: usize,
+        node_comparator: &NodeComparator,
+    ) {
+        Self::reanchor(root_id, tree, |node_id, tree| {
+            let parent_depth = tree[node_id]
+                .parent()
+                .map_or(0, |parent_id| tree[parent_id].get().depth());
+
+            tree[node_id].get().depth() >= min_depth && parent_depth < min_depth
+        });
+
+        Self::sort_children(root_id, tree, node_comparator);
+    }
+
+    /// Hides all directories by moving every file to be a direct child of the root, sorted as a
+    /// whole with `node_comparator`.
+    fn anchor_leaves(root_id: NodeId, tree: &mut Arena<Node>, node_comparator: &NodeComparator) {
+        Self::reanchor(root_id, tree, |node_id, tree| !tree[node_id].get().is_dir());
+        Self::sort_children(root_id, tree, node_comparator);
+    }
+
+    /// Moves every descendant of the root that satisfies `should_anchor`, along with its
+    /// subtree, to be a direct child of the root in traversal order; everything else is removed.
+    /// Expects that no descendant of an anchored node also satisfies `should_anchor`.
+    fn reanchor<P>(root_id: NodeId, tree: &mut Arena<Node>, should_anchor: P)
+    where
+        P: Fn(NodeId, &Arena<Node>) -> bool,
+    {
+        let to_anchor = root_id
+            .descendants(tree)
+            .skip(1)
+            .filter(|node_id| should_anchor(*node_id, tree))
+            .collect::<Vec<_>>();
+
+        for node_id in &to_anchor {
+            node_id.detach(tree);
+        }
+
+        let to_remove = root_id.children(tree).collect::<Vec<_>>();
+
+        for node_id in to_remove {
+            node_id.remove_subtree(tree);
+        }
+
+        for node_id in to_anchor {
+            root_id.append(node_id, tree);
+        }
+    }
+
+    /// Sorts the children of `node_id` with `node_comparator`, which is necessary once they've been
+    /// gathered from different parents.
+    fn sort_children(node_id: NodeId, tree: &mut Arena<Node>, node_comparator: &NodeComparator) {
+        let mut children = node_id.children(tree).collect::<Vec<_>>();
+
+        children.sort_by(|&id_a, &id_b| node_comparator(tree[id_a].get(), tree[id_b].get()));
+
+        for child_id in children {
+            child_id.detach(tree);
+            node_id.append(child_id, tree);
+        }
+    }
+
     /// Compute total number of files for a single directory without recurring into child
     /// directories. Files are grouped into three categories: directories, regular files, and
     /// symlinks.
@@ -345,26 +877,60 @@ impl Tree {
             .fold(FileCount::default(), |acc, node| acc + node)
     }
 
+    /// Returns `true` if the node is the final entry of its parent's listing, that is, it has no
+    /// following siblings and it isn't followed by a summary of collapsed entries.
+    pub fn is_last_sibling(node_id: NodeId, tree: &Arena<Node>) -> bool {
+        node_id.following_siblings(tree).nth(1).is_none()
+            && tree[node_id].parent().map_or(true, |parent_id| {
+                tree[parent_id].get().collapsed().is_none()
+            })
+    }
+
     /// Updates [`column::Properties`] with provided [`Node`].
     fn update_column_properties(col_props: &mut column::Properties, node: &Node, ctx: &Context) {
         if let Some(file_size) = node.file_size() {
-            if ctx.byte_metric() && ctx.human {
+            if ctx.byte_metric() && ctx.fixed_unit().is_none() && ctx.prefix_kind().is_raw() {
                 let out = format!("{file_size}");
-                let [size, unit]: [&str; 2] =
-                    out.split(' ').collect::<Vec<&str>>().try_into().unwrap();
 
-                let file_size_cols = size.len();
-                let file_size_unit_cols = unit.len();
-
-                if file_size_cols > col_props.max_size_width {
-                    col_props.max_size_width = file_size_cols;
+                // Raw byte counts have no unit but may contain digit separators.
+                for size in out.split(' ') {
+                    if size.len() > col_props.max_size_width {
+                        col_props.max_size_width = size.len();
+                    }
                 }
+            } else if ctx.byte_metric() && (ctx.human || ctx.fixed_unit().is_some()) {
+                let out = format!("{file_size}");
+
+                // Both logical and physical sizes share the same column widths.
+                for pair in out.split(' ').collect::<Vec<&str>>().chunks(2) {
+                    let [size, unit]: [&str; 2] = pair.try_into().unwrap();
+
+                    let file_size_cols = size.len();
+                    let file_size_unit_cols = unit.len();
+
+                    if file_size_cols > col_props.max_size_width {
+                        col_props.max_size_width = file_size_cols;
+                    }
 
-                if file_size_unit_cols > col_props.max_size_unit_width {
-                    col_props.max_size_unit_width = file_size_unit_cols;
+                    if file_size_unit_cols > col_props.max_size_unit_width {
+                        col_props.max_size_unit_width = file_size_unit_cols;
+                    }
                 }
             } else {
-                let file_size_cols = utils::num_integral(file_size.value());
+                #[cfg(unix)]
+                let value = match file_size {
+                    FileSize::Block(metric) => metric.blocks(),
+                    FileSize::Both(logical, physical) => logical.value.max(physical.value),
+                    _ => file_size.value(),
+                };
+
+                #[cfg(not(unix))]
+                let value = match file_size {
+                    FileSize::Both(logical, physical) => logical.value.max(physical.value),
+                    _ => file_size.value(),
+                };
+
+                let file_size_cols = utils::num_integral(value);
 
                 if file_size_cols > col_props.max_size_width {
                     col_props.max_size_width = file_size_cols;
@@ -372,8 +938,42 @@ impl Tree {
             };
         }
 
+        // Inode numbers are shown outside of long view as well.
         #[cfg(unix)]
-        if ctx.long {
+        if ctx.ino || ctx.long() {
+            if let Some(ino) = node.ino() {
+                let ino_num_integral = utils::num_integral(ino);
+
+                if ino_num_integral > col_props.max_ino_width {
+                    col_props.max_ino_width = ino_num_integral;
+                }
+            }
+        }
+
+        #[cfg(target_os = "linux")]
+        if ctx.needs_security_context() {
+            if let Some(context) = node.security_context() {
+                let context_len = context.len();
+
+                if context_len > col_props.max_security_context_width {
+                    col_props.max_security_context_width = context_len;
+                }
+            }
+        }
+
+        #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+        if ctx.shows_file_flags() {
+            if let Some(flags) = node.flags() {
+                let flags_len = flags.to_string().len();
+
+                if flags_len > col_props.max_flags_width {
+                    col_props.max_flags_width = flags_len;
+                }
+            }
+        }
+
+        #[cfg(unix)]
+        if ctx.long() {
             if let Some(owner) = node.owner() {
                 let owner_len = owner.len();
 
@@ -390,14 +990,6 @@ impl Tree {
                 }
             }
 
-            if let Some(ino) = node.ino() {
-                let ino_num_integral = utils::num_integral(ino);
-
-                if ino_num_integral > col_props.max_ino_width {
-                    col_props.max_ino_width = ino_num_integral;
-                }
-            }
-
             if let Some(nlink) = node.nlink() {
                 let nlink_num_integral = utils::num_integral(nlink);
 
@@ -421,34 +1013,191 @@ impl TryFrom<&Context> for WalkParallel {
     type Error = Error;
 
     fn try_from(ctx: &Context) -> StdResult<Self, Self::Error> {
+        Tree::walker(ctx, None)
+    }
+}
+
+impl Tree {
+    /// Builds the walker that drives traversal. If `filtered` is provided then every entry that's
+    /// rejected by the entry filter is tallied by the [`filtered::Rule`] responsible, and every
+    /// directory the walker reads is noted so that what its hidden and ignore rules skipped can be
+    /// tallied once traversal completes.
+    fn walker(ctx: &Context, filtered: Option<Arc<filtered::Stats>>) -> Result<WalkParallel> {
         let root_id = fs::canonicalize(ctx.dir())?;
 
         fs::metadata(&root_id)
             .map_err(|e| Error::DirNotFound(format!("{}: {e}", root_id.display())))?;
 
-        let mut builder = WalkBuilder::new(root_id);
+        let mut builder = WalkBuilder::new(&root_id);
 
         builder
             .follow_links(ctx.follow)
-            .git_ignore(!ctx.no_ignore)
-            .git_global(!ctx.no_ignore)
-            .hidden(!ctx.hidden)
             .overrides(ctx.no_git_override()?)
             .same_file_system(ctx.same_fs)
             .threads(ctx.threads);
 
-        if ctx.suppress_size && ctx.level() == 1 {
-            builder.max_depth(Some(1)).threads(1);
+        Self::apply_ignore_rules(&mut builder, ctx);
+
+        // Sorting by count and '--report' need the full traversal to know how many entries each
+        // directory has.
+        let counting = ctx.report
+            || ctx
+                .sort
+                .iter()
+                .any(|key| matches!(key, sort::Type::Count | sort::Type::Rcount));
+
+        let max_depth = (!ctx.measures_sizes() && ctx.level() == 1 && !counting).then_some(1);
+
+        if max_depth.is_some() {
+            builder.max_depth(max_depth).threads(1);
         }
 
-        if ctx.pattern.is_some() {
-            if ctx.glob || ctx.iglob {
-                builder.filter_entry(ctx.glob_predicate()?);
-            } else {
-                builder.filter_entry(ctx.regex_predicate()?);
+        let pattern_predicate = if ctx.pattern.is_none() {
+            None
+        } else if ctx.glob || ctx.iglob {
+            Some(ctx.glob_predicate()?)
+        } else {
+            Some(ctx.regex_predicate()?)
+        };
+
+        let predicates = Self::predicates(ctx)?;
+
+        let exclude_predicate = ctx
+            .exclude_from
+            .as_deref()
+            .map(|exclude_from| Self::exclude_predicate(exclude_from, &root_id))
+            .transpose()?;
+
+        if let Some(stats) = filtered {
+            builder.filter_entry(move |dir_entry| {
+                stats.reached(dir_entry);
+
+                let rule = if !exclude_predicate.as_ref().map_or(true, |p| p(dir_entry)) {
+                    filtered::Rule::Ignored
+                } else if !pattern_predicate.as_ref().map_or(true, |p| p(dir_entry)) {
+                    filtered::Rule::Unmatched
+                } else if !predicates.iter().all(|p| p(dir_entry)) {
+                    filtered::Rule::Other
+                } else {
+                    let is_dir = dir_entry.file_type().is_some_and(|ft| ft.is_dir());
+
+                    if is_dir && max_depth.map_or(true, |max| dir_entry.depth() < max) {
+                        stats.will_read(dir_entry.path());
+                    }
+
+                    return true;
+                };
+
+                stats.record(dir_entry, rule);
+                false
+            });
+        } else {
+            let predicates = exclude_predicate
+                .into_iter()
+                .chain(pattern_predicate)
+                .chain(predicates)
+                .collect::<Vec<_>>();
+
+            if !predicates.is_empty() {
+                builder.filter_entry(move |dir_entry| predicates.iter().all(|p| p(dir_entry)));
             }
         }
 
         Ok(builder.build_parallel())
     }
+
+    /// Predicates of the filtering options other than '--pattern' that every entry must satisfy.
+    fn predicates(ctx: &Context) -> Result<Vec<Filter>> {
+        let ext_predicate = if ctx.ext.is_some() {
+            Some(ctx.ext_predicate()?)
+        } else {
+            None
+        };
+
+        let broken_links_predicate = if ctx.broken_links {
+            Some(Context::broken_links_predicate())
+        } else {
+            None
+        };
+
+        let links_predicate = if ctx.links.is_some() {
+            Some(ctx.links_predicate()?)
+        } else {
+            None
+        };
+
+        let mime_predicate = if ctx.mime.is_some() {
+            Some(ctx.mime_predicate()?)
+        } else {
+            None
+        };
+
+        #[cfg(unix)]
+        let xattr_predicate = if ctx.xattr.is_some() {
+            Some(ctx.xattr_predicate()?)
+        } else {
+            None
+        };
+
+        #[cfg(not(unix))]
+        let xattr_predicate = None;
+
+        #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+        let flags_predicate = if ctx.flags.is_some() {
+            Some(ctx.flags_predicate()?)
+        } else {
+            None
+        };
+
+        #[cfg(not(any(target_os = "macos", target_os = "freebsd")))]
+        let flags_predicate = None;
+
+        let only_ignored_predicate = if ctx.only_ignored {
+            Some(ctx.only_ignored_predicate())
+        } else {
+            None
+        };
+
+        Ok([
+            ext_predicate,
+            broken_links_predicate,
+            links_predicate,
+            mime_predicate,
+            xattr_predicate,
+            flags_predicate,
+            only_ignored_predicate,
+        ]
+        .into_iter()
+        .flatten()
+        .collect())
+    }
+
+    /// Configures `builder` to skip hidden files as well as ignored files as per `ctx`.
+    fn apply_ignore_rules(builder: &mut WalkBuilder, ctx: &Context) {
+        builder
+            .git_ignore(!ctx.no_ignore && !ctx.only_ignored)
+            .git_global(!ctx.no_ignore && !ctx.only_ignored)
+            .git_exclude(!ctx.only_ignored)
+            .ignore(!ctx.only_ignored)
+            .hidden(!ctx.hidden);
+    }
+
+    /// Predicate that rejects entries matching the patterns read from `exclude_from` for
+    /// '--exclude-from'. Like those of a `.gitignore` in `root`, patterns containing a slash are
+    /// anchored at `root`, which is expected to be the canonical path of the scanned directory, so
+    /// the outcome doesn't depend on the working directory.
+    fn exclude_predicate(exclude_from: &Path, root: &Path) -> Result<Filter> {
+        let mut builder = GitignoreBuilder::new(root);
+
+        if let Some(err) = builder.add(exclude_from) {
+            return Err(Error::ExcludeFrom(err));
+        }
+
+        let exclusions = builder.build().map_err(Error::ExcludeFrom)?;
+
+        Ok(Box::new(move |dir_entry| {
+            let is_dir = dir_entry.file_type().is_some_and(|ft| ft.is_dir());
+            !exclusions.matched(dir_entry.path(), is_dir).is_ignore()
+        }))
+    }
 }
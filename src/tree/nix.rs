@@ -0,0 +1,115 @@
+use super::Tree;
+use crate::{
+    context::Context,
+    disk_usage::file_size::FileSize,
+    fs::nix::{StorePath, STORE_DIR},
+};
+use std::{
+    cmp::Reverse,
+    fmt::{self, Display},
+    path::Path,
+};
+
+/// Disk usage of each derivation in the Nix store, printed after the output when '--nix' is used.
+/// Store entries are grouped by derivation so that separate builds of the same package along with
+/// the `.drv` files that produced them are summarized once.
+pub struct Summary {
+    derivations: Vec<Derivation>,
+}
+
+/// The store entries that belong to a single derivation.
+struct Derivation {
+    name: String,
+    builds: usize,
+    file_size: FileSize,
+}
+
+impl Summary {
+    /// Groups every top-level entry of the Nix store beneath the root of `tree` by derivation,
+    /// largest first.
+    pub fn new(tree: &Tree, ctx: &Context) -> Self {
+        let arena = tree.arena();
+
+        let mut derivations: Vec<Derivation> = vec![];
+
+        for node_id in tree.root_id().descendants(arena) {
+            let node = arena[node_id].get();
+
+            if node.parent_path() != Some(Path::new(STORE_DIR)) {
+                continue;
+            }
+
+            let Some(store_path) = node
+                .file_name()
+                .to_str()
+                .and_then(StorePath::from_file_name)
+            else {
+                continue;
+            };
+
+            let Some(file_size) = node.file_size() else {
+                continue;
+            };
+
+            let name = store_path.derivation();
+
+            let index = derivations
+                .iter()
+                .position(|derivation| derivation.name == name)
+                .unwrap_or_else(|| {
+                    derivations.push(Derivation {
+                        name: name.to_owned(),
+                        builds: 0,
+                        file_size: FileSize::from(ctx),
+                    });
+                    derivations.len() - 1
+                });
+
+            let derivation = &mut derivations[index];
+
+            if !store_path.is_drv() {
+                derivation.builds += 1;
+            }
+
+            derivation.file_size += file_size;
+        }
+
+        derivations.sort_by(|a, b| {
+            Reverse(a.file_size.value())
+                .cmp(&Reverse(b.file_size.value()))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        Self { derivations }
+    }
+
+    /// Whether or not no entries of the Nix store were found.
+    pub fn is_empty(&self) -> bool {
+        self.derivations.is_empty()
+    }
+}
+
+impl Display for Summary {
+    /// One line per derivation, noting how many builds of it reside in the store if more than one.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines = self
+            .derivations
+            .iter()
+            .map(|derivation| {
+                let Derivation {
+                    name,
+                    builds,
+                    file_size,
+                } = derivation;
+
+                if *builds > 1 {
+                    format!("{file_size} {name} ({builds} builds)")
+                } else {
+                    format!("{file_size} {name}")
+                }
+            })
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
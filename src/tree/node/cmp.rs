@@ -7,19 +7,62 @@ pub type NodeComparator = dyn Fn(&Node, &Node) -> Ordering;
 
 /// Yields function pointer to the appropriate `Node` comparator.
 pub fn comparator(ctx: &Context) -> Box<NodeComparator> {
-    let sort_type = ctx.sort;
+    if ctx.shuffle {
+        return shuffling::comparator(ctx.seed);
+    }
+
+    let base = chained_comparator(&ctx.sort);
 
     match ctx.dir_order {
-        dir::Order::First => {
-            Box::new(move |a, b| dir_first_comparator(a, b, base_comparator(sort_type)))
-        },
-        dir::Order::Last => {
-            Box::new(move |a, b| dir_last_comparator(a, b, base_comparator(sort_type)))
-        },
-        dir::Order::None => base_comparator(sort_type),
+        dir::Order::First => Box::new(move |a, b| dir_first_comparator(a, b, &base)),
+        dir::Order::Last => Box::new(move |a, b| dir_last_comparator(a, b, &base)),
+        dir::Order::None => base,
     }
 }
 
+/// Yields a comparator that orders [Node]s by the columns of flat output given to '--sort-by',
+/// where each subsequent column breaks ties left by the ones before it.
+pub fn column_comparator(columns: &[sort::Column]) -> Box<NodeComparator> {
+    let comparators = columns
+        .iter()
+        .map(|column| -> Box<NodeComparator> {
+            match column {
+                sort::Column::Path => Box::new(|a, b| a.path().cmp(b.path())),
+                sort::Column::Size => base_comparator(sort::Type::Size),
+                sort::Column::Count => base_comparator(sort::Type::Count),
+                sort::Column::Mtime => base_comparator(sort::Type::Mod),
+                sort::Column::Atime => base_comparator(sort::Type::Access),
+                sort::Column::Btime => base_comparator(sort::Type::Create),
+                #[cfg(unix)]
+                sort::Column::Owner => Box::new(|a, b| a.owner().cmp(&b.owner())),
+                #[cfg(unix)]
+                sort::Column::Group => Box::new(|a, b| a.group().cmp(&b.group())),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Box::new(move |a, b| {
+        comparators.iter().fold(Ordering::Equal, |ordering, cmp| {
+            ordering.then_with(|| cmp(a, b))
+        })
+    })
+}
+
+/// Chains the comparators of each of `sort_types` such that each subsequent comparator is only
+/// consulted if all preceding comparators consider two [Node]s equal.
+fn chained_comparator(sort_types: &[sort::Type]) -> Box<NodeComparator> {
+    let comparators = sort_types
+        .iter()
+        .map(|sort_type| base_comparator(*sort_type))
+        .collect::<Vec<_>>();
+
+    Box::new(move |a, b| {
+        comparators.iter().fold(Ordering::Equal, |ordering, cmp| {
+            ordering.then_with(|| cmp(a, b))
+        })
+    })
+}
+
 /// Orders directories first. Provides a fallback if inputs are not directories.
 fn dir_first_comparator(
     a: &Node,
@@ -49,10 +92,19 @@ fn dir_last_comparator(
 /// Grabs the comparator for two non-dir type [Node]s.
 fn base_comparator(sort_type: sort::Type) -> Box<NodeComparator> {
     Box::new(match sort_type {
+        sort::Type::Kind => |a, b| dir_first_comparator(a, b, |_, _| Ordering::Equal),
         sort::Type::Name => naming::comparator,
         sort::Type::Rname => naming::rev_comparator,
+        sort::Type::NameCi => naming::case_insensitive_comparator,
+        sort::Type::RnameCi => naming::rev_case_insensitive_comparator,
+        sort::Type::Version => naming::version_comparator,
+        sort::Type::Rversion => naming::rev_version_comparator,
+        sort::Type::Ext => naming::ext_comparator,
+        sort::Type::Rext => naming::rev_ext_comparator,
         sort::Type::Size => sizing::comparator,
         sort::Type::Rsize => sizing::rev_comparator,
+        sort::Type::Count => counting::comparator,
+        sort::Type::Rcount => counting::rev_comparator,
         sort::Type::Access => time_stamping::accessed::comparator,
         sort::Type::Raccess => time_stamping::accessed::rev_comparator,
         sort::Type::Create => time_stamping::created::comparator,
@@ -86,10 +138,17 @@ mod time_stamping {
         use core::cmp::Ordering;
         use std::time::SystemTime;
 
-        /// Comparator that sorts [Node]s by Creation timestamp, newer to older.
+        /// Comparator that sorts [Node]s by Creation timestamp, newer to older. Platforms and
+        /// filesystems that don't record birth time fall back to the Alteration timestamp.
         pub fn comparator(a: &Node, b: &Node) -> Ordering {
-            let a_stamp = a.created().unwrap_or_else(SystemTime::now);
-            let b_stamp = b.created().unwrap_or_else(SystemTime::now);
+            let a_stamp = a
+                .created()
+                .or_else(|| a.modified())
+                .unwrap_or_else(SystemTime::now);
+            let b_stamp = b
+                .created()
+                .or_else(|| b.modified())
+                .unwrap_or_else(SystemTime::now);
             a_stamp.cmp(&b_stamp)
         }
 
@@ -135,9 +194,72 @@ mod sizing {
     }
 }
 
+mod counting {
+    use crate::tree::node::Node;
+    use core::cmp::Ordering;
+
+    /// Comparator that sorts [Node]s by the amount of entries they contain, most to fewest.
+    pub fn comparator(a: &Node, b: &Node) -> Ordering {
+        b.descendants().cmp(&a.descendants())
+    }
+
+    /// Comparator that sorts [Node]s by the amount of entries they contain, fewest to most.
+    pub fn rev_comparator(a: &Node, b: &Node) -> Ordering {
+        comparator(b, a)
+    }
+}
+
+mod shuffling {
+    use super::NodeComparator;
+    use crate::tree::node::Node;
+    use std::{
+        collections::hash_map::RandomState,
+        hash::{BuildHasher, Hasher},
+        sync::OnceLock,
+    };
+
+    /// Seed used when '--seed' isn't provided, which is picked once per invocation so that
+    /// every comparator agrees on the order.
+    static RANDOM_SEED: OnceLock<u64> = OnceLock::new();
+
+    const FNV_OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+
+    const FNV_PRIME: u64 = 0x0100_0000_01B3;
+
+    /// Comparator that orders [Node]s by a keyed hash of their file names, yielding an order that
+    /// looks random but is the same across runs given the same `seed`.
+    pub fn comparator(seed: Option<u64>) -> Box<NodeComparator> {
+        let seed = seed.unwrap_or_else(|| {
+            *RANDOM_SEED.get_or_init(|| RandomState::new().build_hasher().finish())
+        });
+
+        Box::new(move |a, b| key(a, seed).cmp(&key(b, seed)))
+    }
+
+    /// FNV-1a of the file name of `node` seeded by `seed` and finalized with the mixer of
+    /// `SplitMix64` so that names differing by a single byte land far apart.
+    fn key(node: &Node, seed: u64) -> u64 {
+        let hash = node
+            .file_name()
+            .to_string_lossy()
+            .bytes()
+            .fold(FNV_OFFSET_BASIS ^ seed, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+            });
+
+        let hash = (hash ^ (hash >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        let hash = (hash ^ (hash >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+        hash ^ (hash >> 31)
+    }
+}
+
 mod naming {
     use crate::tree::node::Node;
+    use caseless::Caseless;
     use core::cmp::Ordering;
+    use std::path::Path;
+    use unicode_normalization::{char::is_combining_mark, Decompositions, UnicodeNormalization};
 
     /// Comparator based on [Node] file names in lexicographical order.
     pub fn comparator(a: &Node, b: &Node) -> Ordering {
@@ -148,4 +270,110 @@ mod naming {
     pub fn rev_comparator(a: &Node, b: &Node) -> Ordering {
         comparator(b, a)
     }
+
+    /// Comparator based on [Node] file names collated the way a dictionary would: case is folded
+    /// and accents are ignored, so `Älder` sorts among names that start with `a`. Names that only
+    /// differ by accents are then ordered by them, and names that only differ by case fall back to
+    /// byte order.
+    pub fn case_insensitive_comparator(a: &Node, b: &Node) -> Ordering {
+        let a_name = a.file_name().to_string_lossy();
+        let b_name = b.file_name().to_string_lossy();
+
+        let unaccented = |folded: Decompositions<_>| folded.filter(|c| !is_combining_mark(*c));
+
+        unaccented(folded(&a_name))
+            .cmp(unaccented(folded(&b_name)))
+            .then_with(|| folded(&a_name).cmp(folded(&b_name)))
+            .then_with(|| a_name.cmp(&b_name))
+    }
+
+    /// Comparator based on [Node] file names in reversed lexicographical order ignoring case.
+    pub fn rev_case_insensitive_comparator(a: &Node, b: &Node) -> Ordering {
+        case_insensitive_comparator(b, a)
+    }
+
+    /// Comparator based on [Node] file extensions, falling back to file names for entries that
+    /// share an extension. Entries without an extension come first.
+    pub fn ext_comparator(a: &Node, b: &Node) -> Ordering {
+        let a_ext = Path::new(a.file_name()).extension();
+        let b_ext = Path::new(b.file_name()).extension();
+
+        a_ext.cmp(&b_ext).then_with(|| comparator(a, b))
+    }
+
+    /// Comparator based on [Node] file extensions in reversed order.
+    pub fn rev_ext_comparator(a: &Node, b: &Node) -> Ordering {
+        ext_comparator(b, a)
+    }
+
+    /// Comparator based on [Node] file names in natural order where runs of digits are compared
+    /// by their numeric value, so `v1.9.0` comes before `v1.10.0`.
+    pub fn version_comparator(a: &Node, b: &Node) -> Ordering {
+        natural_cmp(
+            &a.file_name().to_string_lossy(),
+            &b.file_name().to_string_lossy(),
+        )
+    }
+
+    /// Comparator based on [Node] file names in reversed natural order.
+    pub fn rev_version_comparator(a: &Node, b: &Node) -> Ordering {
+        version_comparator(b, a)
+    }
+
+    /// Characters of `name` after canonical caseless folding, in decomposed form so that accents
+    /// are kept apart from the letters they belong to.
+    fn folded(name: &str) -> Decompositions<impl Iterator<Item = char> + '_> {
+        name.chars().nfd().default_case_fold().nfd()
+    }
+
+    /// Compares `a` and `b` chunk by chunk where a chunk is either a run of digits or a run of
+    /// everything else. Digit runs are compared numerically with leading zeros only breaking ties.
+    fn natural_cmp(a: &str, b: &str) -> Ordering {
+        let mut a_chunks = chunks(a);
+        let mut b_chunks = chunks(b);
+
+        loop {
+            let (a_chunk, b_chunk) = match (a_chunks.next(), b_chunks.next()) {
+                (Some(a_chunk), Some(b_chunk)) => (a_chunk, b_chunk),
+                (a_chunk, b_chunk) => return a_chunk.is_some().cmp(&b_chunk.is_some()),
+            };
+
+            let is_numeric = |chunk: &str| chunk.starts_with(|c: char| c.is_ascii_digit());
+
+            let ordering = if is_numeric(a_chunk) && is_numeric(b_chunk) {
+                let a_num = a_chunk.trim_start_matches('0');
+                let b_num = b_chunk.trim_start_matches('0');
+
+                a_num
+                    .len()
+                    .cmp(&b_num.len())
+                    .then_with(|| a_num.cmp(b_num))
+                    .then_with(|| b_chunk.len().cmp(&a_chunk.len()))
+            } else {
+                a_chunk.cmp(b_chunk)
+            };
+
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+    }
+
+    /// Splits `s` into alternating runs of ASCII digits and non-digits.
+    fn chunks(s: &str) -> impl Iterator<Item = &str> {
+        let mut rest = s;
+
+        std::iter::from_fn(move || {
+            let first = rest.chars().next()?;
+            let numeric = first.is_ascii_digit();
+
+            let end = rest
+                .find(|c: char| c.is_ascii_digit() != numeric)
+                .unwrap_or(rest.len());
+
+            let (chunk, remainder) = rest.split_at(end);
+            rest = remainder;
+            Some(chunk)
+        })
+    }
 }
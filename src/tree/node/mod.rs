@@ -1,19 +1,20 @@
 use crate::{
     context::Context,
-    disk_usage::file_size::{byte, line_count, word_count, DiskUsage, FileSize},
+    disk_usage::file_size::{self, byte, inode_count, line_count, word_count, DiskUsage, FileSize},
     fs::inode::Inode,
     icons,
     styles::get_ls_colors,
-    tree::error::Error,
+    tree::{collapsed::Collapsed, error::Error},
 };
 use ansi_term::Style;
 use ignore::DirEntry;
-use lscolors::Style as LS_Style;
+use lscolors::{Indicator, Style as LS_Style};
 use std::{
     borrow::Cow,
     convert::TryFrom,
-    ffi::OsStr,
+    ffi::{OsStr, OsString},
     fs::{FileType, Metadata},
+    io,
     path::{Path, PathBuf},
     time::SystemTime,
 };
@@ -21,9 +22,17 @@ use std::{
 #[cfg(unix)]
 use crate::{
     disk_usage::file_size::block,
-    fs::permissions::{FileMode, SymbolicNotation},
+    fs::{
+        permissions::{FileMode, SymbolicNotation},
+        xattr::{self, ExtendedAttr},
+    },
 };
 
+#[cfg(target_os = "linux")]
+use crate::fs::fiemap::{self, SharedExtent};
+#[cfg(target_os = "linux")]
+use std::collections::HashSet;
+
 /// Ordering and sorting rules for [Node].
 pub mod cmp;
 
@@ -38,21 +47,31 @@ pub mod unix;
 /// [`Tree`]: super::Tree
 pub struct Node {
     dir_entry: DirEntry,
-    metadata: Metadata,
+    metadata: Option<Metadata>,
     file_size: Option<FileSize>,
     style: Option<Style>,
     symlink_target: Option<PathBuf>,
+    broken_symlink: bool,
     inode: Option<Inode>,
+    collapsed: Option<Collapsed>,
+    descendants: usize,
+    descendant_dirs: usize,
+    share: Option<f64>,
+    vanished: bool,
+    name: Option<OsString>,
 
     #[cfg(unix)]
     unix_attrs: unix::Attrs,
+
+    #[cfg(target_os = "linux")]
+    shared_extents: Vec<SharedExtent>,
 }
 
 impl Node {
     /// Initializes a new [Node].
     pub const fn new(
         dir_entry: DirEntry,
-        metadata: Metadata,
+        metadata: Option<Metadata>,
         file_size: Option<FileSize>,
         style: Option<Style>,
         symlink_target: Option<PathBuf>,
@@ -65,16 +84,122 @@ impl Node {
             file_size,
             style,
             symlink_target,
+            broken_symlink: false,
             inode,
+            collapsed: None,
+            descendants: 0,
+            descendant_dirs: 0,
+            share: None,
+            vanished: false,
+            name: None,
             #[cfg(unix)]
             unix_attrs,
+            #[cfg(target_os = "linux")]
+            shared_extents: Vec::new(),
+        }
+    }
+
+    /// Marks [Node] as a symlink whose target does not exist.
+    const fn with_broken_symlink(mut self, broken_symlink: bool) -> Self {
+        self.broken_symlink = broken_symlink;
+        self
+    }
+
+    /// Sets aside the extents the file shares with others for '--disk-usage unique', which
+    /// aren't counted towards its size until it claims them during [`Tree`] assembly.
+    ///
+    /// [`Tree`]: super::Tree
+    #[cfg(target_os = "linux")]
+    fn with_shared_extents(mut self, ctx: &Context) -> Self {
+        if !matches!(ctx.disk_usage, DiskUsage::Unique) {
+            return self;
+        }
+
+        let Some(FileSize::Byte(ref mut metric)) = self.file_size else {
+            return self;
+        };
+
+        self.shared_extents = fiemap::shared_extents(self.dir_entry.path()).unwrap_or_default();
+
+        let shared = self
+            .shared_extents
+            .iter()
+            .map(|extent| extent.length)
+            .sum::<u64>();
+        metric.value = metric.value.saturating_sub(shared);
+
+        self
+    }
+
+    /// Initializes a [Node] for an entry that was removed after its parent directory was read.
+    fn vanished(dir_entry: DirEntry, symlink_target: Option<PathBuf>) -> Self {
+        let mut node = Self::new(
+            dir_entry,
+            None,
+            None,
+            None,
+            symlink_target,
+            None,
+            #[cfg(unix)]
+            unix::Attrs::default(),
+        );
+        node.vanished = true;
+        node
+    }
+
+    /// Measures the size of a file as determined by '--disk-usage'.
+    fn measure(path: &Path, metadata: &Metadata, ctx: &Context) -> Option<FileSize> {
+        let logical = || {
+            file_size::formatted(
+                byte::Metric::init_logical(metadata, ctx.prefix_kind(), ctx.human),
+                ctx,
+            )
+        };
+
+        let physical = || {
+            file_size::formatted(
+                byte::Metric::init_physical(path, metadata, ctx.prefix_kind(), ctx.human),
+                ctx,
+            )
+        };
+
+        match ctx.disk_usage {
+            DiskUsage::Logical => Some(FileSize::Byte(logical())),
+            DiskUsage::Physical => Some(FileSize::Byte(physical())),
+            // Shared extents are subtracted afterwards as they're kept around to be attributed to
+            // whichever file claims them first.
+            #[cfg(target_os = "linux")]
+            DiskUsage::Unique => Some(FileSize::Byte(physical())),
+            DiskUsage::Both => Some(FileSize::Both(logical(), physical())),
+            DiskUsage::Line => {
+                let metric = line_count::Metric::init(path);
+                metric.map(FileSize::Line)
+            },
+            DiskUsage::Word => {
+                let metric = word_count::Metric::init(path);
+                metric.map(FileSize::Word)
+            },
+            DiskUsage::Inodes => Some(FileSize::Inodes(inode_count::Metric::init())),
+
+            #[cfg(unix)]
+            DiskUsage::Block => {
+                let metric = block::Metric::init(metadata).with_block_size(ctx.block_size);
+                Some(FileSize::Block(metric))
+            },
         }
     }
 
     /// Returns a reference to `file_name`. If file is a symlink then `file_name` is the name of
     /// the symlink not the target.
     pub fn file_name(&self) -> &OsStr {
-        self.dir_entry.file_name()
+        self.name
+            .as_deref()
+            .unwrap_or_else(|| self.dir_entry.file_name())
+    }
+
+    /// Shows [Node] as `name` in place of its actual file name.
+    pub fn set_file_name(&mut self, name: OsString) {
+        self.name = Some(name);
     }
 
     pub const fn dir_entry(&self) -> &DirEntry {
@@ -92,24 +217,53 @@ impl Node {
     pub fn blocks(&self) -> Option<u64> {
         use std::os::unix::fs::MetadataExt;
 
-        let blocks = self.metadata.blocks();
+        let blocks = self.metadata.as_ref()?.blocks();
 
         (blocks != 0).then_some(blocks)
     }
 
     /// Timestamp of when file was last modified.
     pub fn modified(&self) -> Option<SystemTime> {
-        self.metadata.modified().ok()
+        self.metadata.as_ref()?.modified().ok()
     }
 
     /// Timestamp of when file was created.
     pub fn created(&self) -> Option<SystemTime> {
-        self.metadata.created().ok()
+        self.metadata.as_ref()?.created().ok()
     }
 
     /// Timestamp of when file was last accessed.
     pub fn accessed(&self) -> Option<SystemTime> {
-        self.metadata.accessed().ok()
+        self.metadata.as_ref()?.accessed().ok()
+    }
+
+    /// Timestamp of when the file's inode was last changed.
+    #[cfg(unix)]
+    pub fn changed(&self) -> Option<SystemTime> {
+        use std::{os::unix::fs::MetadataExt, time::Duration};
+
+        let md = self.metadata.as_ref()?;
+
+        let since_epoch = Duration::new(
+            u64::try_from(md.ctime()).ok()?,
+            u32::try_from(md.ctime_nsec()).ok()?,
+        );
+
+        SystemTime::UNIX_EPOCH.checked_add(since_epoch)
+    }
+
+    /// Adds the shared extents of the file that no other file has claimed yet to its size.
+    #[cfg(target_os = "linux")]
+    pub fn claim_shared_extents(&mut self, claimed: &mut HashSet<(u64, u64)>) {
+        let Some(inode) = self.inode else {
+            return;
+        };
+
+        let bytes = fiemap::claim(claimed, inode.dev, &self.shared_extents);
+
+        if let Some(FileSize::Byte(ref mut metric)) = self.file_size {
+            metric.value += bytes;
+        }
     }
 
     /// Gets the underlying [Inode] of the entry.
@@ -117,6 +271,37 @@ impl Node {
         self.inode
     }
 
+    /// Whether or not [Node] is a placeholder whose contents are only available from a cloud
+    /// provider.
+    pub fn is_placeholder(&self) -> bool {
+        self.metadata
+            .as_ref()
+            .is_some_and(crate::fs::cloud::is_placeholder)
+    }
+
+    /// Logical size of [Node] if it's a placeholder, i.e. how many of its bytes are only available
+    /// from a cloud provider rather than stored locally.
+    pub fn placeholder_bytes(&self) -> Option<u64> {
+        self.metadata
+            .as_ref()
+            .filter(|md| crate::fs::cloud::is_placeholder(md))
+            .map(Metadata::len)
+    }
+
+    /// The file flags of [Node] as set by `chflags(1)`.
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    pub fn flags(&self) -> Option<crate::fs::permissions::flags::Flags> {
+        self.metadata
+            .as_ref()
+            .map(crate::fs::permissions::flags::Flags::of)
+    }
+
+    /// Whether or not [Node] was removed after its parent directory was read but before its
+    /// metadata could be queried.
+    pub const fn is_vanished(&self) -> bool {
+        self.vanished
+    }
+
     /// Returns the underlying `ino` of the [`DirEntry`].
     #[cfg(unix)]
     pub fn ino(&self) -> Option<u64> {
@@ -129,6 +314,17 @@ impl Node {
         self.inode.map(|inode| inode.nlink)
     }
 
+    /// Returns the device number of the [`DirEntry`] if it's a block or character device.
+    #[cfg(unix)]
+    pub fn rdev(&self) -> Option<u64> {
+        use std::os::unix::fs::{FileTypeExt, MetadataExt};
+
+        let md = self.metadata.as_ref()?;
+        let ft = md.file_type();
+
+        (ft.is_block_device() || ft.is_char_device()).then(|| md.rdev())
+    }
+
     /// Returns `true` if node is a directory.
     pub fn is_dir(&self) -> bool {
         self.file_type().map_or(false, |ft| ft.is_dir())
@@ -139,6 +335,11 @@ impl Node {
         self.symlink_target.is_some()
     }
 
+    /// Is the Node a symlink whose target does not exist.
+    pub const fn is_broken_symlink(&self) -> bool {
+        self.broken_symlink
+    }
+
     /// Path to symlink target.
     pub fn symlink_target_path(&self) -> Option<&Path> {
         self.symlink_target.as_deref()
@@ -165,20 +366,78 @@ impl Node {
         self.dir_entry.path()
     }
 
-    /// Gets '`file_size`'.
+    /// Gets '`file_size`'. This is `None` if the entry couldn't be measured or if sizes aren't
+    /// being measured at all as per [`Context::measures_sizes`].
     pub const fn file_size(&self) -> Option<&FileSize> {
         self.file_size.as_ref()
     }
 
+    /// Whether or not the size of the [`Node`] is greater than `threshold` bytes.
+    pub fn exceeds(&self, threshold: u64) -> bool {
+        self.file_size()
+            .is_some_and(|size| size.value() > threshold)
+    }
+
     /// Sets `file_size`.
     pub fn set_file_size(&mut self, size: FileSize) {
         self.file_size = Some(size);
     }
 
+    /// Summary of children that were omitted from the output, if any.
+    pub const fn collapsed(&self) -> Option<&Collapsed> {
+        self.collapsed.as_ref()
+    }
+
+    /// Sets `collapsed`.
+    pub fn set_collapsed(&mut self, collapsed: Collapsed) {
+        self.collapsed = Some(collapsed);
+    }
+
+    /// The total amount of entries beneath [Node], recursively.
+    pub const fn descendants(&self) -> usize {
+        self.descendants
+    }
+
+    /// Sets `descendants`.
+    pub fn set_descendants(&mut self, descendants: usize) {
+        self.descendants = descendants;
+    }
+
+    /// The amount of directories beneath [Node], recursively.
+    pub const fn descendant_dirs(&self) -> usize {
+        self.descendant_dirs
+    }
+
+    /// Sets `descendant_dirs`.
+    pub fn set_descendant_dirs(&mut self, descendant_dirs: usize) {
+        self.descendant_dirs = descendant_dirs;
+    }
+
+    /// The fraction of the total size of the parent or root that [Node] accounts for as
+    /// determined by '--percent'.
+    pub const fn share(&self) -> Option<f64> {
+        self.share
+    }
+
+    /// Sets `share`.
+    pub fn set_share(&mut self, share: f64) {
+        self.share = Some(share);
+    }
+
+    /// Returns `true` if [Node] is a directory whose contents show up in the output, either as
+    /// children or as a summary of collapsed children.
+    pub fn has_visible_contents(&self, children: usize) -> bool {
+        self.is_dir() && (children > 0 || self.collapsed.is_some())
+    }
+
     /// Attempts to return an instance of [`FileMode`] for the display of symbolic permissions.
     #[cfg(unix)]
     pub fn mode(&self) -> Result<FileMode, Error> {
-        let permissions = self.metadata.permissions();
+        let permissions = self
+            .metadata
+            .as_ref()
+            .ok_or(Error::MissingMetadata)?
+            .permissions();
         let file_mode = permissions.try_mode_symbolic_notation()?;
         Ok(file_mode)
     }
@@ -189,6 +448,49 @@ impl Node {
         self.unix_attrs.has_xattrs
     }
 
+    /// Whether or not [Node] has an access control list.
+    #[cfg(unix)]
+    pub const fn has_acl(&self) -> bool {
+        self.unix_attrs.has_acl
+    }
+
+    /// Decoded entries of the access and default ACLs of [Node] as shown by `getfacl`, the latter
+    /// prefixed with `default:`. These are only needed for '--acl' at the time of rendering.
+    #[cfg(target_os = "linux")]
+    pub fn acl_entries(&self) -> Vec<String> {
+        use crate::fs::permissions::acl;
+
+        let decode = |name: &str| {
+            self.dir_entry
+                .xattr(OsStr::new(name))
+                .and_then(|value| acl::decode(&value))
+                .unwrap_or_default()
+        };
+
+        let access = decode(acl::ACCESS_XATTR)
+            .into_iter()
+            .map(|entry| entry.to_string());
+
+        let default = decode(acl::DEFAULT_XATTR)
+            .into_iter()
+            .map(|entry| format!("default:{entry}"));
+
+        access.chain(default).collect()
+    }
+
+    /// Returns the `SELinux` security context of the [`Node`].
+    #[cfg(target_os = "linux")]
+    pub fn security_context(&self) -> Option<&'static str> {
+        self.unix_attrs.security_context()
+    }
+
+    /// Queries the names and values of the extended attributes of [Node]. These aren't retained as
+    /// they're only needed for '--list-xattrs' at the time of rendering.
+    #[cfg(unix)]
+    pub fn xattrs(&self) -> Vec<xattr::Attr> {
+        self.dir_entry.xattrs()
+    }
+
     /// Returns the owner of the [`Node`].
     #[cfg(unix)]
     pub fn owner(&self) -> Option<&str> {
@@ -225,8 +527,49 @@ impl TryFrom<(DirEntry, &Context)> for Node {
         let path = dir_entry.path();
 
         let link_target = crate::fs::symlink_target(&dir_entry);
+        let broken_symlink = crate::fs::is_broken_symlink(&dir_entry);
+
+        if ctx.structure_only {
+            let indicator = match dir_entry.file_type() {
+                Some(ft) if ft.is_dir() => Indicator::Directory,
+                Some(ft) if ft.is_symlink() => Indicator::SymbolicLink,
+                _ => Indicator::RegularFile,
+            };
+
+            let style = get_ls_colors().ok().map(|ls_colors| {
+                ls_colors
+                    .style_for_indicator(indicator)
+                    .map_or_else(Style::default, LS_Style::to_ansi_term_style)
+            });
+
+            let node = Self::new(
+                dir_entry,
+                None,
+                None,
+                style,
+                link_target,
+                None,
+                #[cfg(unix)]
+                unix::Attrs::default(),
+            );
+
+            return Ok(node.with_broken_symlink(broken_symlink));
+        }
 
-        let metadata = dir_entry.metadata()?;
+        let metadata = match dir_entry.metadata() {
+            Ok(metadata) => metadata,
+
+            // The entry was removed after its parent directory was read, which is to be expected
+            // when scanning live systems, so it's kept around without any metadata.
+            Err(e)
+                if !dir_entry.path_is_symlink()
+                    && e.io_error()
+                        .is_some_and(|e| e.kind() == io::ErrorKind::NotFound) =>
+            {
+                return Ok(Self::vanished(dir_entry, link_target));
+            },
+            Err(e) => return Err(e.into()),
+        };
 
         let style = get_ls_colors().ok().map(|ls_colors| {
             ls_colors
@@ -238,33 +581,9 @@ impl TryFrom<(DirEntry, &Context)> for Node {
 
         let file_size = match file_type {
             Some(ref ft)
-                if !ctx.suppress_size && (ft.is_file() || ft.is_symlink() && !ctx.follow) =>
+                if ctx.measures_sizes() && (ft.is_file() || ft.is_symlink() && !ctx.follow) =>
             {
-                match ctx.disk_usage {
-                    DiskUsage::Logical => {
-                        let metric = byte::Metric::init_logical(&metadata, ctx.unit, ctx.human);
-                        Some(FileSize::Byte(metric))
-                    },
-                    DiskUsage::Physical => {
-                        let metric =
-                            byte::Metric::init_physical(path, &metadata, ctx.unit, ctx.human);
-                        Some(FileSize::Byte(metric))
-                    },
-                    DiskUsage::Line => {
-                        let metric = line_count::Metric::init(path);
-                        metric.map(FileSize::Line)
-                    },
-                    DiskUsage::Word => {
-                        let metric = word_count::Metric::init(path);
-                        metric.map(FileSize::Word)
-                    },
-
-                    #[cfg(unix)]
-                    DiskUsage::Block => {
-                        let metric = block::Metric::init(&metadata);
-                        Some(FileSize::Block(metric))
-                    },
-                }
+                Self::measure(path, &metadata, ctx)
             },
             _ => None,
         };
@@ -272,21 +591,61 @@ impl TryFrom<(DirEntry, &Context)> for Node {
         let inode = Inode::try_from(&metadata).ok();
 
         #[cfg(unix)]
-        let unix_attrs = if ctx.long {
+        let unix_attrs = if ctx.needs_unix_attrs() && ctx.numeric_uid_gid {
+            unix::Attrs::numeric(&metadata, &dir_entry)
+        } else if ctx.needs_unix_attrs() {
             unix::Attrs::from((&metadata, &dir_entry))
         } else {
             unix::Attrs::default()
         };
 
-        Ok(Self::new(
+        #[cfg(target_os = "linux")]
+        let unix_attrs = if ctx.needs_security_context() {
+            unix_attrs.with_security_context(&dir_entry)
+        } else {
+            unix_attrs
+        };
+
+        let node = Self::new(
             dir_entry,
-            metadata,
+            Some(metadata),
             file_size,
             style,
             link_target,
             inode,
             #[cfg(unix)]
             unix_attrs,
-        ))
+        )
+        .with_broken_symlink(broken_symlink);
+
+        #[cfg(target_os = "linux")]
+        let node = node.with_shared_extents(ctx);
+
+        Ok(node)
     }
 }
+
+#[test]
+fn vanished_after_readdir() {
+    use clap::Parser;
+
+    let tmp = tempfile::TempDir::new().unwrap();
+    let path = tmp.path().join("ulthar.txt");
+
+    std::fs::write(&path, "There are no cats in Ulthar").unwrap();
+
+    let dir_entry = ignore::WalkBuilder::new(tmp.path())
+        .build()
+        .filter_map(Result::ok)
+        .find(|dir_entry| dir_entry.path() == path)
+        .unwrap();
+
+    std::fs::remove_file(&path).unwrap();
+
+    let ctx = Context::parse_from(["erd", "--no-config"]);
+    let node = Node::try_from((dir_entry, &ctx)).unwrap();
+
+    assert!(node.is_vanished());
+    assert!(node.file_size().is_none());
+    assert!(node.inode().is_none());
+}
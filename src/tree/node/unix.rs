@@ -1,33 +1,93 @@
-use crate::fs::{ug::UserGroupInfo, xattr::ExtendedAttr};
+use crate::{
+    fs::{ug::UserGroupInfo, xattr::ExtendedAttr},
+    intern::Symbol,
+};
 use ignore::DirEntry;
 use std::{convert::From, fs::Metadata};
 
+#[cfg(target_os = "linux")]
+use std::ffi::OsStr;
+
 /// File attributes that are optionally computed and specific to Unix-like systems.
 #[derive(Default)]
 pub struct Attrs {
     pub has_xattrs: bool,
-    owner: Option<String>,
-    group: Option<String>,
+    pub has_acl: bool,
+    owner: Option<Symbol>,
+    group: Option<Symbol>,
+    #[cfg(target_os = "linux")]
+    security_context: Option<Symbol>,
 }
 
 impl Attrs {
     /// Constructor for [`Attrs`].
-    pub const fn new(has_xattrs: bool, owner: Option<String>, group: Option<String>) -> Self {
+    pub const fn new(has_xattrs: bool, owner: Option<Symbol>, group: Option<Symbol>) -> Self {
         Self {
             has_xattrs,
+            has_acl: false,
             owner,
             group,
+            #[cfg(target_os = "linux")]
+            security_context: None,
+        }
+    }
+
+    /// Reads the `SELinux` security context of `entry` from its `security.selinux` extended
+    /// attribute.
+    #[cfg(target_os = "linux")]
+    pub fn with_security_context(self, entry: &DirEntry) -> Self {
+        let security_context = entry.xattr(OsStr::new("security.selinux")).map(|value| {
+            let value = value.strip_suffix(b"\0").unwrap_or(&value);
+            Symbol::intern(&String::from_utf8_lossy(value))
+        });
+
+        Self {
+            security_context,
+            ..self
         }
     }
 
+    /// Returns the `SELinux` security context of the file.
+    #[cfg(target_os = "linux")]
+    pub fn security_context(&self) -> Option<&'static str> {
+        self.security_context.map(Symbol::as_str)
+    }
+
+    /// Checks whether or not `entry`, which is known to have extended attributes, has an access
+    /// control list beyond what its permissions express.
+    #[cfg(target_os = "linux")]
+    fn with_acl(self, entry: &DirEntry) -> Self {
+        use crate::fs::permissions::acl;
+
+        let has_acl = self.has_xattrs
+            && (entry.has_xattr(OsStr::new(acl::ACCESS_XATTR))
+                || entry.has_xattr(OsStr::new(acl::DEFAULT_XATTR)));
+
+        Self { has_acl, ..self }
+    }
+
     /// Returns the file owner.
-    pub fn owner(&self) -> Option<&str> {
-        self.owner.as_deref()
+    pub fn owner(&self) -> Option<&'static str> {
+        self.owner.map(Symbol::as_str)
     }
 
     /// Returns the file's group.
-    pub fn group(&self) -> Option<&str> {
-        self.group.as_deref()
+    pub fn group(&self) -> Option<&'static str> {
+        self.group.map(Symbol::as_str)
+    }
+}
+
+impl Attrs {
+    /// Initializes a [`Attrs`] that reports the owner and group by their numeric IDs.
+    pub fn numeric(md: &Metadata, entry: &DirEntry) -> Self {
+        let (owner, group) = md.owner_and_group_ids();
+
+        let attrs = Self::new(entry.has_xattrs(), Some(owner), Some(group));
+
+        #[cfg(target_os = "linux")]
+        let attrs = attrs.with_acl(entry);
+
+        attrs
     }
 }
 
@@ -36,10 +96,14 @@ impl From<(&Metadata, &DirEntry)> for Attrs {
     fn from((md, entry): (&Metadata, &DirEntry)) -> Self {
         let has_xattrs = entry.has_xattrs();
 
-        if let Ok((o, g)) = md.try_get_owner_and_group() {
-            return Self::new(has_xattrs, Some(o), Some(g));
-        }
+        let attrs = md.try_get_owner_and_group().map_or_else(
+            |_| Self::new(has_xattrs, None, None),
+            |(o, g)| Self::new(has_xattrs, Some(o), Some(g)),
+        );
+
+        #[cfg(target_os = "linux")]
+        let attrs = attrs.with_acl(entry);
 
-        Self::new(has_xattrs, None, None)
+        attrs
     }
 }
@@ -0,0 +1,69 @@
+use super::{count::FileCount, Tree};
+use crate::{context::Context, disk_usage::file_size::FileSize};
+use std::fmt::{self, Display};
+
+/// Grand totals of a [Tree] that are printed after the output when '--report' is used.
+pub struct Report {
+    total_size: Option<String>,
+    file_count: FileCount,
+    errors: usize,
+}
+
+impl Report {
+    /// Tallies up everything beneath the root of `tree` that traversal came across.
+    pub fn new(tree: &Tree, ctx: &Context) -> Self {
+        let arena = tree.arena();
+        let root_id = tree.root_id();
+
+        let total_size = ctx.measures_sizes().then(|| {
+            arena[root_id]
+                .get()
+                .file_size()
+                .map_or_else(|| FileSize::from(ctx).to_string(), ToString::to_string)
+        });
+
+        // Counted during traversal so that neither '--top' nor pruning skews them.
+        let scanned = tree.scanned();
+
+        let file_count = FileCount {
+            num_dirs: scanned.num_dirs,
+            num_files: scanned.num_files,
+            num_links: scanned.num_links,
+            ..FileCount::default()
+        };
+
+        Self {
+            total_size,
+            file_count,
+            errors: tree.errors(),
+        }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = |count: usize, noun: &str, nouns: &str| {
+            format!("{count} {}", if count == 1 { noun } else { nouns })
+        };
+
+        let FileCount {
+            num_dirs,
+            num_files,
+            num_links,
+            ..
+        } = self.file_count;
+
+        let mut components = vec![];
+
+        if let Some(ref total_size) = self.total_size {
+            components.push(format!("{total_size} total"));
+        }
+
+        components.push(plural(num_dirs, "directory", "directories"));
+        components.push(plural(num_files, "file", "files"));
+        components.push(plural(num_links, "symlink", "symlinks"));
+        components.push(plural(self.errors, "error", "errors"));
+
+        write!(f, "{}", components.join(", "))
+    }
+}
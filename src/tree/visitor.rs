@@ -1,32 +1,46 @@
 use std::sync::mpsc::Sender;
 
-use crate::{tree::node::Node, Context};
+use crate::{
+    tree::{cancel::Token, node::Node},
+    Context,
+};
 use ignore::{DirEntry, Error as IgnoreError, ParallelVisitor, ParallelVisitorBuilder, WalkState};
 
 pub enum TraversalState {
     Ongoing(Node),
+    Error,
     Done,
 }
 
 pub struct Branch<'a> {
     ctx: &'a Context,
     tx: Sender<TraversalState>,
+    cancel: &'a Token,
 }
 
 pub struct BranchVisitorBuilder<'a> {
     ctx: &'a Context,
     tx: Sender<TraversalState>,
+    cancel: &'a Token,
 }
 
 impl<'a> BranchVisitorBuilder<'a> {
-    pub fn new(ctx: &'a Context, tx: Sender<TraversalState>) -> Self {
-        Self { ctx, tx }
+    pub fn new(ctx: &'a Context, tx: Sender<TraversalState>, cancel: &'a Token) -> Self {
+        Self { ctx, tx, cancel }
     }
 }
 
 impl<'a> Branch<'a> {
-    pub fn new(ctx: &'a Context, tx: Sender<TraversalState>) -> Self {
-        Self { ctx, tx }
+    pub fn new(ctx: &'a Context, tx: Sender<TraversalState>, cancel: &'a Token) -> Self {
+        Self { ctx, tx, cancel }
+    }
+
+    /// Lets the receiving end know that an entry couldn't be read and skips it.
+    fn report_error(&self) -> WalkState {
+        if self.tx.send(TraversalState::Error).is_err() {
+            return WalkState::Quit;
+        }
+        WalkState::Skip
     }
 }
 
@@ -38,8 +52,12 @@ impl From<Node> for TraversalState {
 
 impl ParallelVisitor for Branch<'_> {
     fn visit(&mut self, entry: Result<DirEntry, IgnoreError>) -> WalkState {
+        if self.cancel.is_cancelled() {
+            return WalkState::Quit;
+        }
+
         let Ok(dir_entry) = entry else {
-            return WalkState::Skip;
+            return self.report_error();
         };
 
         match Node::try_from((dir_entry, self.ctx)) {
@@ -49,14 +67,14 @@ impl ParallelVisitor for Branch<'_> {
                 }
                 WalkState::Continue
             },
-            _ => WalkState::Skip,
+            _ => self.report_error(),
         }
     }
 }
 
 impl<'s> ParallelVisitorBuilder<'s> for BranchVisitorBuilder<'s> {
     fn build(&mut self) -> Box<dyn ParallelVisitor + 's> {
-        let visitor = Branch::new(self.ctx, self.tx.clone());
+        let visitor = Branch::new(self.ctx, self.tx.clone(), self.cancel);
         Box::new(visitor)
     }
 }
@@ -0,0 +1,85 @@
+use super::Tree;
+use crate::{context::Context, disk_usage::file_size::FileSize, fs::xdg::Category};
+use std::fmt::{self, Display};
+
+/// How much of a [Tree] falls into each XDG category, printed after the output when '--xdg' is
+/// used.
+pub struct Summary {
+    totals: Vec<(Category, FileSize)>,
+}
+
+impl Summary {
+    /// Tallies up the usage of every category beneath the root of `tree`. The outermost entry of a
+    /// category accounts for all of it, less any base directory of another category nested within.
+    pub fn new(tree: &Tree, ctx: &Context) -> Self {
+        let arena = tree.arena();
+        let root_id = tree.root_id();
+
+        let mut totals: Vec<(Category, FileSize)> = vec![];
+
+        for node_id in root_id.descendants(arena) {
+            let node = arena[node_id].get();
+            let category = Category::of(node.path());
+
+            let enclosing = if node_id == root_id {
+                None
+            } else {
+                node.parent_path().and_then(Category::of)
+            };
+
+            if category == enclosing {
+                continue;
+            }
+
+            let Some(file_size) = node.file_size() else {
+                continue;
+            };
+
+            if let Some(category) = category {
+                *Self::total(&mut totals, category, ctx) += file_size;
+            }
+
+            if let Some(enclosing) = enclosing {
+                *Self::total(&mut totals, enclosing, ctx) -= file_size;
+            }
+        }
+
+        totals.sort_by_key(|(category, _)| *category);
+
+        Self { totals }
+    }
+
+    /// Whether or not no entries fall into any category.
+    pub fn is_empty(&self) -> bool {
+        self.totals.is_empty()
+    }
+
+    /// The running total of `category`, starting from zero.
+    fn total<'a>(
+        totals: &'a mut Vec<(Category, FileSize)>,
+        category: Category,
+        ctx: &Context,
+    ) -> &'a mut FileSize {
+        let index = totals
+            .iter()
+            .position(|(c, _)| *c == category)
+            .unwrap_or_else(|| {
+                totals.push((category, FileSize::from(ctx)));
+                totals.len() - 1
+            });
+
+        &mut totals[index].1
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let components = self
+            .totals
+            .iter()
+            .map(|(category, file_size)| format!("{file_size} {category}"))
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", components.join(", "))
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    ffi::OsString,
+    fmt::{self, Display},
+};
+
+/// Version of erdtree as declared in the manifest.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The target triple erdtree was compiled for.
+const TARGET: &str = env!("ERDTREE_TARGET");
+
+/// Abbreviated hash of the commit erdtree was built from or `unknown` if it couldn't be determined.
+const GIT_COMMIT: &str = env!("ERDTREE_GIT_COMMIT");
+
+/// Cargo features enabled at compile-time.
+const FEATURES: &[&str] = &[
+    #[cfg(feature = "nix")]
+    "nix",
+    #[cfg(feature = "zfs")]
+    "zfs",
+    #[cfg(feature = "mimalloc")]
+    "mimalloc",
+    #[cfg(feature = "jemalloc")]
+    "jemalloc",
+];
+
+/// Platform-specific backends and whether or not this build supports them.
+const BACKENDS: &[(&str, bool)] = &[
+    ("xattr", cfg!(unix)),
+    ("acl", cfg!(target_os = "linux")),
+    ("btrfs", cfg!(target_os = "linux")),
+    (
+        "chflags",
+        cfg!(any(target_os = "macos", target_os = "freebsd")),
+    ),
+];
+
+/// Human-readable build information printed by `--version`.
+pub struct Info;
+
+/// Same as [`Info`] but formatted as a JSON document for `--version --json`.
+pub struct InfoJson;
+
+impl Display for Info {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let features = if FEATURES.is_empty() {
+            String::from("none")
+        } else {
+            FEATURES.join(", ")
+        };
+
+        let backends = BACKENDS
+            .iter()
+            .map(|(name, supported)| format!("{}{name}", if *supported { '+' } else { '-' }))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        writeln!(f, "erdtree {VERSION}")?;
+        writeln!(f, "commit:   {GIT_COMMIT}")?;
+        writeln!(f, "target:   {TARGET}")?;
+        writeln!(f, "features: {features}")?;
+        write!(f, "backends: {backends}")
+    }
+}
+
+impl Display for InfoJson {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let backends = BACKENDS
+            .iter()
+            .map(|(name, supported)| ((*name).to_owned(), serde_json::Value::Bool(*supported)))
+            .collect::<serde_json::Map<_, _>>();
+
+        let info = serde_json::json!({
+            "version": VERSION,
+            "commit": GIT_COMMIT,
+            "target": TARGET,
+            "features": FEATURES,
+            "backends": backends,
+        });
+
+        write!(f, "{info}")
+    }
+}
+
+/// Whether `args` ask for `--version` and if so whether or not as JSON. This is checked ahead of
+/// [`crate::context::Context::try_init`] so that build information is reported even when the rest
+/// of the arguments or the config file wouldn't pass validation.
+pub fn requested(args: impl Iterator<Item = OsString>) -> Option<bool> {
+    let mut version = false;
+    let mut json = false;
+
+    for arg in args.skip(1).take_while(|arg| arg != "--") {
+        match arg.to_str() {
+            Some("--version" | "-V") => version = true,
+            Some("--json") => json = true,
+            _ => (),
+        }
+    }
+
+    version.then_some(json)
+}
+
+#[test]
+fn requested_version() {
+    let args = |args: &[&str]| {
+        args.iter()
+            .map(OsString::from)
+            .collect::<Vec<_>>()
+            .into_iter()
+    };
+
+    assert_eq!(requested(args(&["erd", "--version"])), Some(false));
+    assert_eq!(requested(args(&["erd", "-V", "--json"])), Some(true));
+    assert_eq!(
+        requested(args(&["erd", "--sort-by", "size", "--version"])),
+        Some(false)
+    );
+    assert_eq!(requested(args(&["erd", "--json"])), None);
+    assert_eq!(requested(args(&["erd", "--", "--version"])), None);
+}
@@ -0,0 +1,24 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn aligned_sizes() {
+    assert_eq!(
+        utils::run_cmd(&["--aligned-sizes", "tests/data"]),
+        indoc!(
+            "┌─ cassildas_song.md .................................................  143 B
+┌─ the_yellow_king ......................................................  143 B
+├─ nylarlathotep.txt ....................................................  100 B
+├─ nemesis.txt ..........................................................  161 B
+├─ necronomicon.txt .....................................................   83 B
+│  ┌─ lipsum.txt ........................................................  446 B
+├─ lipsum ...............................................................  446 B
+│  ┌─ polaris.txt .......................................................  308 B
+├─ dream_cycle ..........................................................  308 B
+data .................................................................... 1241 B
+
+3 directories, 6 files"
+        )
+    )
+}
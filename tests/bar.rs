@@ -0,0 +1,24 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn bar() {
+    assert_eq!(
+        utils::run_cmd(&["--bar", "tests/data"]),
+        indoc!(
+            "143 B █▏            ┌─ cassildas_song.md
+ 143 B █▏         ┌─ the_yellow_king
+ 100 B ▊          ├─ nylarlathotep.txt
+ 161 B █▎         ├─ nemesis.txt
+  83 B ▋          ├─ necronomicon.txt
+ 446 B ███▋       │  ┌─ lipsum.txt
+ 446 B ███▋       ├─ lipsum
+ 308 B ██▌        │  ┌─ polaris.txt
+ 308 B ██▌        ├─ dream_cycle
+1241 B ██████████ data
+
+3 directories, 6 files"
+        )
+    )
+}
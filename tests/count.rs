@@ -0,0 +1,24 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn count() {
+    assert_eq!(
+        utils::run_cmd(&["--count", "tests/data"]),
+        indoc!(
+            "143 B    ┌─ cassildas_song.md
+ 143 B ┌─ the_yellow_king (1 file, 0 dirs)
+ 100 B ├─ nylarlathotep.txt
+ 161 B ├─ nemesis.txt
+  83 B ├─ necronomicon.txt
+ 446 B │  ┌─ lipsum.txt
+ 446 B ├─ lipsum (1 file, 0 dirs)
+ 308 B │  ┌─ polaris.txt
+ 308 B ├─ dream_cycle (1 file, 0 dirs)
+1241 B data (6 files, 3 dirs)
+
+3 directories, 6 files"
+        )
+    )
+}
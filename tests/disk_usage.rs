@@ -0,0 +1,163 @@
+use indoc::indoc;
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn disk_usage_both() {
+    let logical = utils::run_cmd(&["tests/data"]);
+    let both = utils::run_cmd(&["--disk-usage", "both", "tests/data"]);
+
+    assert_eq!(logical.lines().count(), both.lines().count());
+
+    // Physical sizes depend on the filesystem so only the logical column is compared verbatim.
+    for (logical_row, both_row) in logical.lines().zip(both.lines()) {
+        if logical_row.is_empty() || logical_row.ends_with("files") {
+            assert_eq!(logical_row, both_row);
+            continue;
+        }
+
+        let logical_fields = logical_row.split_whitespace().collect::<Vec<_>>();
+        let both_fields = both_row.split_whitespace().collect::<Vec<_>>();
+
+        assert_eq!(logical_fields[..2], both_fields[..2]);
+        assert!(both_fields[2].parse::<u64>().is_ok());
+        assert_eq!(both_fields[3], "B");
+        assert_eq!(logical_fields[2..], both_fields[4..]);
+    }
+}
+
+#[test]
+fn disk_usage_raw_grouped() {
+    assert_eq!(
+        utils::run_cmd(&["--unit", "raw-grouped", "tests/data"]),
+        indoc!(
+            "143    ┌─ cassildas_song.md
+  143 ┌─ the_yellow_king
+  100 ├─ nylarlathotep.txt
+  161 ├─ nemesis.txt
+   83 ├─ necronomicon.txt
+  446 │  ┌─ lipsum.txt
+  446 ├─ lipsum
+  308 │  ┌─ polaris.txt
+  308 ├─ dream_cycle
+1,241 data
+
+3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn disk_usage_unique_without_shared_extents() {
+    let physical = utils::run_cmd(&["--disk-usage", "physical", "tests/data"]);
+    let unique = utils::run_cmd(&["--disk-usage", "unique", "tests/data"]);
+
+    assert_eq!(physical, unique);
+}
+
+#[test]
+fn disk_usage_empty_dir() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+
+    fs::create_dir(tmp.path().join("carcosa"))?;
+    fs::write(tmp.path().join("hastur.txt"), "")?;
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let out = utils::run_cmd(&[&dir]);
+
+    assert_eq!(
+        out,
+        format!(
+            "0 B ┌─ hastur.txt\n0 B ├─ carcosa\n0 B {}\n\n1 directory, 1 file",
+            tmp.path().file_name().unwrap().to_string_lossy()
+        ),
+        "Empty directories should be reported as zero rather than unmeasured"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn disk_usage_unique_reflinked() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let original = tmp.path().join("original");
+    let copy = tmp.path().join("copy");
+
+    fs::write(&original, "0".repeat(1 << 20))?;
+
+    // Only copy-on-write filesystems such as btrfs and XFS can reflink.
+    let reflinked = std::process::Command::new("cp")
+        .arg("--reflink=always")
+        .arg(&original)
+        .arg(&copy)
+        .status()
+        .is_ok_and(|status| status.success());
+
+    if !reflinked {
+        return Ok(());
+    }
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let sizes = |disk_usage| {
+        utils::run_cmd(&["--disk-usage", disk_usage, "--unit", "raw", &dir])
+            .lines()
+            .take(3)
+            .map(|line| {
+                line.split_whitespace()
+                    .next()
+                    .unwrap()
+                    .parse::<u64>()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let physical = sizes("physical");
+    let unique = sizes("unique");
+
+    assert_eq!(
+        unique[2],
+        physical[2] / 2,
+        "Shared extents should count towards the total once"
+    );
+    assert_eq!(
+        unique[..2].iter().filter(|&&size| size == 0).count(),
+        1,
+        "Shared extents should be attributed to exactly one of the files sharing them"
+    );
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn disk_usage_size_states() -> Result<(), Box<dyn Error>> {
+    use std::os::unix::net::UnixListener;
+
+    let tmp = TempDir::new()?;
+
+    fs::write(tmp.path().join("hastur.txt"), "")?;
+    fs::write(tmp.path().join("yhtill.txt"), "carcosa")?;
+    let _listener = UnixListener::bind(tmp.path().join("lake.sock"))?;
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let root = tmp.path().file_name().unwrap().to_string_lossy();
+
+    assert_eq!(
+        utils::run_cmd(&[&dir]),
+        format!("7 B ┌─ yhtill.txt\n  - ├─ lake.sock\n0 B ├─ hastur.txt\n7 B {root}\n\n3 files"),
+        "Expected entries pending a measurement as '-' and empty files as '0 B'"
+    );
+
+    assert_eq!(
+        utils::run_cmd(&["--suppress-size", &dir]),
+        format!("┌─ yhtill.txt\n ├─ lake.sock\n ├─ hastur.txt\n {root}\n\n3 files"),
+        "Expected suppressed sizes to be left out rather than shown as placeholders"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use indoc::indoc;
+use std::{error::Error, fs, io::Write};
+use tempfile::{NamedTempFile, TempDir};
+
+mod utils;
+
+#[test]
+fn exclude_from() -> Result<(), Box<dyn Error>> {
+    let mut exclude_file = NamedTempFile::new()?;
+    writeln!(exclude_file, "*.txt")?;
+    writeln!(exclude_file, "!nemesis.txt")?;
+
+    let exclude_path = exclude_file.path().to_string_lossy().into_owned();
+
+    let out = utils::run_cmd(&["--exclude-from", &exclude_path, "--prune", "tests/data"]);
+
+    assert_eq!(
+        out,
+        indoc!(
+            "143 B    ┌─ cassildas_song.md
+             143 B ┌─ the_yellow_king
+             161 B ├─ nemesis.txt
+             304 B data
+
+             1 directory, 2 files"
+        ),
+        "Failed to exclude entries using patterns from file"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn exclude_from_anchored() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let nested = tmp.path().join("crates").join("target");
+
+    fs::create_dir_all(&nested)?;
+    fs::create_dir(tmp.path().join("target"))?;
+    fs::write(tmp.path().join("target").join("arkham.o"), "")?;
+    fs::write(nested.join("innsmouth.o"), "")?;
+
+    let mut exclude_file = NamedTempFile::new()?;
+    writeln!(exclude_file, "/target")?;
+
+    let exclude_path = exclude_file.path().to_string_lossy().into_owned();
+    let dir = tmp.path().to_string_lossy().into_owned();
+
+    let out = utils::run_cmd(&["--exclude-from", &exclude_path, "--suppress-size", &dir]);
+
+    assert_eq!(
+        out,
+        format!(
+            "┌─ innsmouth.o\n    ┌─ target\n ┌─ crates\n {}\n\n2 directories, 1 file",
+            tmp.path().file_name().unwrap().to_string_lossy()
+        ),
+        "Anchored patterns should only match relative to the directory being scanned"
+    );
+
+    Ok(())
+}
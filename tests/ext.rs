@@ -0,0 +1,50 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn ext() {
+    assert_eq!(
+        utils::run_cmd(&["--ext", "md", "tests/data"]),
+        indoc!(
+            "143 B    ┌─ cassildas_song.md
+143 B ┌─ the_yellow_king
+143 B data
+
+1 directory, 1 file"
+        )
+    );
+}
+
+#[test]
+fn ext_multiple() {
+    assert_eq!(
+        utils::run_cmd(&["--ext", "md,.TXT", "--level", "1", "tests/data"]),
+        indoc!(
+            "143 B ┌─ the_yellow_king
+ 100 B ├─ nylarlathotep.txt
+ 161 B ├─ nemesis.txt
+  83 B ├─ necronomicon.txt
+ 446 B ├─ lipsum
+ 308 B ├─ dream_cycle
+1241 B data
+
+3 directories, 6 files"
+        )
+    );
+}
+
+#[test]
+fn ext_with_pattern() {
+    assert_eq!(
+        utils::run_cmd(&["--ext", "txt", "--pattern", "^n", "tests/data"]),
+        indoc!(
+            "100 B ┌─ nylarlathotep.txt
+161 B ├─ nemesis.txt
+ 83 B ├─ necronomicon.txt
+344 B data
+
+3 files"
+        )
+    );
+}
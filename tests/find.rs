@@ -0,0 +1,30 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn find() {
+    assert_eq!(
+        utils::run_cmd(&["--find", "lipsum", "tests/data"]),
+        indoc!(
+            "446 B    ┌─ lipsum.txt
+ 446 B ┌─ lipsum
+1241 B data
+
+1 directory, 1 file"
+        )
+    )
+}
+
+#[test]
+fn find_fuzzy() {
+    assert_eq!(
+        utils::run_cmd(&["--find", "nmc", "--fuzzy", "tests/data"]),
+        indoc!(
+            "83 B ┌─ necronomicon.txt
+1241 B data
+
+1 file"
+        )
+    )
+}
@@ -0,0 +1,24 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn fixed_unit() {
+    assert_eq!(
+        utils::run_cmd(&["--unit", "fixed:KiB", "tests/data"]),
+        indoc!(
+            "0.1 KiB    ┌─ cassildas_song.md
+0.1 KiB ┌─ the_yellow_king
+0.1 KiB ├─ nylarlathotep.txt
+0.2 KiB ├─ nemesis.txt
+0.1 KiB ├─ necronomicon.txt
+0.4 KiB │  ┌─ lipsum.txt
+0.4 KiB ├─ lipsum
+0.3 KiB │  ┌─ polaris.txt
+0.3 KiB ├─ dream_cycle
+1.2 KiB data
+
+3 directories, 6 files"
+        )
+    )
+}
@@ -0,0 +1,39 @@
+#![cfg(any(target_os = "macos", target_os = "freebsd"))]
+
+use std::{error::Error, fs, process::Command};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn flags_prunes_directories_without_matches() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let kept = tmp.path().join("kadath");
+    let empty = tmp.path().join("leng");
+
+    fs::create_dir(&kept)?;
+    fs::create_dir(&empty)?;
+    fs::write(kept.join("onyx_castle.txt"), "")?;
+    fs::write(empty.join("plateau.txt"), "")?;
+
+    let status = Command::new("chflags")
+        .arg("hidden")
+        .arg(kept.join("onyx_castle.txt"))
+        .status()?;
+
+    assert!(status.success(), "Failed to set the hidden flag");
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let out = utils::run_cmd(&["--flags", "hidden", "--suppress-size", &dir]);
+
+    assert!(
+        out.contains("onyx_castle.txt") && out.contains("kadath"),
+        "Expected the flagged file and its parent: {out}"
+    );
+    assert!(
+        !out.contains("leng") && !out.contains("plateau.txt"),
+        "Expected the directory without matches to be pruned: {out}"
+    );
+
+    Ok(())
+}
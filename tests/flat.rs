@@ -1,4 +1,5 @@
 use indoc::indoc;
+use std::process::{Command, Stdio};
 
 mod utils;
 
@@ -60,3 +61,120 @@ fn flat_with_level() {
         )
     )
 }
+
+#[test]
+fn flat_sort_by() {
+    assert_eq!(
+        utils::run_cmd(&["--layout", "flat", "--sort-by", "size,path", "tests/data"]),
+        indoc!(
+            "83 B   necronomicon.txt
+ 100 B   nylarlathotep.txt
+ 143 B   the_yellow_king/cassildas_song.md
+ 143 B   the_yellow_king
+ 161 B   nemesis.txt
+ 308 B   dream_cycle/polaris.txt
+ 308 B   dream_cycle
+ 446 B   lipsum/lipsum.txt
+ 446 B   lipsum
+1241 B   data
+
+3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn flat_group_by() {
+    assert_eq!(
+        utils::run_cmd(&["--layout", "flat", "--group-by", "ext", "tests/data"]),
+        indoc!(
+            "308 B   dream_cycle/polaris.txt
+ 100 B   nylarlathotep.txt
+ 161 B   nemesis.txt
+  83 B   necronomicon.txt
+ 446 B   lipsum/lipsum.txt
+[ext: txt] 1098 B
+ 143 B   the_yellow_king/cassildas_song.md
+[ext: md] 143 B
+ 143 B   the_yellow_king
+ 446 B   lipsum
+ 308 B   dream_cycle
+[directories]
+1241 B   data
+
+3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn flat_group_by_ext_directories() -> Result<(), Box<dyn std::error::Error>> {
+    use std::fs;
+    use tempfile::TempDir;
+
+    let tmp = TempDir::new()?;
+
+    fs::create_dir(tmp.path().join("kadath"))?;
+    fs::write(tmp.path().join("kadath").join("onyx.txt"), "onyx")?;
+    fs::write(tmp.path().join("LICENSE"), "ulthar")?;
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let out = utils::run_cmd(&["--layout", "iflat", "--group-by", "ext", &dir]);
+
+    let headings = out
+        .lines()
+        .filter(|line| line.starts_with('['))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        headings,
+        ["[directories]", "[ext: -] 6 B", "[ext: txt] 4 B"],
+        "Expected directories apart from files without an extension and without a subtotal"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sort_by_requires_flat() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "--no-config",
+            "--sort-by",
+            "size",
+            "tests/data",
+        ])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(stderr.contains("'--sort-by' only applies to '--layout flat' and '--layout iflat'"));
+}
+
+#[test]
+fn group_by_requires_flat() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "--no-config",
+            "--group-by",
+            "ext",
+            "tests/data",
+        ])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(stderr.contains("'--group-by' only applies to '--layout flat' and '--layout iflat'"));
+}
@@ -36,3 +36,38 @@ fn hardlink() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[cfg(unix)]
+#[test]
+fn hardlink_nlink() -> Result<(), Box<dyn Error>> {
+    use tempfile::TempDir;
+
+    let tmp = TempDir::new()?;
+
+    fs::write(tmp.path().join("kadath.txt"), "")?;
+    fs::write(tmp.path().join("ulthar.txt"), "")?;
+    fs::hard_link(tmp.path().join("kadath.txt"), tmp.path().join("curwin.hpl"))?;
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let out = utils::run_cmd(&["--long", "--nlink", "--parseable", "--suppress-size", &dir]);
+
+    let nlinks = out
+        .lines()
+        .filter(|row| row.ends_with(".txt") || row.ends_with(".hpl"))
+        .map(|row| {
+            let fields = row.split_whitespace().collect::<Vec<_>>();
+            (fields[fields.len() - 1], fields[1])
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        nlinks,
+        [
+            ("ulthar.txt", "1"),
+            ("kadath.txt", "2"),
+            ("curwin.hpl", "2")
+        ]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+#![cfg(unix)]
+
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn header() {
+    assert_eq!(
+        utils::run_cmd(&["--long=size", "--header", "--level", "1", "tests/data"]),
+        indoc!(
+            "Size Name
+ 143 B ┌─ the_yellow_king
+ 100 B ├─ nylarlathotep.txt
+ 161 B ├─ nemesis.txt
+  83 B ├─ necronomicon.txt
+ 446 B ├─ lipsum
+ 308 B ├─ dream_cycle
+1241 B data
+
+3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn header_flat() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--long=size",
+            "--header",
+            "--layout",
+            "flat",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        indoc!(
+            "Size   Path
+ 143 B   the_yellow_king
+ 100 B   nylarlathotep.txt
+ 161 B   nemesis.txt
+  83 B   necronomicon.txt
+ 446 B   lipsum
+ 308 B   dream_cycle
+1241 B   data
+
+3 directories, 6 files"
+        )
+    )
+}
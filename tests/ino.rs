@@ -0,0 +1,31 @@
+#![cfg(unix)]
+
+mod utils;
+
+#[test]
+fn ino() {
+    use std::os::unix::fs::MetadataExt;
+
+    let out = utils::run_cmd(&["--inodes", "--level", "1", "tests/data"]);
+
+    let rows = out.lines().take_while(|line| !line.is_empty());
+
+    for row in rows {
+        let fields = row.split_whitespace().collect::<Vec<_>>();
+        let name = fields[fields.len() - 1];
+
+        let path = if name == "data" {
+            String::from("tests/data")
+        } else {
+            format!("tests/data/{name}")
+        };
+
+        let ino = std::fs::symlink_metadata(&path).unwrap().ino();
+
+        assert_eq!(
+            fields[0],
+            ino.to_string(),
+            "Expected the inode number of {path}: {row}"
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn inode_count() {
+    assert_eq!(
+        utils::run_cmd(&["--disk-usage", "inodes", "tests/data"]),
+        indoc!(
+            "1    ┌─ cassildas_song.md
+ 2 ┌─ the_yellow_king
+ 1 ├─ nylarlathotep.txt
+ 1 ├─ nemesis.txt
+ 1 ├─ necronomicon.txt
+ 1 │  ┌─ lipsum.txt
+ 2 ├─ lipsum
+ 1 │  ┌─ polaris.txt
+ 2 ├─ dream_cycle
+10 data
+
+3 directories, 6 files"
+        )
+    )
+}
@@ -0,0 +1,249 @@
+use indoc::indoc;
+use std::process::{Command, Stdio};
+
+mod utils;
+
+/// Removes the `id` and `mtime` fields from each record as they differ from machine to machine.
+fn without_ids(output: &str) -> String {
+    output
+        .lines()
+        .map(|line| without_field(&without_field(line, "id"), "mtime"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Removes the string or null valued `field` from the record `line`.
+fn without_field(line: &str, field: &str) -> String {
+    let key = format!(",\"{field}\":");
+
+    let Some(start) = line.find(&key) else {
+        return line.to_owned();
+    };
+
+    let value = &line[start + key.len()..];
+
+    let len = match value.strip_prefix('"') {
+        Some(quoted) => quoted.find('"').unwrap() + 2,
+        None => value.find([',', '}']).unwrap(),
+    };
+
+    format!("{}{}", &line[..start], &value[len..])
+}
+
+#[test]
+fn jsonl() {
+    assert_eq!(
+        without_ids(&utils::run_cmd(&[
+            "--output",
+            "jsonl",
+            "--level",
+            "1",
+            "tests/data"
+        ])),
+        indoc!(
+            r#"
+            {"type":"dir","path":"dream_cycle","name":"dream_cycle","depth":1,"size":308}
+            {"type":"dir","path":"lipsum","name":"lipsum","depth":1,"size":446}
+            {"type":"file","path":"necronomicon.txt","name":"necronomicon.txt","depth":1,"size":83}
+            {"type":"file","path":"nemesis.txt","name":"nemesis.txt","depth":1,"size":161}
+            {"type":"file","path":"nylarlathotep.txt","name":"nylarlathotep.txt","depth":1,"size":100}
+            {"type":"dir","path":"the_yellow_king","name":"the_yellow_king","depth":1,"size":143}
+            {"type":"dir","path":".","name":"data","depth":0,"size":1241}
+            {"type":"summary","dirs":3,"files":3,"links":0,"size":1241}"#
+        ),
+        "Failed to print one record per entry followed by a summary"
+    )
+}
+
+#[test]
+#[cfg(unix)]
+fn jsonl_id() -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::unix::fs::MetadataExt;
+
+    let md = std::fs::metadata("tests/data/nemesis.txt")?;
+    let id = format!("\"id\":\"{}:{}\"", md.dev(), md.ino());
+
+    let stdout = utils::run_cmd(&["--output", "jsonl", "tests/data"]);
+
+    assert!(
+        stdout
+            .lines()
+            .any(|line| line.contains(&id) && line.contains("\"path\":\"nemesis.txt\"")),
+        "Expected the device and inode number as the id of nemesis.txt: {stdout}"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn jsonl_id_hardlink() -> Result<(), Box<dyn std::error::Error>> {
+    use std::{fs, os::unix::fs::MetadataExt};
+
+    let tmp = tempfile::TempDir::new()?;
+
+    fs::write(tmp.path().join("kadath.txt"), "onyx")?;
+    fs::hard_link(tmp.path().join("kadath.txt"), tmp.path().join("ulthar.txt"))?;
+
+    let md = fs::metadata(tmp.path().join("kadath.txt"))?;
+    let prefix = format!("{}:{}:", md.dev(), md.ino());
+
+    let stdout = utils::run_cmd(&["--output", "jsonl", &tmp.path().to_string_lossy()]);
+
+    let ids = stdout
+        .lines()
+        .filter(|line| line.contains("\"type\":\"file\""))
+        .map(|line| {
+            let id = line.split_once("\"id\":\"").unwrap().1;
+            id.split_once('"').unwrap().0
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(ids.len(), 2, "Expected a record per hard link: {stdout}");
+    assert_ne!(
+        ids[0], ids[1],
+        "Expected hard links to have ids of their own"
+    );
+    assert!(
+        ids.iter().all(|id| id.starts_with(&prefix)),
+        "Expected the device and inode number in the id of each hard link: {stdout}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn jsonl_mtime() -> Result<(), Box<dyn std::error::Error>> {
+    use chrono::{DateTime, FixedOffset, SecondsFormat, Utc};
+
+    let modified = std::fs::metadata("tests/data/nemesis.txt")?.modified()?;
+    let modified = DateTime::<Utc>::from(modified);
+
+    let offset = modified
+        .with_timezone(&FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap())
+        .to_rfc3339_opts(SecondsFormat::Secs, true);
+
+    // Whether or not daylight saving time applies depends on when the file was modified.
+    let zoned = modified
+        .with_timezone(&chrono_tz::America::New_York)
+        .to_rfc3339_opts(SecondsFormat::Secs, true);
+
+    for (tz, mtime) in [("+05:30", offset), ("America/New_York", zoned)] {
+        let stdout = utils::run_cmd(&["--output", "jsonl", "--timezone", tz, "tests/data"]);
+
+        assert!(
+            stdout
+                .lines()
+                .any(|line| line.contains("\"path\":\"nemesis.txt\"")
+                    && line.ends_with(&format!(",\"mtime\":\"{mtime}\"}}"))),
+            "Expected the time of last modification in {tz}: {stdout}"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn jsonl_omits_report() {
+    let stdout = utils::run_cmd(&["--output", "jsonl", "--report", "tests/data"]);
+
+    assert!(
+        stdout.lines().all(|line| line.starts_with('{')),
+        "Expected nothing but records: {stdout}"
+    );
+}
+
+#[test]
+fn jsonl_streams_directories_after_their_contents() {
+    assert_eq!(
+        without_ids(&utils::run_cmd(&[
+            "--output",
+            "jsonl",
+            "tests/data/the_yellow_king"
+        ])),
+        indoc!(
+            r#"
+            {"type":"file","path":"cassildas_song.md","name":"cassildas_song.md","depth":1,"size":143}
+            {"type":"dir","path":".","name":"the_yellow_king","depth":0,"size":143}
+            {"type":"summary","dirs":0,"files":1,"links":0,"size":143}"#
+        ),
+        "Failed to write the record of a directory after those of its contents"
+    )
+}
+
+#[test]
+fn jsonl_output_file() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = tempfile::TempDir::new()?;
+    let path = tmp.path().join("records.jsonl");
+
+    let stdout = utils::run_cmd(&[
+        "--output",
+        "jsonl",
+        "--output-file",
+        &path.to_string_lossy(),
+        "tests/data",
+    ]);
+
+    assert!(stdout.is_empty(), "Expected nothing on stdout: {stdout}");
+
+    assert_eq!(
+        without_ids(std::fs::read_to_string(&path)?.trim()),
+        without_ids(&utils::run_cmd(&["--output", "jsonl", "tests/data"])),
+        "Expected the records in the file"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn jsonl_rejects_reshaping() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "--no-config",
+            "--output",
+            "jsonl",
+            "--dirs-only",
+            "tests/data",
+        ])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(stderr.contains("'--dirs-only' can't be used with '--output jsonl'"));
+}
+
+#[test]
+fn jsonl_pattern() {
+    let text = utils::run_cmd(&["--pattern", "cassildas*", "tests/data"]);
+    let jsonl = utils::run_cmd(&["--output", "jsonl", "--pattern", "cassildas*", "tests/data"]);
+
+    let listed = text
+        .lines()
+        .take_while(|line| !line.is_empty())
+        .map(|line| line.rsplit(' ').next().unwrap())
+        .collect::<Vec<_>>();
+
+    let recorded = jsonl
+        .lines()
+        .filter_map(|line| {
+            let name = line.split_once(",\"name\":\"")?.1;
+            name.split_once('"').map(|(name, _)| name)
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        listed,
+        ["cassildas_song.md", "the_yellow_king", "data"],
+        "Expected directories without matches to be pruned"
+    );
+    assert_eq!(
+        recorded, listed,
+        "Expected the same entries in records as in the tree"
+    );
+}
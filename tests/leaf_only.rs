@@ -0,0 +1,72 @@
+use indoc::{formatdoc, indoc};
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn leaf_only() {
+    assert_eq!(
+        utils::run_cmd(&["--leaf-only", "tests/data"]),
+        indoc!(
+            "308 B ┌─ polaris.txt
+ 100 B ├─ nylarlathotep.txt
+ 161 B ├─ nemesis.txt
+  83 B ├─ necronomicon.txt
+ 446 B ├─ lipsum.txt
+ 143 B ├─ cassildas_song.md
+1241 B data
+
+6 files"
+        )
+    )
+}
+
+#[test]
+fn leaf_only_paths() {
+    assert_eq!(
+        utils::run_cmd(&["--leaf-only", "--leaf-paths", "tests/data"]),
+        indoc!(
+            "308 B ┌─ dream_cycle/polaris.txt
+ 100 B ├─ nylarlathotep.txt
+ 161 B ├─ nemesis.txt
+  83 B ├─ necronomicon.txt
+ 446 B ├─ lipsum/lipsum.txt
+ 143 B ├─ the_yellow_king/cassildas_song.md
+1241 B data
+
+6 files"
+        )
+    )
+}
+
+#[test]
+fn leaf_only_sorted() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let x = tmp.path().join("a").join("x");
+    let y = tmp.path().join("b").join("y");
+
+    fs::create_dir_all(&x)?;
+    fs::create_dir_all(&y)?;
+    fs::write(x.join("big"), "0".repeat(9000))?;
+    fs::write(y.join("small"), "0".repeat(100))?;
+    fs::write(y.join("huge"), "0".repeat(20000))?;
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+
+    assert_eq!(
+        utils::run_cmd(&["--leaf-only", "--sort", "size", &dir]),
+        formatdoc!(
+            "100 B ┌─ small
+             9000 B ├─ big
+            20000 B ├─ huge
+            29100 B {}
+
+            3 files",
+            tmp.path().file_name().unwrap().to_string_lossy()
+        ),
+        "Failed to sort leaves across the directories they were gathered from"
+    );
+
+    Ok(())
+}
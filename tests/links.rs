@@ -0,0 +1,28 @@
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn links() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let src = tmp.path().join("kadath.txt");
+
+    fs::write(&src, "The dream-quest of unknown Kadath\n")?;
+    fs::write(tmp.path().join("ulthar.txt"), "The cats of Ulthar\n")?;
+    fs::hard_link(&src, tmp.path().join("curwin.hpl"))?;
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let out = utils::run_cmd(&["--links", ">1", "--suppress-size", &dir]);
+
+    assert_eq!(
+        out,
+        format!(
+            "┌─ kadath.txt\n ├─ curwin.hpl\n {}\n\n2 files",
+            tmp.path().file_name().unwrap().to_string_lossy()
+        ),
+        "Failed to filter by amount of hard links"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn mime() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let images = tmp.path().join("images");
+
+    fs::create_dir(&images)?;
+    fs::write(images.join("pic"), b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR")?;
+    fs::write(tmp.path().join("notes.txt"), "not an image")?;
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let out = utils::run_cmd(&["--mime", "image/*", "--suppress-size", &dir]);
+
+    assert_eq!(
+        out,
+        format!(
+            "┌─ pic\n ┌─ images\n {}\n\n1 directory, 1 file",
+            tmp.path().file_name().unwrap().to_string_lossy()
+        ),
+        "Failed to filter by MIME type"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use indoc::{formatdoc, indoc};
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn min_depth() {
+    assert_eq!(
+        utils::run_cmd(&["--min-depth", "2", "tests/data"]),
+        indoc!(
+            "308 B ┌─ dream_cycle/polaris.txt
+ 446 B ├─ lipsum/lipsum.txt
+ 143 B ├─ the_yellow_king/cassildas_song.md
+1241 B data
+
+3 files"
+        )
+    )
+}
+
+#[test]
+fn min_depth_flat() {
+    assert_eq!(
+        utils::run_cmd(&["--min-depth", "2", "--layout", "flat", "tests/data"]),
+        indoc!(
+            "308 B   dream_cycle/polaris.txt
+ 446 B   lipsum/lipsum.txt
+ 143 B   the_yellow_king/cassildas_song.md
+1241 B   data
+
+3 files"
+        )
+    )
+}
+
+#[test]
+fn min_depth_sorted() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let x = tmp.path().join("a").join("x");
+    let y = tmp.path().join("b").join("y");
+
+    fs::create_dir_all(&x)?;
+    fs::create_dir_all(&y)?;
+    fs::write(x.join("big"), "0".repeat(9000))?;
+    fs::write(y.join("small"), "0".repeat(100))?;
+    fs::write(y.join("huge"), "0".repeat(20000))?;
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+
+    assert_eq!(
+        utils::run_cmd(&["--min-depth", "3", "--sort", "size", &dir]),
+        formatdoc!(
+            "100 B ┌─ b/y/small
+             9000 B ├─ a/x/big
+            20000 B ├─ b/y/huge
+            29100 B {}
+
+            3 files",
+            tmp.path().file_name().unwrap().to_string_lossy()
+        ),
+        "Failed to sort entries across the directories they were gathered from"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use indoc::formatdoc;
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn no_artifacts() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let node_modules = tmp.path().join("node_modules");
+    let src = tmp.path().join("src");
+
+    fs::create_dir_all(node_modules.join("pkg"))?;
+    fs::create_dir(&src)?;
+    fs::write(node_modules.join("pkg").join("index.js"), "hi\n")?;
+    fs::write(node_modules.join("a.js"), "x\n")?;
+    fs::write(src.join("main.rs"), "y\n")?;
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let out = utils::run_cmd(&["--no-artifacts", &dir]);
+
+    assert_eq!(
+        out,
+        formatdoc!(
+            "2 B    ┌─ main.rs
+            2 B ┌─ src
+                │  ┌─ … 2 more (5 B)
+            5 B ├─ node_modules
+            7 B {}
+
+            2 directories, 1 file",
+            tmp.path().file_name().unwrap().to_string_lossy()
+        ),
+        "Failed to collapse the contents of artifact directories"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn no_artifacts_prune() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let target = tmp.path().join("target");
+
+    fs::create_dir_all(&target)?;
+    fs::create_dir(tmp.path().join("empty"))?;
+    fs::write(target.join("erd"), "0123456789")?;
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let out = utils::run_cmd(&["--no-artifacts", "--prune", &dir]);
+
+    assert_eq!(
+        out,
+        formatdoc!(
+            "┌─ … 1 more (10 B)
+            10 B ┌─ target
+            10 B {}
+
+            1 directory",
+            tmp.path().file_name().unwrap().to_string_lossy()
+        ),
+        "Failed to keep collapsed artifact directories when pruning"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn only_ignored() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+
+    fs::create_dir(tmp.path().join(".git"))?;
+    fs::create_dir(tmp.path().join("target"))?;
+    fs::create_dir(tmp.path().join("src"))?;
+    fs::write(tmp.path().join(".gitignore"), "target/\n*.log\n")?;
+    fs::write(tmp.path().join("target").join("arkham.o"), "")?;
+    fs::write(tmp.path().join("src").join("innsmouth.rs"), "")?;
+    fs::write(tmp.path().join("src").join("dunwich.log"), "")?;
+    fs::write(tmp.path().join("kingsport.md"), "")?;
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let out = utils::run_cmd(&["--only-ignored", "--suppress-size", &dir]);
+
+    assert_eq!(
+        out,
+        format!(
+            "┌─ arkham.o\n ┌─ target\n │  ┌─ dunwich.log\n ├─ src\n {}\n\n2 directories, 2 files",
+            tmp.path().file_name().unwrap().to_string_lossy()
+        ),
+        "Failed to only show ignored files"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn only_ignored_nested() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let src = tmp.path().join("src");
+
+    fs::create_dir(tmp.path().join(".git"))?;
+    fs::create_dir(&src)?;
+    fs::write(tmp.path().join(".gitignore"), "*.log\n")?;
+    fs::write(src.join(".gitignore"), "!dunwich.log\n")?;
+    fs::write(src.join("dunwich.log"), "")?;
+    fs::write(src.join("arkham.log"), "")?;
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let out = utils::run_cmd(&["--only-ignored", "--suppress-size", &dir]);
+
+    assert_eq!(
+        out,
+        format!(
+            "┌─ arkham.log\n ┌─ src\n {}\n\n1 directory, 1 file",
+            tmp.path().file_name().unwrap().to_string_lossy()
+        ),
+        "Nested ignore files should take precedence over those above them"
+    );
+
+    Ok(())
+}
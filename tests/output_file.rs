@@ -0,0 +1,24 @@
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn output_file() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let path = tmp.path().join("report.txt");
+
+    fs::write(&path, "stale")?;
+
+    let stdout = utils::run_cmd(&["--output-file", &path.to_string_lossy(), "tests/data"]);
+
+    assert!(stdout.is_empty(), "Expected nothing on stdout: {stdout}");
+
+    assert_eq!(
+        fs::read_to_string(&path)?.trim(),
+        utils::run_cmd(&["tests/data"]),
+        "Expected the output in the file in place of its previous contents"
+    );
+
+    Ok(())
+}
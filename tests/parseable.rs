@@ -0,0 +1,196 @@
+#![cfg(unix)]
+
+mod utils;
+
+#[test]
+fn parseable() {
+    let out = utils::run_cmd(&["--long", "--parseable", "tests/data"]);
+
+    let mut paths = vec![];
+
+    // Every line is a row, with neither a blank line nor a tally of directories and files.
+    for row in out.lines() {
+        let fields = row.split(' ').collect::<Vec<_>>();
+
+        assert_eq!(
+            fields.len(),
+            5,
+            "Expected exactly five fields separated by single spaces: {row:?}"
+        );
+        assert_eq!(fields[0].len(), 10, "Expected permissions first: {row:?}");
+        assert!(
+            fields[2].contains('T'),
+            "Expected a timestamp third: {row:?}"
+        );
+        assert!(fields[3].ends_with('B'), "Expected a size fourth: {row:?}");
+
+        paths.push(fields[4]);
+    }
+
+    assert_eq!(
+        paths,
+        [
+            "the_yellow_king/cassildas_song.md",
+            "the_yellow_king",
+            "nylarlathotep.txt",
+            "nemesis.txt",
+            "necronomicon.txt",
+            "lipsum/lipsum.txt",
+            "lipsum",
+            "dream_cycle/polaris.txt",
+            "dream_cycle",
+            "data",
+        ],
+        "Expected paths relative to the root without tree prefixes"
+    );
+}
+
+#[test]
+fn parseable_header() {
+    let out = utils::run_cmd(&[
+        "--long",
+        "--header",
+        "--parseable",
+        "--level",
+        "0",
+        "tests/data",
+    ]);
+
+    let mut rows = out.lines();
+
+    assert_eq!(rows.next(), Some("Mode Owner Date Size Path"));
+    assert_eq!(rows.next().map(|row| row.split(' ').count()), Some(5));
+}
+
+#[test]
+fn parseable_suppress_size() {
+    let out = utils::run_cmd(&["--long", "--parseable", "--suppress-size", "tests/data"]);
+
+    for row in out.lines() {
+        assert_eq!(
+            row.split(' ').count(),
+            4,
+            "Expected no size field when sizes are suppressed: {row:?}"
+        );
+    }
+}
+
+#[test]
+fn parseable_numeric_uid_gid() {
+    use std::os::unix::fs::MetadataExt;
+
+    let out = utils::run_cmd(&[
+        "--long",
+        "--group",
+        "--numeric-uid-gid",
+        "--parseable",
+        "--suppress-size",
+        "--level",
+        "0",
+        "tests/data",
+    ]);
+
+    let metadata = std::fs::metadata("tests/data").unwrap();
+    let fields = out.split_whitespace().collect::<Vec<_>>();
+
+    assert_eq!(fields[1], metadata.uid().to_string());
+    assert_eq!(fields[2], metadata.gid().to_string());
+}
+
+#[test]
+fn parseable_ctime() {
+    use chrono::{DateTime, Local};
+    use std::{
+        os::unix::fs::MetadataExt,
+        time::{Duration, SystemTime},
+    };
+
+    let out = utils::run_cmd(&[
+        "--long",
+        "--time",
+        "ctime",
+        "--parseable",
+        "--suppress-size",
+        "--level",
+        "0",
+        "tests/data",
+    ]);
+
+    let metadata = std::fs::metadata("tests/data").unwrap();
+    let ctime = SystemTime::UNIX_EPOCH
+        + Duration::new(metadata.ctime() as u64, metadata.ctime_nsec() as u32);
+    let expected = DateTime::<Local>::from(ctime).format("%Y-%m-%dT%H:%M:%S%Z");
+
+    let fields = out.split_whitespace().collect::<Vec<_>>();
+
+    assert_eq!(fields[2], expected.to_string());
+}
+
+#[test]
+fn parseable_selected_columns() {
+    use std::os::unix::fs::MetadataExt;
+
+    let out = utils::run_cmd(&[
+        "--long=nlink,size",
+        "--parseable",
+        "--level",
+        "0",
+        "tests/data",
+    ]);
+
+    let metadata = std::fs::metadata("tests/data").unwrap();
+    let nlink = metadata.nlink().to_string();
+
+    let fields = out.lines().next().unwrap().split(' ').collect::<Vec<_>>();
+
+    assert_eq!(
+        fields,
+        [nlink.as_str(), "1241B", "data"],
+        "Expected only the selected columns in order with disk usage among them"
+    );
+}
+
+#[test]
+fn parseable_timezone() {
+    for (args, offset) in [
+        (&["--timezone", "+05:30"][..], "+05:30"),
+        (&["--timezone", "-0800"][..], "-08:00"),
+        (&["--timezone", "Asia/Kolkata"][..], "+05:30"),
+        (&["--utc"][..], "+00:00"),
+    ] {
+        let out = utils::run_cmd(
+            &[
+                &["--long", "--parseable", "--suppress-size"][..],
+                args,
+                &["tests/data"],
+            ]
+            .concat(),
+        );
+
+        for row in out.lines() {
+            let timestamp = row.split_whitespace().nth(2).unwrap();
+
+            assert!(
+                timestamp.ends_with(offset),
+                "Expected a timestamp at {offset}: {row}"
+            );
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn parseable_device_numbers() {
+    let out = utils::run_cmd(&["--long", "--parseable", "--level", "1", "/dev"]);
+
+    let null = out
+        .lines()
+        .find(|line| line.ends_with(" null"))
+        .expect("Expected /dev/null to be listed");
+
+    assert_eq!(
+        null.split_whitespace().nth(3),
+        Some("1,3"),
+        "Expected the major and minor numbers of /dev/null in place of its size: {null}"
+    );
+}
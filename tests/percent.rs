@@ -0,0 +1,45 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn percent_of_parent() {
+    assert_eq!(
+        utils::run_cmd(&["--percent", "parent", "tests/data"]),
+        indoc!(
+            "143 B    ┌─ cassildas_song.md (100.0%)
+ 143 B ┌─ the_yellow_king (11.5%)
+ 100 B ├─ nylarlathotep.txt (8.1%)
+ 161 B ├─ nemesis.txt (13.0%)
+  83 B ├─ necronomicon.txt (6.7%)
+ 446 B │  ┌─ lipsum.txt (100.0%)
+ 446 B ├─ lipsum (35.9%)
+ 308 B │  ┌─ polaris.txt (100.0%)
+ 308 B ├─ dream_cycle (24.8%)
+1241 B data (100.0%)
+
+3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn percent_of_root() {
+    assert_eq!(
+        utils::run_cmd(&["--percent", "root", "tests/data"]),
+        indoc!(
+            "143 B    ┌─ cassildas_song.md (11.5%)
+ 143 B ┌─ the_yellow_king (11.5%)
+ 100 B ├─ nylarlathotep.txt (8.1%)
+ 161 B ├─ nemesis.txt (13.0%)
+  83 B ├─ necronomicon.txt (6.7%)
+ 446 B │  ┌─ lipsum.txt (35.9%)
+ 446 B ├─ lipsum (35.9%)
+ 308 B │  ┌─ polaris.txt (24.8%)
+ 308 B ├─ dream_cycle (24.8%)
+1241 B data (100.0%)
+
+3 directories, 6 files"
+        )
+    )
+}
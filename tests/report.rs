@@ -0,0 +1,58 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn report() {
+    assert_eq!(
+        utils::run_cmd(&["--report", "tests/data"]),
+        indoc!(
+            "143 B    ┌─ cassildas_song.md
+ 143 B ┌─ the_yellow_king
+ 100 B ├─ nylarlathotep.txt
+ 161 B ├─ nemesis.txt
+  83 B ├─ necronomicon.txt
+ 446 B │  ┌─ lipsum.txt
+ 446 B ├─ lipsum
+ 308 B │  ┌─ polaris.txt
+ 308 B ├─ dream_cycle
+1241 B data
+
+3 directories, 6 files
+1241 B total, 3 directories, 6 files, 0 symlinks, 0 errors"
+        )
+    )
+}
+
+#[test]
+fn report_counts_beyond_level() {
+    let out = utils::run_cmd(&["--report", "--level", "1", "tests/data"]);
+
+    assert!(out.ends_with("1241 B total, 3 directories, 6 files, 0 symlinks, 0 errors"));
+}
+
+#[test]
+fn report_counts_beyond_level_without_sizes() {
+    let out = utils::run_cmd(&["--report", "--level", "1", "--suppress-size", "tests/data"]);
+
+    assert!(out.ends_with("\n3 directories, 6 files, 0 symlinks, 0 errors"));
+}
+
+#[test]
+fn report_counts_before_top_and_filters() {
+    let out = utils::run_cmd(&["--report", "--top", "1", "tests/data"]);
+
+    assert!(out.ends_with("1241 B total, 3 directories, 6 files, 0 symlinks, 0 errors"));
+
+    let out = utils::run_cmd(&[
+        "--report",
+        "--top",
+        "1",
+        "--glob",
+        "--pattern",
+        "*.txt",
+        "tests/data",
+    ]);
+
+    assert!(out.ends_with("1098 B total, 3 directories, 5 files, 0 symlinks, 0 errors"));
+}
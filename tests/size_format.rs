@@ -0,0 +1,111 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn size_align_left_with_precision() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--human",
+            "--precision",
+            "2",
+            "--size-align",
+            "left",
+            "tests/data"
+        ]),
+        indoc!(
+            "143 B       ┌─ cassildas_song.md
+143 B    ┌─ the_yellow_king
+100 B    ├─ nylarlathotep.txt
+161 B    ├─ nemesis.txt
+83 B     ├─ necronomicon.txt
+446 B    │  ┌─ lipsum.txt
+446 B    ├─ lipsum
+308 B    │  ┌─ polaris.txt
+308 B    ├─ dream_cycle
+1.21 KiB data
+
+3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn size_width() {
+    assert_eq!(
+        utils::run_cmd(&["--size-width", "6", "--level", "1", "tests/data"]),
+        indoc!(
+            "143 B ┌─ the_yellow_king
+   100 B ├─ nylarlathotep.txt
+   161 B ├─ nemesis.txt
+    83 B ├─ necronomicon.txt
+   446 B ├─ lipsum
+   308 B ├─ dream_cycle
+  1241 B data
+
+3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn size_width_align_left() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--size-width",
+            "10",
+            "--size-align",
+            "left",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        indoc!(
+            "143 B        ┌─ the_yellow_king
+100 B        ├─ nylarlathotep.txt
+161 B        ├─ nemesis.txt
+83 B         ├─ necronomicon.txt
+446 B        ├─ lipsum
+308 B        ├─ dream_cycle
+1241 B       data
+
+3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn round_up() {
+    assert_eq!(
+        utils::run_cmd(&["--human", "--round", "up", "--level", "1", "tests/data"]),
+        indoc!(
+            "143   B ┌─ the_yellow_king
+100   B ├─ nylarlathotep.txt
+161   B ├─ nemesis.txt
+ 83   B ├─ necronomicon.txt
+446   B ├─ lipsum
+308   B ├─ dream_cycle
+1.3 KiB data
+
+3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn unit_sectors() {
+    assert_eq!(
+        utils::run_cmd(&["--unit", "sectors", "--level", "1", "tests/data"]),
+        indoc!(
+            "1 ┌─ the_yellow_king
+1 ├─ nylarlathotep.txt
+1 ├─ nemesis.txt
+1 ├─ necronomicon.txt
+1 ├─ lipsum
+1 ├─ dream_cycle
+6 data
+
+3 directories, 6 files"
+        )
+    )
+}
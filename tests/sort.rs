@@ -1,4 +1,6 @@
 use indoc::indoc;
+use std::{error::Error, fs, thread, time::Duration};
+use tempfile::TempDir;
 
 mod utils;
 
@@ -84,3 +86,192 @@ fn sort_size() {
         "Failed to sort by descending size"
     )
 }
+
+#[test]
+fn sort_version() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+
+    for name in ["v1.10.0", "v1.9.0", "file10", "file2", "file02"] {
+        fs::write(tmp.path().join(name), "")?;
+    }
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let out = utils::run_cmd(&["--sort", "version", "--suppress-size", &dir]);
+
+    assert_eq!(
+        out,
+        format!(
+            "┌─ v1.10.0\n ├─ v1.9.0\n ├─ file10\n ├─ file2\n ├─ file02\n {}\n\n5 files",
+            tmp.path().file_name().unwrap().to_string_lossy()
+        ),
+        "Failed to sort by version-aware file name"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sort_name_ci() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+
+    for name in [
+        "Yuggoth",
+        "azathoth",
+        "Älder",
+        "nyarlathotep",
+        "Éibon",
+        "eibon",
+    ] {
+        fs::write(tmp.path().join(name), "")?;
+    }
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let out = utils::run_cmd(&["--sort", "name-ci", "--suppress-size", &dir]);
+
+    assert_eq!(
+        out,
+        format!(
+            "┌─ Yuggoth\n ├─ nyarlathotep\n ├─ Éibon\n ├─ eibon\n ├─ azathoth\n ├─ Älder\n {}\n\n6 files",
+            tmp.path().file_name().unwrap().to_string_lossy()
+        ),
+        "Failed to sort by file name ignoring case and accents"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sort_ext() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+
+    for name in [
+        "nemesis.txt",
+        "dagon.md",
+        "README",
+        "azathoth.txt",
+        "cthulhu.md",
+    ] {
+        fs::write(tmp.path().join(name), "")?;
+    }
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let out = utils::run_cmd(&["--sort", "ext", "--suppress-size", &dir]);
+
+    assert_eq!(
+        out,
+        format!(
+            "┌─ nemesis.txt\n ├─ azathoth.txt\n ├─ dagon.md\n ├─ cthulhu.md\n ├─ README\n {}\n\n5 files",
+            tmp.path().file_name().unwrap().to_string_lossy()
+        ),
+        "Failed to sort by file extension"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sort_count() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+
+    let files = [
+        "celephais/ooth-nargai.txt",
+        "ulthar/cats.txt",
+        "ulthar/atal.txt",
+        "kadath/onyx/castle.txt",
+        "kadath/onyx/gods.txt",
+    ];
+
+    for file in files {
+        let path = tmp.path().join(file);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, "")?;
+    }
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let out = utils::run_cmd(&["--sort", "rcount", "--level", "1", "--suppress-size", &dir]);
+
+    assert_eq!(
+        out,
+        format!(
+            "┌─ kadath\n ├─ ulthar\n ├─ celephais\n {}\n\n4 directories, 5 files",
+            tmp.path().file_name().unwrap().to_string_lossy()
+        ),
+        "Failed to sort by descendant count"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sort_multiple_keys() {
+    assert_eq!(
+        utils::run_cmd(&["--sort", "type,name", "tests/data"]),
+        indoc!(
+            "143 B    ┌─ cassildas_song.md
+ 143 B ┌─ the_yellow_king
+ 446 B │  ┌─ lipsum.txt
+ 446 B ├─ lipsum
+ 308 B │  ┌─ polaris.txt
+ 308 B ├─ dream_cycle
+ 100 B ├─ nylarlathotep.txt
+ 161 B ├─ nemesis.txt
+  83 B ├─ necronomicon.txt
+1241 B data
+
+3 directories, 6 files"
+        ),
+        "Failed to sort by file type then by file name"
+    )
+}
+
+#[test]
+fn sort_btime() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+
+    for name in ["dagon.txt", "azathoth.txt", "cthulhu.txt"] {
+        fs::write(tmp.path().join(name), "")?;
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let out = utils::run_cmd(&["--sort", "rbtime", "--suppress-size", &dir]);
+
+    assert_eq!(
+        out,
+        format!(
+            "┌─ dagon.txt\n ├─ azathoth.txt\n ├─ cthulhu.txt\n {}\n\n3 files",
+            tmp.path().file_name().unwrap().to_string_lossy()
+        ),
+        "Failed to sort by creation time"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sort_shuffle_seeded() {
+    let sorted = utils::run_cmd(&["tests/data"]);
+    let first = utils::run_cmd(&["--shuffle", "--seed", "7", "--layout", "flat", "tests/data"]);
+    let second = utils::run_cmd(&["--shuffle", "--seed", "7", "--layout", "flat", "tests/data"]);
+
+    assert_eq!(first, second, "Expected the same order given the same seed");
+
+    let mut shuffled_names = first
+        .lines()
+        .filter_map(|line| line.split_whitespace().last())
+        .map(|path| path.rsplit('/').next().unwrap())
+        .collect::<Vec<_>>();
+
+    let mut sorted_names = sorted
+        .lines()
+        .filter_map(|line| line.split_whitespace().last())
+        .collect::<Vec<_>>();
+
+    shuffled_names.sort_unstable();
+    sorted_names.sort_unstable();
+
+    assert_eq!(
+        shuffled_names, sorted_names,
+        "Expected every entry exactly once"
+    );
+}
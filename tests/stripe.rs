@@ -0,0 +1,68 @@
+use indoc::indoc;
+use std::process::{Command, Stdio};
+
+mod utils;
+
+#[test]
+fn stripe() {
+    assert_eq!(
+        utils::run_cmd(&["--stripe", "row", "--color", "force", "tests/data"]),
+        indoc!(
+            "143 B    ┌─ cassildas_song.md
+ 143 B ┌─ the_yellow_king
+ 100 B ├─ nylarlathotep.txt
+ 161 B ├─ nemesis.txt
+  83 B ├─ necronomicon.txt
+ 446 B │  ┌─ lipsum.txt
+ 446 B ├─ lipsum
+ 308 B │  ┌─ polaris.txt
+ 308 B ├─ dream_cycle
+1241 B data
+
+3 directories, 6 files"
+        ),
+        "Failed to preserve layout when shading rows"
+    )
+}
+
+#[test]
+fn stripe_shades_alternate_rows() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "--threads",
+            "1",
+            "--disk-usage",
+            "logical",
+            "--sort",
+            "name",
+            "--no-config",
+            "--stripe",
+            "row",
+            "--color",
+            "force",
+            "tests/data",
+        ])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let rows = stdout
+        .lines()
+        .take_while(|row| !row.is_empty())
+        .collect::<Vec<_>>();
+
+    assert_eq!(rows.len(), 10);
+
+    for (i, row) in rows.iter().enumerate() {
+        assert_eq!(
+            row.starts_with("\x1b[48;5;236m"),
+            i % 2 == 1,
+            "Expected only every other row to be shaded but got '{row:?}'"
+        );
+    }
+}
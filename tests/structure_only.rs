@@ -0,0 +1,25 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn structure_only() {
+    assert_eq!(
+        utils::run_cmd(&["--structure-only", "tests/data"]),
+        indoc!(
+            "┌─ cassildas_song.md
+ ┌─ the_yellow_king
+ ├─ nylarlathotep.txt
+ ├─ nemesis.txt
+ ├─ necronomicon.txt
+ │  ┌─ lipsum.txt
+ ├─ lipsum
+ │  ┌─ polaris.txt
+ ├─ dream_cycle
+ data
+
+3 directories, 6 files"
+        ),
+        "Failed to print structure without metadata."
+    )
+}
@@ -36,4 +36,145 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn broken_links() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let target = Path::new("./tests/data/nemesis.txt").canonicalize()?;
+
+        symlink(target, tmp.path().join("intact"))?;
+        symlink(tmp.path().join("missing"), tmp.path().join("broken"))?;
+
+        let dir = tmp.path().to_string_lossy().into_owned();
+        let out = super::utils::run_cmd(&["--broken-links", "--suppress-size", &dir]);
+
+        let missing = tmp.path().join("missing");
+
+        assert_eq!(
+            out,
+            format!(
+                "┌─ broken \u{219b} {}\n {}\n\n1 link",
+                missing.display(),
+                tmp.path().file_name().unwrap().to_string_lossy()
+            ),
+            "Failed to show only broken symlinks"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn logical_layout() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let real = tmp.path().join("real");
+
+        std::fs::create_dir(&real)?;
+        std::fs::write(real.join("shunned_house.txt"), "The Shunned House\n")?;
+        symlink(&real, tmp.path().join("mirror"))?;
+
+        let dir = tmp.path().to_string_lossy().into_owned();
+        let root = tmp.path().file_name().unwrap().to_string_lossy();
+
+        let physical = super::utils::run_cmd(&["--follow", &dir]);
+        let logical = super::utils::run_cmd(&["--follow", "--logical-layout", &dir]);
+
+        assert!(
+            physical.contains(&format!("mirror \u{2192} {}", real.display())),
+            "Failed to mark followed symlink"
+        );
+        assert!(
+            physical.contains(&format!("36 B {root}")),
+            "Failed to count the file at each path it's reachable from"
+        );
+        assert!(
+            logical.contains(&format!("mirror \u{21d2} {}", real.display())),
+            "Failed to mark logically attributed symlink"
+        );
+        assert!(
+            logical.contains(&format!("18 B {root}")),
+            "Failed to count the file reachable via symlink only once"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn logical_layout_outside() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let outside = TempDir::new()?;
+        let scanned = tmp.path().join("scanned");
+
+        std::fs::create_dir(&scanned)?;
+        std::fs::write(outside.path().join("dagon.txt"), "Dagon\n")?;
+        symlink(outside.path(), scanned.join("innsmouth"))?;
+
+        let dir = scanned.to_string_lossy().into_owned();
+
+        let physical = super::utils::run_cmd(&["--follow", &dir]);
+        let logical = super::utils::run_cmd(&["--follow", "--logical-layout", &dir]);
+
+        assert!(
+            physical.lines().any(|line| line.trim() == "6 B scanned"),
+            "Failed to count the target outside of the tree towards the symlink"
+        );
+        assert!(
+            logical.lines().any(|line| line.trim() == "6 B scanned"),
+            "Failed to attribute the target outside of the tree to the symlink"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn follow_sums_targets() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let outside = TempDir::new()?;
+        let scanned = tmp.path().join("scanned");
+        let vault = outside.path().join("vault");
+
+        std::fs::create_dir(&scanned)?;
+        std::fs::create_dir(&vault)?;
+        std::fs::write(outside.path().join("azathoth.txt"), "Azathoth\n")?;
+        std::fs::write(vault.join("yog_sothoth.txt"), "Yog-Sothoth\n")?;
+        std::fs::write(scanned.join("nyarlathotep.txt"), "Nyarlathotep\n")?;
+        symlink(
+            outside.path().join("azathoth.txt"),
+            scanned.join("azathoth"),
+        )?;
+        symlink(&vault, scanned.join("vault"))?;
+
+        let dir = scanned.to_string_lossy().into_owned();
+
+        let out = super::utils::run_cmd(&["--follow", &dir]);
+
+        assert!(
+            out.lines().any(|line| line.trim() == "34 B scanned"),
+            "Failed to sum the sizes of followed symlink targets"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn follow_cycle() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let nested = tmp.path().join("nested");
+
+        std::fs::create_dir(&nested)?;
+        std::fs::write(nested.join("the_hound.txt"), "The Hound\n")?;
+        symlink("..", nested.join("up"))?;
+
+        let dir = tmp.path().to_string_lossy().into_owned();
+        let root = tmp.path().file_name().unwrap().to_string_lossy();
+
+        let out = super::utils::run_cmd(&["--follow", "--logical-layout", &dir]);
+
+        assert_eq!(
+            out,
+            format!("10 B    ┌─ the_hound.txt\n10 B ┌─ nested\n10 B {root}\n\n1 directory, 1 file"),
+            "Failed to break symlink cycle"
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,48 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn top() {
+    assert_eq!(
+        utils::run_cmd(&["--top", "2", "tests/data"]),
+        indoc!(
+            "┌─ … 4 more (487 B)
+ 446 B │  ┌─ lipsum.txt
+ 446 B ├─ lipsum
+ 308 B │  ┌─ polaris.txt
+ 308 B ├─ dream_cycle
+1241 B data
+
+2 directories, 2 files"
+        ),
+        "Failed to collapse entries beyond the limit"
+    )
+}
+
+#[test]
+fn top_inverted() {
+    assert_eq!(
+        utils::run_cmd(&["--top", "2", "--layout", "inverted", "tests/data"]),
+        indoc!(
+            "1241 B data
+ 308 B ├─ dream_cycle
+ 308 B │  └─ polaris.txt
+ 446 B ├─ lipsum
+ 446 B │  └─ lipsum.txt
+       └─ … 4 more (487 B)
+
+2 directories, 2 files"
+        ),
+        "Failed to collapse entries beyond the limit with inverted layout"
+    )
+}
+
+#[test]
+fn max_children() {
+    assert_eq!(
+        utils::run_cmd(&["--max-children", "2", "tests/data"]),
+        utils::run_cmd(&["--top", "2", "tests/data"]),
+        "Failed to treat '--max-children' as an alias of '--top'"
+    )
+}
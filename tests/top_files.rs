@@ -0,0 +1,22 @@
+use std::{error::Error, fs};
+
+mod utils;
+
+#[test]
+fn top_files() -> Result<(), Box<dyn Error>> {
+    let data = fs::canonicalize("tests/data")?;
+
+    assert_eq!(
+        utils::run_cmd(&["--top-files", "3", "tests/data"]),
+        format!(
+            "446 B   {}
+ 308 B   {}
+ 161 B   {}",
+            data.join("lipsum").join("lipsum.txt").display(),
+            data.join("dream_cycle").join("polaris.txt").display(),
+            data.join("nemesis.txt").display(),
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use std::{
+    error::Error,
+    fs,
+    process::{Command, Stdio},
+};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn verbose() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+
+    fs::write(tmp.path().join(".ignore"), "*.log\n")?;
+    fs::write(tmp.path().join(".hidden"), "")?;
+    fs::write(tmp.path().join("azathoth.log"), "")?;
+    fs::write(tmp.path().join("nyarlathotep.txt"), "")?;
+    fs::write(tmp.path().join("yog-sothoth.md"), "")?;
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let args = ["--suppress-size", "--glob", "--pattern", "*.txt", &dir];
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "--threads",
+            "1",
+            "--sort",
+            "name",
+            "--no-config",
+        ])
+        .arg("--verbose")
+        .args(args)
+        .stdin(Stdio::null())
+        .output()?;
+
+    assert_eq!(
+        String::from_utf8(strip_ansi_escapes::strip(output.stdout)?)?.trim(),
+        utils::run_cmd(&args),
+        "Expected '--verbose' to leave the output as is"
+    );
+
+    assert_eq!(
+        String::from_utf8(output.stderr)?
+            .lines()
+            .last()
+            .unwrap_or_default(),
+        ".: 2 hidden, 1 ignored, 1 unmatched",
+        "Failed to tally the entries that were filtered out"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+mod utils;
+
+#[test]
+fn version() {
+    let out = utils::run_cmd(&["--version"]);
+    let mut lines = out.lines();
+
+    assert_eq!(
+        lines.next(),
+        Some(concat!("erdtree ", env!("CARGO_PKG_VERSION")))
+    );
+
+    let fields = lines
+        .map(|line| line.split_once(':').unwrap().0)
+        .collect::<Vec<_>>();
+
+    assert_eq!(fields, ["commit", "target", "features", "backends"]);
+
+    let backends = out.lines().last().unwrap();
+
+    for backend in ["xattr", "acl", "btrfs", "chflags"] {
+        assert!(
+            backends.contains(&format!("+{backend}")) || backends.contains(&format!("-{backend}")),
+            "Expected {backend} among the backends: {backends}"
+        );
+    }
+
+    assert!(!backends.contains("uring") && !backends.contains("windows-acl"));
+}
+
+#[test]
+fn version_json() {
+    let out = utils::run_cmd(&["--version", "--json"]);
+    let info = serde_json::from_str::<serde_json::Value>(&out).unwrap();
+
+    assert_eq!(info["version"], env!("CARGO_PKG_VERSION"));
+    assert!(info["commit"].is_string());
+    assert!(info["target"].is_string());
+    assert!(info["features"].is_array());
+
+    let backends = info["backends"].as_object().unwrap();
+    let mut names = backends.keys().map(String::as_str).collect::<Vec<_>>();
+    names.sort_unstable();
+
+    assert_eq!(names, ["acl", "btrfs", "chflags", "xattr"]);
+    assert!(backends.values().all(serde_json::Value::is_boolean));
+}
+
+#[test]
+fn version_ignores_invalid_arguments() {
+    let out = utils::run_cmd(&["--sort-by", "size", "--version"]);
+
+    assert!(
+        out.starts_with("erdtree "),
+        "Expected --version to be reported before arguments are validated: {out}"
+    );
+}
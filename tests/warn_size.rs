@@ -0,0 +1,50 @@
+use indoc::indoc;
+use std::process::{Command, Stdio};
+
+mod utils;
+
+#[test]
+fn warn_size_within_budget() {
+    assert_eq!(
+        utils::run_cmd(&["--warn-size", "2K", "--fail-on-warn", "tests/data"]),
+        indoc!(
+            "143 B    ┌─ cassildas_song.md
+ 143 B ┌─ the_yellow_king
+ 100 B ├─ nylarlathotep.txt
+ 161 B ├─ nemesis.txt
+  83 B ├─ necronomicon.txt
+ 446 B │  ┌─ lipsum.txt
+ 446 B ├─ lipsum
+ 308 B │  ┌─ polaris.txt
+ 308 B ├─ dream_cycle
+1241 B data
+
+3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn warn_size_over_budget() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "--disk-usage",
+            "logical",
+            "--no-config",
+            "--warn-size",
+            "300",
+            "--fail-on-warn",
+            "tests/data",
+        ])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(stderr.contains("5 entries exceeded the size given to '--warn-size'"));
+}
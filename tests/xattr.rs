@@ -0,0 +1,129 @@
+#![cfg(unix)]
+
+use std::{error::Error, ffi::CString, fs, os::unix::ffi::OsStrExt, path::Path};
+use tempfile::TempDir;
+
+mod utils;
+
+/// Sets the extended attribute `name` of `path`, returning `false` if the filesystem doesn't
+/// support user extended attributes.
+fn set_xattr(path: &Path, name: &str, value: &[u8]) -> bool {
+    let path = CString::new(path.as_os_str().as_bytes()).unwrap();
+    let name = CString::new(name).unwrap();
+
+    #[cfg(not(target_os = "macos"))]
+    let ret = unsafe {
+        libc::setxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            value.as_ptr().cast(),
+            value.len(),
+            0,
+        )
+    };
+
+    #[cfg(target_os = "macos")]
+    let ret = unsafe {
+        libc::setxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            value.as_ptr().cast(),
+            value.len(),
+            0,
+            0,
+        )
+    };
+
+    ret == 0
+}
+
+#[test]
+fn list_xattrs() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let file = tmp.path().join("carcosa.txt");
+
+    fs::write(&file, "")?;
+
+    if !set_xattr(&file, "user.king", b"yellow") {
+        return Ok(());
+    }
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+
+    let sizes = utils::run_cmd(&["--list-xattrs", &dir]);
+
+    assert!(
+        sizes.contains("0 B ┌─ carcosa.txt\n    │  user.king (6 B)\n"),
+        "Expected the attribute and its size beneath the file: {sizes}"
+    );
+
+    let values = utils::run_cmd(&["-@", "--xattr-values", &dir]);
+
+    assert!(
+        values.contains("│  user.king = \"yellow\"\n"),
+        "Expected the value of the attribute: {values}"
+    );
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn security_context() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let file = tmp.path().join("carcosa.txt");
+
+    fs::write(&file, "")?;
+
+    // Setting security attributes requires privileges that may be missing.
+    if !set_xattr(&file, "security.selinux", b"system_u:object_r:tmp_t:s0\0") {
+        return Ok(());
+    }
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+    let out = utils::run_cmd(&["--context", &dir]);
+
+    let row = out
+        .lines()
+        .find(|line| line.ends_with("carcosa.txt"))
+        .unwrap();
+
+    assert!(
+        row.starts_with("system_u:object_r:tmp_t:s0 "),
+        "Expected the security context to lead the row: {row}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn xattr_filter() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let carcosa = tmp.path().join("carcosa");
+    let hali = tmp.path().join("hali");
+
+    fs::create_dir(&carcosa)?;
+    fs::create_dir(&hali)?;
+    fs::write(carcosa.join("cassilda.txt"), "")?;
+    fs::write(hali.join("camilla.txt"), "")?;
+    fs::write(hali.join("hastur.txt"), "")?;
+
+    if !set_xattr(&carcosa, "user.king", b"yellow")
+        || !set_xattr(&hali.join("hastur.txt"), "user.king", b"yellow")
+    {
+        return Ok(());
+    }
+
+    let dir = tmp.path().to_string_lossy().into_owned();
+
+    assert_eq!(
+        utils::run_cmd(&["--xattr", "user.king", "--suppress-size", &dir]),
+        format!(
+            "┌─ hastur.txt\n ┌─ hali\n ├─ carcosa\n {}\n\n2 directories, 1 file",
+            tmp.path().file_name().unwrap().to_string_lossy()
+        ),
+        "Expected the directory and the file carrying the attribute but nothing else"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+#[cfg(target_os = "linux")]
+mod test {
+    use std::{
+        error::Error,
+        fs,
+        path::Path,
+        process::{Command, Stdio},
+    };
+    use tempfile::TempDir;
+
+    /// Same as `utils::run_cmd` but with the XDG base directories in `envs` set for the child
+    /// process alone, as setting them for the test process would race with other tests.
+    fn run_cmd_with_env(args: &[&str], envs: &[(&str, &Path)]) -> String {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--",
+                "--threads",
+                "1",
+                "--disk-usage",
+                "logical",
+                "--sort",
+                "name",
+                "--no-config",
+            ])
+            .args(args)
+            .envs(envs.iter().copied())
+            .stdin(Stdio::null())
+            .output()
+            .unwrap();
+
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        String::from_utf8(strip_ansi_escapes::strip(output.stdout).unwrap())
+            .unwrap()
+            .trim()
+            .to_string()
+    }
+
+    #[test]
+    fn xdg() -> Result<(), Box<dyn Error>> {
+        let tmp = TempDir::new()?;
+        let cache = tmp.path().join("cache");
+
+        fs::create_dir(&cache)?;
+        fs::create_dir(tmp.path().join("documents"))?;
+        fs::write(cache.join("thumbnails.db"), "")?;
+        fs::write(tmp.path().join("documents").join("pnakotic.txt"), "")?;
+
+        let dir = tmp.path().to_string_lossy().into_owned();
+        let out = run_cmd_with_env(
+            &["--xdg", "--suppress-size", &dir],
+            &[("XDG_CACHE_HOME", &cache)],
+        );
+
+        assert_eq!(
+            out,
+            format!(
+                "┌─ pnakotic.txt\n ┌─ documents\n │  ┌─ thumbnails.db\n ├─ cache [cache]\n {} [temp]\n\n2 directories, 2 files",
+                tmp.path().file_name().unwrap().to_string_lossy()
+            ),
+            "Failed to annotate XDG base directories"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn xdg_summary() -> Result<(), Box<dyn Error>> {
+        let tmp = TempDir::new()?;
+        let cache = tmp.path().join("cache");
+        let data = cache.join("data");
+
+        fs::create_dir_all(&data)?;
+        fs::create_dir(tmp.path().join("documents"))?;
+        fs::write(cache.join("thumbnails.db"), "0123456789")?;
+        fs::write(data.join("shoggoth.db"), "abcd")?;
+        fs::write(tmp.path().join("documents").join("pnakotic.txt"), "xy")?;
+
+        let dir = tmp.path().to_string_lossy().into_owned();
+        let out = run_cmd_with_env(
+            &["--xdg", &dir],
+            &[("XDG_CACHE_HOME", &cache), ("XDG_DATA_HOME", &data)],
+        );
+
+        assert_eq!(
+            out,
+            format!(
+                "2 B    ┌─ pnakotic.txt\n 2 B ┌─ documents\n10 B │  ┌─ thumbnails.db\n 4 B │  │  ┌─ shoggoth.db\n 4 B │  ├─ data [data]\n14 B ├─ cache [cache]\n16 B {} [temp]\n\n3 directories, 3 files\n10 B cache, 4 B data, 2 B temp",
+                tmp.path().file_name().unwrap().to_string_lossy()
+            ),
+            "Failed to total usage per XDG category"
+        );
+
+        Ok(())
+    }
+}